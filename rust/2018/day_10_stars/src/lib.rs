@@ -1,44 +1,32 @@
-#[macro_use]
-extern crate nom;
-extern crate image;
-extern crate tesseract;
-
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec2;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{i32, space0},
+    combinator::map,
+    sequence::{delimited, preceded, separated_pair},
+    IResult,
+};
 use std::cmp::max;
 use std::cmp::min;
-use std::str::FromStr;
 
 pub fn read_stars(input: &str) -> String {
-    let stars = Stars::read(input);
-    let imgbuf = stars.draw(stars.bounding_box_minima());
-    imgbuf.save("img.png").unwrap();
-    tesseract::ocr("img.png", "eng").trim().to_string()
+    aoc_core::part1::<Stars>(input).unwrap()
 }
 
-#[derive(Debug)]
-struct VectorXY {
-    x: i32,
-    y: i32,
+type VectorXY = Vec2<i32>;
+
+fn parse_vector(input: &str) -> IResult<&str, VectorXY> {
+    map(
+        delimited(
+            tag("<"),
+            separated_pair(preceded(space0, i32), tag(","), preceded(space0, i32)),
+            tag(">"),
+        ),
+        |(x, y)| VectorXY { x, y },
+    )(input)
 }
 
-named!(
-        parse_vector<&str, VectorXY>,
-        do_parse!(
-            tag_s!("<")
-            >> take_while!(|c| c==' ')
-            >> x_sign: alt!(tag_s!("-") | tag_s!(""))
-            >> x_value: map_res!(nom::digit, FromStr::from_str)
-            >> tag_s!(",")
-            >> take_while!(|c| c==' ')
-            >> y_sign: alt!(tag_s!("-") | tag_s!(""))
-            >> y_value: map_res!(nom::digit, FromStr::from_str)
-            >> tag_s!(">")
-            >> (VectorXY {
-                x: if x_sign == "-" { 0-x_value } else { x_value },
-                y: if y_sign == "-" { 0-y_value } else { y_value },
-            })
-        )
-    );
-
 #[derive(Debug)]
 struct Star {
     initial: VectorXY,
@@ -47,62 +35,34 @@ struct Star {
 
 impl Star {
     fn advance(&self, seconds: i16) -> VectorXY {
-        VectorXY {
-            x: self.initial.x + self.velocity.x * i32::from(seconds),
-            y: self.initial.y + self.velocity.y * i32::from(seconds),
-        }
+        self.initial + self.velocity * i32::from(seconds)
     }
 }
 
-struct Stars(Vec<Star>);
+pub struct Stars(Vec<Star>);
 
 impl Stars {
-    fn read(input: &str) -> Self {
-        Stars(
-            input
-                .lines()
-                .map(parse_star)
-                .filter_map(|ms| ms.ok().map(|rs| rs.1))
-                .collect(),
-        )
+    fn read(input: &str) -> Result<Self, ParseError> {
+        input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| aoc_core::finish(input, parse_star(line)))
+            .collect::<Result<_, _>>()
+            .map(Stars)
     }
 
     fn advance<'a>(&'a self, seconds: i16) -> impl Iterator<Item = VectorXY> + 'a {
         self.0.iter().map(move |star| star.advance(seconds))
     }
 
-    fn draw(&self, seconds: i16) -> image::ImageBuffer<image::Luma<u8>, Vec<u8>> {
-        let bounding_box = self.bounding_box(seconds);
-        let x_min = bounding_box.0;
-        let y_min = bounding_box.1;
-        let mut imgbuf = image::ImageBuffer::new(
-            2 * (bounding_box.2 - bounding_box.0 + 1) as u32,
-            2 * (bounding_box.3 - bounding_box.1 + 1) as u32,
-        );
+    fn draw(&self, seconds: i16) -> Vec<Vec<bool>> {
+        let (x_min, y_min, x_max, y_max) = self.bounding_box(seconds);
+        let mut pixels =
+            vec![vec![false; (x_max - x_min + 1) as usize]; (y_max - y_min + 1) as usize];
         for v in self.advance(seconds) {
-            imgbuf.put_pixel(
-                (v.x - x_min) as u32 * 2,
-                (v.y - y_min) as u32 * 2,
-                image::Luma([255u8]),
-            );
-            imgbuf.put_pixel(
-                (v.x - x_min) as u32 * 2 + 1,
-                (v.y - y_min) as u32 * 2,
-                image::Luma([255u8]),
-            );
-            imgbuf.put_pixel(
-                (v.x - x_min) as u32 * 2,
-                (v.y - y_min) as u32 * 2 + 1,
-                image::Luma([255u8]),
-            );
-            imgbuf.put_pixel(
-                (v.x - x_min) as u32 * 2 + 1,
-                (v.y - y_min) as u32 * 2 + 1,
-                image::Luma([255u8]),
-            );
+            pixels[(v.y - y_min) as usize][(v.x - x_min) as usize] = true;
         }
-
-        imgbuf
+        pixels
     }
 
     fn bounding_box(&self, seconds: i16) -> (i32, i32, i32, i32) {
@@ -144,45 +104,60 @@ impl Stars {
     }
 }
 
-named!(
-        parse_star<&str, Star>,
-        do_parse!(
-            tag_s!("position=")
-            >> initial: parse_vector
-            >> tag_s!(" velocity=")
-            >> velocity: parse_vector
-            >> ( Star { initial, velocity } )
-        )
-    );
+impl Solution for Stars {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = i16;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Stars::read(input)?)
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        aoc_ocr::read(&self.draw(self.bounding_box_minima()))
+    }
+
+    fn part2(&self, _: &()) -> Result<i16, Error> {
+        Ok(self.bounding_box_minima())
+    }
+}
+
+fn parse_star(input: &str) -> IResult<&str, Star> {
+    map(
+        separated_pair(
+            preceded(tag("position="), parse_vector),
+            tag(" velocity="),
+            parse_vector,
+        ),
+        |(initial, velocity)| Star { initial, velocity },
+    )(input)
+}
 
 #[cfg(test)]
 mod read_stars_tests {
-    use read_stars;
-    use Stars;
+    use crate::{read_stars, Stars};
 
     #[test]
-    #[ignore]
     fn worked_example() {
         assert_eq!(
-            read_stars(include_str!("../worked_example.txt")),
-            "HI".to_string()
+            Stars::read(include_str!("../worked_example.txt"))
+                .unwrap()
+                .bounding_box_minima(),
+            3
         );
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(
-            read_stars(include_str!("../input.txt")),
-            "ZRHBXXJE".to_string() // actually it's ZRABXXJC
-        );
+        let input = aoc_input::challenge(2018, 10);
+        assert_eq!(read_stars(&input), "ZRABXXJC".to_string());
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle_wait_time() {
-        assert_eq!(
-            Stars::read(include_str!("../input.txt")).bounding_box_minima(),
-            10710
-        );
+        let input = aoc_input::challenge(2018, 10);
+        assert_eq!(Stars::read(&input).unwrap().bounding_box_minima(), 10710);
     }
-
 }
@@ -1,3 +1,5 @@
+use aoc_core::{Error, Solution};
+
 pub fn highest_total_square(
     serial_number: usize,
     square_size: usize,
@@ -68,7 +70,7 @@ impl FuelCell {
     }
 }
 
-struct FuelGrid {
+pub struct FuelGrid {
     summed_square_tables: SummedSquareTable,
 }
 
@@ -86,7 +88,7 @@ impl FuelGrid {
         for x in 1..=301 - s {
             for y in 1..=301 - s {
                 let current = self.summed_square_tables.box_sum(x, y, s);
-                if max.map_or(true, |m| m < current) {
+                if max.is_none_or(|m| m < current) {
                     max = Some(current);
                     best = Some((x, y));
                 }
@@ -105,9 +107,37 @@ impl FuelGrid {
     }
 }
 
+impl Solution for FuelGrid {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let serial_number = input
+            .trim()
+            .parse()
+            .map_err(|_| Error::parse(input, input.trim(), "a serial number"))?;
+        Ok(FuelGrid::new(serial_number))
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        let (_, x, y) = self
+            .best_square_for_size(3)
+            .ok_or_else(|| Error::no_answer("no square"))?;
+        Ok(format!("{},{}", x, y))
+    }
+
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        let (_, x, y, size) = self
+            .best_square_overall()
+            .ok_or_else(|| Error::no_answer("no square"))?;
+        Ok(format!("{},{},{}", x, y, size))
+    }
+}
+
 #[cfg(test)]
 mod highest_3_by_3_total_square_tests {
-    use highest_total_square;
+    use crate::highest_total_square;
 
     #[test]
     fn worked_example_1() {
@@ -127,7 +157,7 @@ mod highest_3_by_3_total_square_tests {
 
 #[cfg(test)]
 mod highest_variable_square_size_tests {
-    use highest_variable_square;
+    use crate::highest_variable_square;
 
     #[test]
     fn worked_example_1() {
@@ -141,5 +171,4 @@ mod highest_variable_square_size_tests {
     fn puzzle() {
         assert_eq!(highest_variable_square(5034), Some((109, 229, 251, 16)));
     }
-
 }
@@ -1,99 +1,118 @@
-#[macro_use]
-extern crate nom;
-
+use aoc_automaton::{Automaton, Board, RuleTable, Sparse};
+use aoc_core::{Error, ParseError, Solution};
+use aoc_cycle::simulate_until;
+use nom::{
+    bytes::complete::{tag, take_while, take_while_m_n},
+    combinator::map,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
 use std::collections::BTreeSet;
-use std::collections::HashSet;
 use std::iter::FromIterator;
 
-type Rule = (bool, bool, bool, bool, bool);
+type Rule = [bool; 5];
 
+#[derive(Clone)]
 pub struct Cave {
-    pots: BTreeSet<i64>,
-    rules: HashSet<Rule>,
+    pots: Automaton<i64>,
+    rules: RuleTable,
 }
 
 impl Cave {
-    pub fn try_new(input: &str) -> Result<Self, (nom::Err<&str>)> {
-        let (input, pots) = parse_pots(input)?;
-        let mut rules = HashSet::new();
-        for line in input.lines().filter(|l| !l.is_empty()) {
-            if let (_, Some(rule)) = parse_rule(line)? {
-                rules.insert(rule);
+    pub fn try_new(input: &str) -> Result<Self, ParseError> {
+        let (rest, pots) = parse_pots(input).map_err(|e| ParseError::from_nom(input, e))?;
+        let mut rules = RuleTable::new(2);
+        for line in rest.lines().filter(|l| !l.is_empty()) {
+            if let Some(rule) = aoc_core::finish(input, parse_rule(line))? {
+                rules.insert(&rule);
             }
         }
-        Ok(Cave { pots, rules })
+        Ok(Cave {
+            pots: Automaton::new(Sparse::from_cells(pots)),
+            rules,
+        })
     }
 
-    fn pots(&self) -> &BTreeSet<i64> {
-        &self.pots
+    fn pot_index_sum(&self) -> i64 {
+        self.pots.board().live_cells().iter().sum()
     }
 
-    fn rules(&self) -> &HashSet<Rule> {
-        &self.rules
+    pub fn generation_sum(self, generations: u64) -> i64 {
+        let initial_sum = self.pot_index_sum();
+        let growth = simulate_until(
+            self,
+            |cave| {
+                let sum = cave.pot_index_sum();
+                cave.step();
+                cave.pot_index_sum() - sum
+            },
+            // patterns that match grow and move the same way wherever they are
+            |cave| cave.pots.board().shape_hash(),
+            generations,
+        );
+        initial_sum + growth
     }
 
-    fn pot_index_sum(&self) -> i64 {
-        self.pots().iter().cloned().sum()
+    fn step(&mut self) {
+        self.pots.step_table(&self.rules);
     }
+}
 
-    pub fn generation_sum(mut self, generations: i64) -> i64 {
-        let mut pot_counts = (None, None, None);
-        for i in 0..generations {
-            pot_counts = (pot_counts.1, pot_counts.2, Some(self.pot_index_sum()));
-            if let (Some(a), Some(b), Some(c)) = pot_counts {
-                if c - b == b - a {
-                    return (generations - i) * (b - a) + c;
-                }
-            }
-            self = self.step();
-        }
-        self.pot_index_sum()
+impl Solution for Cave {
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Cave::try_new(input)?)
     }
 
-    fn step(mut self) -> Self {
-        if let (Some(first), Some(last)) =
-            (self.pots().iter().next(), self.pots().iter().rev().next())
-        {
-            let mut new_pots = BTreeSet::new();
-            for pot_num in first - 2..=last + 2 {
-                let t = |i| self.pots().contains(&(pot_num + i));
-                let active_rule = (t(-2), t(-1), t(0), t(1), t(2));
-                if self.rules().contains(&active_rule) {
-                    new_pots.insert(pot_num);
-                }
-            }
-            self.pots = new_pots;
-        };
-        self
+    fn part1(&self, _: &()) -> Result<i64, Error> {
+        Ok(self.clone().generation_sum(20))
+    }
+
+    fn part2(&self, _: &()) -> Result<i64, Error> {
+        Ok(self.clone().generation_sum(50_000_000_000))
     }
 }
 
-named!(parse_pots < &str,BTreeSet<i64>>,
-    do_parse!(
-        tag_s!("initial state: ")
-            >> pots: take_while!(|c| c == '#' || c== '.')
-            >> (
-                BTreeSet::from_iter(pots.chars().enumerate().filter_map(|(i, l)| if l == '#' {Some(i as i64)} else {None}))
-            )
-    )
-);
-
-named!(parse_rule< &str, Option<Rule>>,
-    do_parse!(
-            rule: take_while_m_n!(5, 5, |c| c == '#' || c== '.')
-            >> tag_s!(" => ")
-            >> result: take_while_m_n!(1, 1, |c| c == '#' || c == '.')
-            >> (
-                if result == "#" {
-                    let mut rs = rule.chars();
-                    let mut t = || rs.next() == Some('#');
-                    Some((t(), t(), t(), t(), t()))
+fn is_pot(c: char) -> bool {
+    c == '#' || c == '.'
+}
+
+fn parse_pots(input: &str) -> IResult<&str, BTreeSet<i64>> {
+    map(
+        preceded(tag("initial state: "), take_while(is_pot)),
+        |pots: &str| {
+            BTreeSet::from_iter(pots.chars().enumerate().filter_map(|(i, l)| {
+                if l == '#' {
+                    Some(i as i64)
                 } else {
                     None
                 }
-            )
-    )
-);
+            }))
+        },
+    )(input)
+}
+
+fn parse_rule(input: &str) -> IResult<&str, Option<Rule>> {
+    map(
+        separated_pair(
+            take_while_m_n(5, 5, is_pot),
+            tag(" => "),
+            take_while_m_n(1, 1, is_pot),
+        ),
+        |(rule, result): (&str, &str)| {
+            if result == "#" {
+                let mut rs = rule.chars();
+                let mut t = || rs.next() == Some('#');
+                Some([t(), t(), t(), t(), t()])
+            } else {
+                None
+            }
+        },
+    )(input)
+}
 
 #[cfg(test)]
 mod worked_example_part_1 {
@@ -1,44 +1,56 @@
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::{Direction, Vec2};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 use std::str::FromStr;
 
+pub type Location = Vec2<i16>;
+
+/// How long to run the carts for before giving up on them crashing. The puzzle's
+/// carts are down to one after about twelve thousand ticks.
+const MAX_TICKS: usize = 100_000;
+
+#[derive(Clone)]
 pub struct Simulation {
     track: Track,
     carts: Vec<Cart>,
 }
 
 impl Simulation {
-    pub fn first_crash(&mut self) -> (u8, u8) {
-        loop {
-            if let Some(crash) = self.move_or_crash() {
-                return crash;
+    pub fn first_crash(&mut self) -> Result<Location, Error> {
+        for _ in 0..MAX_TICKS {
+            if let Some(crash) = self.move_or_crash()? {
+                return Ok(crash);
             }
         }
+        Err(Error::no_answer(format!(
+            "no carts crashed within {MAX_TICKS} ticks"
+        )))
     }
 
-    pub fn last_cart(&mut self) -> (u8, u8) {
-        loop {
-            self.move_or_crash();
-            match (self.carts.get(0), self.carts.get(1)) {
-                (Some(cart), None) => return cart.location,
+    pub fn last_cart(&mut self) -> Result<Location, Error> {
+        for _ in 0..MAX_TICKS {
+            self.move_or_crash()?;
+            match (self.carts.first(), self.carts.get(1)) {
+                (Some(cart), None) => return Ok(cart.location),
                 (Some(_a), Some(_b)) => {}
-                _ => panic!("too many crashes"),
+                _ => return Err(Error::no_answer("every cart crashed")),
             }
         }
+        Err(Error::no_answer(format!(
+            "more than one cart was left after {MAX_TICKS} ticks"
+        )))
     }
 
     fn carts(&self) -> impl Iterator<Item = &Cart> {
         self.carts.iter()
     }
 
-    fn move_or_crash(&mut self) -> Option<(u8, u8)> {
-        self.carts.sort_by_key(|cart| {
-            let (x, y) = cart.location;
-            (y, x)
-        });
+    fn move_or_crash(&mut self) -> Result<Option<Location>, Error> {
+        self.carts.sort_by_key(|cart| cart.location);
         let mut first_crash = None;
-        let mut cart_locations: HashMap<(u8, u8), (u8, u8)> =
+        let mut cart_locations: HashMap<Location, Location> =
             HashMap::from_iter(self.carts().map(|c| (c.location, c.id)));
         let mut crashed_carts = HashSet::new();
         for cart in self.carts.iter_mut() {
@@ -47,6 +59,12 @@ impl Simulation {
             }
             cart_locations.remove(&cart.location);
             cart.update(&self.track);
+            if !self.track.laid.contains(&cart.location) {
+                return Err(Error::no_answer(format!(
+                    "a cart ran off the track at {}",
+                    cart.location
+                )));
+            }
             if let Some(other_cart) = cart_locations.insert(cart.location, cart.id) {
                 first_crash = Some(first_crash.unwrap_or(cart.location));
                 crashed_carts.insert(cart.id);
@@ -55,19 +73,19 @@ impl Simulation {
             }
         }
         self.carts.retain(|cart| !crashed_carts.contains(&cart.id));
-        first_crash
+        Ok(first_crash)
     }
 }
 
 impl FromStr for Simulation {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut carts = Vec::new();
 
         for (y, line) in s.lines().enumerate() {
             for (x, t) in line.chars().enumerate() {
-                let (x, y) = (x as u8, y as u8);
+                let location = Location::new(x as i16, y as i16);
                 use crate::Direction::*;
                 let cart_direction = match t {
                     '^' => Some(North),
@@ -77,11 +95,14 @@ impl FromStr for Simulation {
                     _ => None,
                 };
                 if let Some(d) = cart_direction {
-                    carts.push(Cart::new((x, y), d, IntersectionBehaviour::Left));
+                    carts.push(Cart::new(location, d, IntersectionBehaviour::Left));
                 }
             }
         }
 
+        if carts.len() < 2 {
+            return Err(ParseError::at_end(s, "at least two carts"));
+        }
         Ok(Simulation {
             track: s.parse()?,
             carts,
@@ -89,17 +110,35 @@ impl FromStr for Simulation {
     }
 }
 
-#[derive(PartialEq, Debug)]
+impl Solution for Simulation {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        Ok(self.clone().first_crash()?.to_string())
+    }
+
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        Ok(self.clone().last_cart()?.to_string())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 struct Cart {
-    id: (u8, u8),
-    location: (u8, u8),
+    id: Location,
+    location: Location,
     direction: Direction,
     intersection_behaviour: IntersectionBehaviour,
 }
 
 impl Cart {
     fn new(
-        location: (u8, u8),
+        location: Location,
         direction: Direction,
         intersection_behaviour: IntersectionBehaviour,
     ) -> Self {
@@ -115,12 +154,7 @@ impl Cart {
         use crate::Direction::{East, North, South, West};
         use crate::IntersectionBehaviour::{Left, Right, Straight};
         use crate::TrackDirection::{Intersection, PrimaryDiagonal, SecondaryDiagonal};
-        self.location = match &self.direction {
-            North => (self.location.0, self.location.1 - 1),
-            East => (self.location.0 + 1, self.location.1),
-            South => (self.location.0, self.location.1 + 1),
-            West => (self.location.0 - 1, self.location.1),
-        };
+        self.location += self.direction.offset();
 
         self.direction = match (
             self.direction,
@@ -160,43 +194,40 @@ impl Cart {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 enum IntersectionBehaviour {
     Left,
     Straight,
     Right,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Track {
-    tracks: HashMap<(u8, u8), TrackDirection>,
+    tracks: HashMap<Location, TrackDirection>,
+    /// Everywhere there's track, straight or not.
+    laid: HashSet<Location>,
 }
 
 impl FromStr for Track {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut track = Track::default();
         for (y, line) in s.lines().enumerate() {
-            for (x, t) in line.chars().enumerate() {
-                let (x, y) = (x as u8, y as u8);
+            for (x, (i, t)) in line.char_indices().enumerate() {
+                let location = Location::new(x as i16, y as i16);
                 use crate::TrackDirection::*;
                 let track_segment = match t {
                     '\\' => Some(PrimaryDiagonal),
                     '/' => Some(SecondaryDiagonal),
                     '+' => Some(Intersection),
-                    _ => None,
+                    '-' | '|' | '^' | 'v' | '<' | '>' => None,
+                    ' ' => continue,
+                    _ => return Err(ParseError::new(s, &line[i..], "a piece of track or a cart")),
                 };
+                track.laid.insert(location);
                 if let Some(segment) = track_segment {
-                    track.tracks.insert((x, y), segment);
+                    track.tracks.insert(location, segment);
                 }
             }
         }
@@ -204,7 +235,7 @@ impl FromStr for Track {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 enum TrackDirection {
     PrimaryDiagonal,   // \
     SecondaryDiagonal, // /
@@ -216,6 +247,7 @@ mod worked_example_part_1 {
     use crate::Cart;
     use crate::Direction;
     use crate::IntersectionBehaviour;
+    use crate::Location;
     use crate::Simulation;
 
     fn simulation() -> Simulation {
@@ -227,20 +259,29 @@ mod worked_example_part_1 {
         assert_eq!(
             simulation().carts().collect::<Vec<_>>(),
             vec!(
-                &Cart::new((2, 0), Direction::East, IntersectionBehaviour::Left),
-                &Cart::new((9, 3), Direction::South, IntersectionBehaviour::Left)
+                &Cart::new(
+                    Location::new(2, 0),
+                    Direction::East,
+                    IntersectionBehaviour::Left
+                ),
+                &Cart::new(
+                    Location::new(9, 3),
+                    Direction::South,
+                    IntersectionBehaviour::Left
+                )
             )
         );
     }
 
     #[test]
     fn first_crash() {
-        assert_eq!(simulation().first_crash(), (7, 3));
+        assert_eq!(simulation().first_crash().unwrap(), Location::new(7, 3));
     }
 }
 
 #[cfg(test)]
 mod worked_example_part_2 {
+    use crate::Location;
     use crate::Simulation;
     fn simulation() -> Simulation {
         include_str!("../worked_example/crash.txt").parse().unwrap()
@@ -248,12 +289,38 @@ mod worked_example_part_2 {
 
     #[test]
     fn last_cart() {
-        assert_eq!(simulation().last_cart(), (6, 4));
+        assert_eq!(simulation().last_cart().unwrap(), Location::new(6, 4));
+    }
+}
+
+#[cfg(test)]
+mod malformed {
+    use crate::Simulation;
+    use aoc_core::{Error, Solution};
+
+    #[test]
+    fn needs_two_carts() {
+        assert!(matches!(Simulation::parse(""), Err(Error::Parse(_))));
+        assert!(matches!(Simulation::parse("->-"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn gives_up_on_carts_that_never_crash() {
+        let loops = concat!(r"/>\ /<\", "\n", r"\-/ \-/");
+        let simulation = Simulation::parse(loops).unwrap();
+        assert!(matches!(simulation.part1(&()), Err(Error::NoAnswer(_))));
+        assert!(matches!(simulation.part2(&()), Err(Error::NoAnswer(_))));
+        let crash = Simulation::parse("->-<-").unwrap();
+        assert_eq!(crash.part1(&()).unwrap(), "2,0");
+        assert!(matches!(crash.part2(&()), Err(Error::NoAnswer(_))));
+        let dead_end = Simulation::parse("-> <-").unwrap();
+        assert!(matches!(dead_end.part1(&()), Err(Error::NoAnswer(_))));
     }
 }
 
 #[cfg(test)]
 mod puzzle {
+    use crate::Location;
     use crate::Simulation;
     fn simulation() -> Simulation {
         include_str!("../puzzle.txt").parse().unwrap()
@@ -261,11 +328,11 @@ mod puzzle {
 
     #[test]
     fn first_crash() {
-        assert_eq!(simulation().first_crash(), (50, 54));
+        assert_eq!(simulation().first_crash().unwrap(), Location::new(50, 54));
     }
 
     #[test]
     fn last_cart() {
-        assert_eq!(simulation().last_cart(), (50, 100));
+        assert_eq!(simulation().last_cart().unwrap(), Location::new(50, 100));
     }
 }
@@ -1,3 +1,5 @@
+use aoc_core::{Error, Solution};
+
 pub fn list_of_10_scores_after_n(n: usize) -> Vec<u8> {
     let mut kitchen = Kitchen::new();
     kitchen.step_until_n_recipies(n + 10);
@@ -9,7 +11,7 @@ pub fn score_search(s: &[u8]) -> usize {
 
     let check_at = |kitchen: &Kitchen, search_point| {
         if kitchen.recipies.get(search_point..search_point + s.len()) == Some(s) {
-            return Some(search_point);
+            Some(search_point)
         } else {
             None
         }
@@ -25,6 +27,40 @@ pub fn score_search(s: &[u8]) -> usize {
     }
 }
 
+/// The puzzle input's digits, read both as a recipe count and as a sequence of scores.
+pub struct PuzzleInput(Vec<u8>);
+
+impl Solution for PuzzleInput {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let digits = input.trim();
+        digits
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| Error::parse(input, &digits[i..], "a digit"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(PuzzleInput)
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        let n = self.0.iter().fold(0, |n, &d| n * 10 + d as usize);
+        Ok(list_of_10_scores_after_n(n)
+            .iter()
+            .map(|score| score.to_string())
+            .collect())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(score_search(&self.0))
+    }
+}
+
 struct Kitchen {
     elf_pointers: [usize; 2],
     recipies: Vec<u8>,
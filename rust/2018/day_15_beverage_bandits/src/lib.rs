@@ -1,6 +1,8 @@
-extern crate image;
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec2;
+use aoc_render::{Animate, Backend, Cell, Colour, Raster, Render};
+use aoc_search::TieBreak;
 use core::cmp::max;
-use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -13,43 +15,13 @@ enum FighterType {
     Elf,
 }
 
-#[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct Coordinate {
-    x: u32,
-    y: u32,
-}
-
-impl Coordinate {
-    fn ordered_neighbours(self) -> [Self; 4] {
-        let (x, y) = (self.x, self.y);
-        [
-            Coordinate { x, y: y - 1 },
-            Coordinate { x: x - 1, y },
-            Coordinate { x: x + 1, y },
-            Coordinate { x, y: y + 1 },
-        ]
-    }
-
-    fn is_neighbour(self, other: Self) -> bool {
-        match (self.x == other.x, self.y == other.y) {
-            (true, true) => false,
-            (false, false) => false,
-            (true, false) => self.y + 1 == other.y || self.y == other.y + 1,
-            (false, true) => self.x + 1 == other.x || self.x == other.x + 1,
-        }
-    }
-}
+/// Sorts in reading order, which is how the puzzle breaks ties.
+type Coordinate = Vec2<u32>;
 
-impl Ord for Coordinate {
-    fn cmp(&self, other: &Coordinate) -> Ordering {
-        (self.y, self.x).cmp(&(other.y, other.x))
-    }
-}
-
-impl PartialOrd for Coordinate {
-    fn partial_cmp(&self, other: &Coordinate) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+/// The squares next to `c`, in reading order.
+fn ordered_neighbours(c: Coordinate) -> [Coordinate; 4] {
+    let (across, down) = (Coordinate::new(1, 0), Coordinate::new(0, 1));
+    [c - down, c - across, c + across, c + down]
 }
 
 impl FighterType {
@@ -75,10 +47,11 @@ struct Fighter {
 }
 
 impl Fighter {
-    fn to_pixel(&self) -> image::Rgb<u8> {
+    /// Goblins are blue and elves green, fading as they're hurt.
+    fn to_cell(&self) -> Cell {
         match self.fighter_type {
-            FighterType::Goblin => image::Rgb([0u8, 0u8, self.health]),
-            FighterType::Elf => image::Rgb([0u8, self.health, 0u8]),
+            FighterType::Goblin => Cell::new('G', Colour::new(0, 0, self.health)),
+            FighterType::Elf => Cell::new('E', Colour::new(0, self.health, 0)),
         }
     }
 
@@ -95,7 +68,7 @@ impl Fighter {
     fn has_targets(battle: &Battle, id: FighterId) -> Option<bool> {
         if let Some(fighter) = battle.fighters.get(&id) {
             let ft = fighter.fighter_type;
-            Some(battle.fighters.values().any({ |f| f.fighter_type != ft }))
+            Some(battle.fighters.values().any(|f| f.fighter_type != ft))
         } else {
             None
         }
@@ -103,7 +76,7 @@ impl Fighter {
 
     fn move_fighter(battle: &mut Battle, id: FighterId) {
         if let Some(fighter) = battle.fighters.get(&id) {
-            let view = BattleMovement::new(&battle, fighter);
+            let view = BattleMovement::new(battle, fighter);
             if let Some(fighter) = battle.fighters.get_mut(&id) {
                 fighter.location = view.new_location();
             }
@@ -120,11 +93,11 @@ impl Fighter {
                     .fighters
                     .values_mut()
                     .filter(|f| f.fighter_type != fighter_type)
-                    .filter(|f| f.location.is_neighbour(location))
+                    .filter(|f| f.location.manhattan(location) == 1)
                     .map(|f| (f.location, f)),
             );
             if let Some(weakest_health) = enemies.values().map(|f| f.health).min() {
-                for c in location.ordered_neighbours().iter() {
+                for c in ordered_neighbours(location).iter() {
                     if let Some(enemy) = enemies.get_mut(c) {
                         if enemy.health != weakest_health {
                             continue;
@@ -187,11 +160,8 @@ impl BattleMovement {
     }
 
     fn new_location(&self) -> Coordinate {
-        let immediate_moves = self.position.ordered_neighbours();
-        if immediate_moves
-            .iter()
-            .any({ |c| self.enemies.contains(&c) })
-        {
+        let immediate_moves = ordered_neighbours(self.position);
+        if immediate_moves.iter().any(|c| self.enemies.contains(c)) {
             return self.position;
         }
 
@@ -199,65 +169,44 @@ impl BattleMovement {
             .enemies
             .iter()
             .flat_map(|&c| {
-                let neighbours = c.ordered_neighbours();
+                let neighbours = ordered_neighbours(c);
                 neighbours
-                    .into_iter()
+                    .iter()
                     .filter(|&n| self.open_tiles.contains(n))
                     .cloned()
                     .collect::<Vec<_>>()
             })
             .collect();
 
-        let mut unvisited_set = self.open_tiles.clone();
-        let mut current_nodes_ordered = [
-            HashSet::new(),
-            HashSet::new(),
-            HashSet::new(),
-            HashSet::new(),
-        ];
-        for (working_nodes, &c) in current_nodes_ordered.iter_mut().zip(immediate_moves.iter()) {
-            if unvisited_set.remove(&c) {
-                working_nodes.insert(c);
-            }
-        }
-        let mut expanded = true;
-        while expanded {
-            expanded = false;
-            for target in targets.iter() {
-                for (working_nodes, &initial_move) in
-                    current_nodes_ordered.iter().zip(immediate_moves.iter())
-                {
-                    if working_nodes.contains(target) {
-                        return initial_move;
-                    }
-                }
-            }
-
-            for working_nodes in current_nodes_ordered.iter_mut() {
-                let mut new_working_nodes = HashSet::new();
-                for working_node in working_nodes.iter() {
-                    for n in working_node.ordered_neighbours().iter().cloned() {
-                        if unvisited_set.remove(&n) {
-                            new_working_nodes.insert(n);
-                            expanded = true
-                        }
-                    }
-                }
-                *working_nodes = new_working_nodes;
+        let nearest = aoc_search::bfs(|&c: &Coordinate| {
+            IntoIterator::into_iter(ordered_neighbours(c)).filter(|n| self.open_tiles.contains(n))
+        })
+        .tie_break(TieBreak::Smallest)
+        .start(self.position)
+        .shortest(|c| targets.contains(c));
+        match nearest {
+            Some(nearest) => {
+                let on_paths = nearest.paths.on_paths_to(nearest.ends.first().copied());
+                IntoIterator::into_iter(immediate_moves)
+                    .find(|c| on_paths.contains(c))
+                    .unwrap_or(self.position)
             }
+            None => self.position,
         }
-        self.position
     }
 }
 
 impl FromStr for Battle {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut battle = Battle::default();
         for (line, line_number) in s.lines().filter(|&l| !l.is_empty()).zip(0..) {
             battle.dimensions.y = max(battle.dimensions.y, line_number);
-            for (c, col_number) in line.chars().zip(0..) {
+            for ((i, c), col_number) in line.char_indices().zip(0..) {
+                if !matches!(c, '#' | '.' | 'G' | 'E') {
+                    return Err(ParseError::new(s, &line[i..], "a wall, cavern or unit"));
+                }
                 let coord = Coordinate {
                     x: col_number,
                     y: line_number,
@@ -279,27 +228,12 @@ impl FromStr for Battle {
 }
 
 impl Battle {
-    pub fn to_image(&self) -> image::RgbImage {
-        let fighters: HashMap<Coordinate, &Fighter> =
-            HashMap::from_iter(self.fighters.values().map(|f| (f.location, f)));
-        image::ImageBuffer::from_fn(self.dimensions.x + 1, self.dimensions.y + 1, |x, y| {
-            let coord = Coordinate { x, y };
-            match (fighters.get(&coord), self.caverns.contains(&coord)) {
-                (Some(fighter), _) => fighter.to_pixel(),
-                (None, true) => image::Rgb([255u8; 3]),
-                (None, false) => image::Rgb([0u8; 3]),
-            }
-        })
-    }
-
     fn turn_order(&self) -> Vec<FighterId> {
         let mut fighter_ids = self.fighters.keys().cloned().collect::<Vec<_>>();
         fighter_ids.sort_unstable_by_key(|id| {
-            if let Some(fighter) = self.fighters.get(id) {
-                Some((fighter.location.y, fighter.location.x))
-            } else {
-                None
-            }
+            self.fighters
+                .get(id)
+                .map(|fighter| (fighter.location.y, fighter.location.x))
         });
         fighter_ids
     }
@@ -320,11 +254,11 @@ impl Battle {
     fn complete(&self) -> bool {
         self.fighters
             .values()
-            .all({ |f| f.fighter_type == FighterType::Goblin })
+            .all(|f| f.fighter_type == FighterType::Goblin)
             || self
                 .fighters
                 .values()
-                .all({ |f| f.fighter_type == FighterType::Elf })
+                .all(|f| f.fighter_type == FighterType::Elf)
     }
 
     pub fn final_round(mut self) -> Self {
@@ -356,6 +290,10 @@ impl Battle {
         self.fighters.values().map(|f| u32::from(f.health)).sum()
     }
 
+    pub fn outcome(&self) -> u32 {
+        self.round_number * self.hit_points_sum()
+    }
+
     pub fn super_powered_elves(self) -> Option<Self> {
         for ap in 4..=200 {
             let mut candidate_battle = self.clone();
@@ -372,9 +310,60 @@ impl Battle {
     }
 }
 
+impl Render for Battle {
+    fn render(&self) -> Raster {
+        let fighters: HashMap<Coordinate, &Fighter> =
+            HashMap::from_iter(self.fighters.values().map(|f| (f.location, f)));
+        let (width, height) = (self.dimensions.x + 1, self.dimensions.y + 1);
+        Raster::from_fn(width as usize, height as usize, |p| {
+            let coord = Coordinate::new(p.x as u32, p.y as u32);
+            match (fighters.get(&coord), self.caverns.contains(&coord)) {
+                (Some(fighter), _) => fighter.to_cell(),
+                (None, true) => Cell::new('.', Colour::WHITE),
+                (None, false) => Cell::new('#', Colour::BLACK),
+            }
+        })
+    }
+}
+
+/// The battle of part one, a frame per round.
+impl Animate for Battle {
+    fn animate(&self, backend: &mut dyn Backend) -> std::io::Result<()> {
+        let mut battle = self.clone();
+        backend.frame(&battle.render())?;
+        while !battle.complete() {
+            battle = battle.round();
+            backend.frame(&battle.render())?;
+        }
+        Ok(())
+    }
+}
+
+impl Solution for Battle {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.clone().final_round().outcome())
+    }
+
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        self.clone()
+            .super_powered_elves()
+            .map(|battle| battle.outcome())
+            .ok_or_else(|| Error::no_answer("the elves can't win without losses"))
+    }
+}
+
 #[cfg(test)]
 mod worked_example_1 {
     use crate::Battle;
+    use aoc_render::{Animate, Render};
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_1.txt")
@@ -393,30 +382,45 @@ mod worked_example_1 {
     #[test]
     fn it_produces_the_correct_initial_image() {
         assert_eq!(
-            battle_at_round(0).to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle_at_round(0).render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_1_0.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
     #[test]
     fn it_is_correct_on_1st_round() {
         assert_eq!(
-            battle_at_round(1).to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle_at_round(1).render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_1_1.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
     #[test]
     fn it_is_correct_on_2nd_round() {
         assert_eq!(
-            battle_at_round(2).to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle_at_round(2).render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_1_2.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
+        );
+    }
+
+    #[test]
+    fn it_animates_each_round() {
+        let mut frames = Vec::new();
+        battle().animate(&mut frames).unwrap();
+        // the starting positions, then the end of each of the 47 full rounds
+        assert_eq!(frames.len(), 48);
+        assert_eq!(
+            frames[0].to_string(),
+            include_str!("../worked_examples/example_1.txt")
         );
     }
 
@@ -433,10 +437,11 @@ mod worked_example_1 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_1_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
@@ -453,6 +458,7 @@ mod worked_example_1 {
 #[cfg(test)]
 mod worked_example_2 {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_2.txt")
@@ -463,20 +469,22 @@ mod worked_example_2 {
     #[test]
     fn it_produces_the_correct_initial_image() {
         assert_eq!(
-            battle().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_2_1.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
     #[test]
     fn it_produces_the_correct_image_after_one_round() {
         assert_eq!(
-            battle().round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_2_2.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
@@ -493,10 +501,11 @@ mod worked_example_2 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_2_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 }
@@ -504,6 +513,7 @@ mod worked_example_2 {
 #[cfg(test)]
 mod worked_example_3 {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_3.txt")
@@ -524,10 +534,11 @@ mod worked_example_3 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_3_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 }
@@ -535,6 +546,7 @@ mod worked_example_3 {
 #[cfg(test)]
 mod worked_example_4 {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_4.txt")
@@ -555,10 +567,11 @@ mod worked_example_4 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_4_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 }
@@ -566,6 +579,7 @@ mod worked_example_4 {
 #[cfg(test)]
 mod worked_example_5 {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_5.txt")
@@ -586,10 +600,11 @@ mod worked_example_5 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_5_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 }
@@ -597,6 +612,7 @@ mod worked_example_5 {
 #[cfg(test)]
 mod worked_example_6 {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../worked_examples/example_6.txt")
@@ -617,10 +633,11 @@ mod worked_example_6 {
     #[test]
     fn it_has_the_correct_final_image() {
         assert_eq!(
-            battle().final_round().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().final_round().render(), 1),
             image::load_from_memory(include_bytes!("../worked_examples/example_6_final.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 }
@@ -628,6 +645,7 @@ mod worked_example_6 {
 #[cfg(test)]
 mod puzzle {
     use crate::Battle;
+    use aoc_render::Render;
 
     fn battle() -> Battle {
         include_str!("../puzzle.txt").parse().unwrap()
@@ -636,10 +654,11 @@ mod puzzle {
     #[test]
     fn it_produces_the_correct_initial_image() {
         assert_eq!(
-            battle().to_image().into_vec(),
+            aoc_render::rgb_pixels(&battle().render(), 1),
             image::load_from_memory(include_bytes!("../puzzle/0.png"))
                 .unwrap()
-                .raw_pixels()
+                .to_rgb8()
+                .into_raw()
         );
     }
 
@@ -0,0 +1,242 @@
+//! The wrist device's sixteen opcodes, and a machine to run them on.
+//!
+//! Day 16 works out which number is which opcode. Days 19 and 21 run programs written with the
+//! opcodes' names, on six registers with one bound to the instruction pointer.
+use std::fmt;
+use std::str::FromStr;
+
+pub type Value = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+use Opcode::*;
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori, Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri,
+        Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Addr => "addr",
+            Addi => "addi",
+            Mulr => "mulr",
+            Muli => "muli",
+            Banr => "banr",
+            Bani => "bani",
+            Borr => "borr",
+            Bori => "bori",
+            Setr => "setr",
+            Seti => "seti",
+            Gtir => "gtir",
+            Gtri => "gtri",
+            Gtrr => "gtrr",
+            Eqir => "eqir",
+            Eqri => "eqri",
+            Eqrr => "eqrr",
+        }
+    }
+
+    /// The registers after running this with operands `a`, `b` and `c`, or `None` if an
+    /// operand that names a register is out of range.
+    pub fn apply<const N: usize>(
+        self,
+        registers: &[Value; N],
+        a: Value,
+        b: Value,
+        c: Value,
+    ) -> Option<[Value; N]> {
+        let r = |i: Value| registers.get(i).copied();
+        let value = match self {
+            Addr => r(a)? + r(b)?,
+            Addi => r(a)? + b,
+            Mulr => r(a)? * r(b)?,
+            Muli => r(a)? * b,
+            Banr => r(a)? & r(b)?,
+            Bani => r(a)? & b,
+            Borr => r(a)? | r(b)?,
+            Bori => r(a)? | b,
+            Setr => r(a)?,
+            Seti => a,
+            Gtir => Value::from(a > r(b)?),
+            Gtri => Value::from(r(a)? > b),
+            Gtrr => Value::from(r(a)? > r(b)?),
+            Eqir => Value::from(a == r(b)?),
+            Eqri => Value::from(r(a)? == b),
+            Eqrr => Value::from(r(a)? == r(b)?),
+        };
+        let mut after = *registers;
+        *after.get_mut(c)? = value;
+        Some(after)
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Opcode::ALL
+            .iter()
+            .copied()
+            .find(|o| o.name() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub a: Value,
+    pub b: Value,
+    pub c: Value,
+}
+
+/// Why the device stopped before the end of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fault {
+    /// Where the instruction pointer was.
+    pub ip: Value,
+    pub instruction: Instruction,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Instruction { opcode, a, b, c } = self.instruction;
+        write!(
+            f,
+            "`{opcode} {a} {b} {c}` at {} names a register the device doesn't have",
+            self.ip
+        )
+    }
+}
+
+/// `N` registers, all starting at 0, and optionally one of them bound to the instruction
+/// pointer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device<const N: usize> {
+    pub registers: [Value; N],
+    pub ip_register: Option<usize>,
+}
+
+impl<const N: usize> Default for Device<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Device<N> {
+    pub fn new() -> Self {
+        Self {
+            registers: [0; N],
+            ip_register: None,
+        }
+    }
+
+    /// Binds register `register` to the instruction pointer, as a program's `#ip` line does.
+    pub fn with_ip_register(mut self, register: usize) -> Self {
+        self.ip_register = Some(register);
+        self
+    }
+
+    /// Returns false, leaving the registers alone, if the instruction names a register the
+    /// device doesn't have.
+    pub fn execute(&mut self, instruction: &Instruction) -> bool {
+        let Instruction { opcode, a, b, c } = *instruction;
+        match opcode.apply(&self.registers, a, b, c) {
+            Some(registers) => {
+                self.registers = registers;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs until the instruction pointer leaves the program.
+    pub fn run(&mut self, program: &[Instruction]) -> Result<(), Fault> {
+        let mut ip = 0;
+        while let Some(instruction) = program.get(ip) {
+            if let Some(r) = self.ip_register {
+                self.registers[r] = ip;
+            }
+            if !self.execute(instruction) {
+                return Err(Fault {
+                    ip,
+                    instruction: *instruction,
+                });
+            }
+            if let Some(r) = self.ip_register {
+                ip = self.registers[r];
+            }
+            ip += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_each_kind_of_operand() {
+        let registers = [3, 2, 1, 1];
+        assert_eq!(Mulr.apply(&registers, 2, 1, 2), Some([3, 2, 2, 1]));
+        assert_eq!(Addi.apply(&registers, 2, 1, 2), Some([3, 2, 2, 1]));
+        assert_eq!(Seti.apply(&registers, 2, 1, 2), Some([3, 2, 2, 1]));
+        assert_eq!(Gtir.apply(&registers, 3, 0, 0), Some([0, 2, 1, 1]));
+        assert_eq!(Addr.apply(&registers, 4, 0, 0), None);
+        assert_eq!(Seti.apply(&registers, 4, 0, 4), None);
+    }
+
+    #[test]
+    fn runs_with_a_bound_instruction_pointer() {
+        // the day 19 example
+        let program: Vec<Instruction> = [
+            (Seti, 5, 0, 1),
+            (Seti, 6, 0, 2),
+            (Addi, 0, 1, 0),
+            (Addr, 1, 2, 3),
+            (Setr, 1, 0, 0),
+            (Seti, 8, 0, 4),
+            (Seti, 9, 0, 5),
+        ]
+        .iter()
+        .map(|&(opcode, a, b, c)| Instruction { opcode, a, b, c })
+        .collect();
+        let mut device = Device::<6>::new().with_ip_register(0);
+        device.run(&program).unwrap();
+        assert_eq!(device.registers, [6, 5, 6, 0, 0, 9]);
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for opcode in Opcode::ALL.iter() {
+            assert_eq!(opcode.to_string().parse(), Ok(*opcode));
+        }
+    }
+}
@@ -1,7 +1,201 @@
+pub mod device;
+
+use aoc_core::{Error, ParseError, Solution};
+use device::{Device, Instruction, Opcode, Value};
+use std::collections::BTreeSet;
+
+/// An instruction as the manual writes it, before the opcode numbers are known.
+type Numbered = [Value; 4];
+
+struct Sample {
+    before: [Value; 4],
+    instruction: Numbered,
+    after: [Value; 4],
+}
+
+impl Sample {
+    fn matching_opcodes(&self) -> impl Iterator<Item = Opcode> + '_ {
+        let [_, a, b, c] = self.instruction;
+        Opcode::ALL
+            .iter()
+            .copied()
+            .filter(move |o| o.apply(&self.before, a, b, c) == Some(self.after))
+    }
+}
+
+/// The captured samples and the test program.
+pub struct Manual {
+    samples: Vec<Sample>,
+    program: Vec<Numbered>,
+}
+
+fn numbers(
+    input: &str,
+    line: &str,
+    separator: &str,
+    expected: &str,
+) -> Result<Numbered, ParseError> {
+    let mut parts = line.split(separator);
+    let mut numbers = [0; 4];
+    for n in numbers.iter_mut() {
+        *n = parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(|| ParseError::new(input, line, expected))?;
+    }
+    match parts.next() {
+        None => Ok(numbers),
+        Some(_) => Err(ParseError::new(input, line, expected)),
+    }
+}
+
+fn registers(input: &str, line: Option<&str>, label: &str) -> Result<[Value; 4], ParseError> {
+    let expected = format!("`{label} [a, b, c, d]`");
+    let line = line.ok_or_else(|| ParseError::at_end(input, expected.as_str()))?;
+    let inside = line
+        .strip_prefix(label)
+        .map(str::trim_start)
+        .and_then(|l| l.strip_prefix('['))
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or_else(|| ParseError::new(input, line, expected.as_str()))?;
+    numbers(input, inside, ", ", &expected)
+}
+
+fn instruction(input: &str, line: Option<&str>) -> Result<Numbered, ParseError> {
+    let expected = "four numbers";
+    let line = line.ok_or_else(|| ParseError::at_end(input, expected))?;
+    numbers(input, line, " ", expected)
+}
+
+impl Manual {
+    fn try_new(input: &str) -> Result<Self, ParseError> {
+        let (samples, program) = input.split_once("\n\n\n").unwrap_or((input, ""));
+        let mut samples_read = Vec::new();
+        for sample in samples.split("\n\n").filter(|s| !s.trim().is_empty()) {
+            let mut lines = sample.lines().filter(|l| !l.is_empty());
+            samples_read.push(Sample {
+                before: registers(input, lines.next(), "Before:")?,
+                instruction: instruction(input, lines.next())?,
+                after: registers(input, lines.next(), "After:")?,
+            });
+        }
+        let program = program
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| instruction(input, Some(l)))
+            .collect::<Result<_, _>>()?;
+        Ok(Manual {
+            samples: samples_read,
+            program,
+        })
+    }
+
+    pub fn samples_like_three_or_more(&self) -> usize {
+        self.samples
+            .iter()
+            .filter(|s| s.matching_opcodes().nth(2).is_some())
+            .count()
+    }
+
+    /// Which opcode each number is. Each number starts out as any opcode that fits all its
+    /// samples, and once a number can only be one opcode, no other number can be that one.
+    fn opcode_numbers(&self) -> Result<[Opcode; 16], Error> {
+        let mut candidates: Vec<BTreeSet<Opcode>> = vec![Opcode::ALL.iter().copied().collect(); 16];
+        for sample in self.samples.iter() {
+            let fits: BTreeSet<Opcode> = sample.matching_opcodes().collect();
+            let number = sample.instruction[0];
+            let possible = candidates
+                .get_mut(number)
+                .ok_or_else(|| Error::no_answer(format!("there's no opcode numbered {number}")))?;
+            possible.retain(|o| fits.contains(o));
+        }
+
+        let mut known: [Option<Opcode>; 16] = [None; 16];
+        while let Some(number) = (0..16).find(|&n| known[n].is_none() && candidates[n].len() == 1) {
+            let opcode = *candidates[number].iter().next().unwrap();
+            known[number] = Some(opcode);
+            for possible in candidates.iter_mut() {
+                possible.remove(&opcode);
+            }
+        }
+
+        let mut opcodes = [Opcode::Addr; 16];
+        for (number, opcode) in known.iter().enumerate() {
+            opcodes[number] = opcode.ok_or_else(|| {
+                Error::no_answer(format!("the samples don't pin down opcode {number}"))
+            })?;
+        }
+        Ok(opcodes)
+    }
+
+    pub fn run_test_program(&self) -> Result<Value, Error> {
+        let opcodes = self.opcode_numbers()?;
+        let program: Vec<Instruction> = self
+            .program
+            .iter()
+            .map(|&[number, a, b, c]| {
+                opcodes
+                    .get(number)
+                    .map(|&opcode| Instruction { opcode, a, b, c })
+                    .ok_or_else(|| Error::no_answer(format!("there's no opcode numbered {number}")))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut device = Device::<4>::new();
+        device
+            .run(&program)
+            .map_err(|fault| Error::no_answer(fault.to_string()))?;
+        Ok(device.registers[0])
+    }
+}
+
+impl Solution for Manual {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Manual::try_new(input)?)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.samples_like_three_or_more())
+    }
+
+    fn part2(&self, _: &()) -> Result<Value, Error> {
+        self.run_test_program()
+    }
+}
+
+#[cfg(test)]
+mod worked_example {
+    use crate::Manual;
+
+    #[test]
+    fn behaves_like_three_opcodes() {
+        let manual =
+            Manual::try_new("Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n").unwrap();
+        assert_eq!(manual.samples_like_three_or_more(), 1);
+        assert_eq!(manual.samples[0].matching_opcodes().count(), 3);
+    }
+}
+
 #[cfg(test)]
-mod tests {
+mod puzzle {
+    use crate::Manual;
+
+    fn manual() -> Manual {
+        Manual::try_new(&aoc_input::challenge(2018, 16)).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn samples_like_three_or_more() {
+        assert_eq!(manual().samples_like_three_or_more(), 651);
+    }
+
     #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn test_program() {
+        assert_eq!(manual().run_test_program(), Ok(706));
     }
 }
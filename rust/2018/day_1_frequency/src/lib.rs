@@ -1,66 +1,59 @@
-pub fn frequency(input: &'static str) -> i32 {
-    numbers_iterator(input).sum()
+use aoc_core::{Error, ParseError, Solution};
+use nom::character::complete::i32;
+
+pub fn frequency(input: &str) -> i32 {
+    aoc_core::part1::<FrequencyChanges>(input).unwrap()
 }
 
-pub fn repeated_frequency(input: &'static str) -> i32 {
-    let mut seen = std::collections::HashSet::new();
-    let mut cumulative_sum = 0;
-    loop {
-        for num in numbers_iterator(input) {
-            if !seen.insert(cumulative_sum) {
-                return cumulative_sum;
-            }
-            cumulative_sum += num;
-        }
-    }
+pub fn repeated_frequency(input: &str) -> i32 {
+    aoc_core::part2::<FrequencyChanges>(input).unwrap()
 }
 
-fn numbers_iterator(input: &'static str) -> impl Iterator<Item = i32> + Clone {
-    NumberIterator {
-        chars: input.chars().peekable(),
+pub struct FrequencyChanges(Vec<i32>);
+
+impl Solution for FrequencyChanges {
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(FrequencyChanges(parse_changes(input)?))
     }
-}
 
-#[derive(Clone)]
-struct NumberIterator<'a> {
-    chars: std::iter::Peekable<std::str::Chars<'a>>,
-}
+    fn part1(&self, _: &()) -> Result<i32, Error> {
+        Ok(self.0.iter().sum())
+    }
 
-impl<'a> Iterator for NumberIterator<'a> {
-    type Item = i32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut value = None;
-        let mut sign = None;
-        while let Some(c) = self.chars.by_ref().next() {
-            match (c, c.to_digit(10), sign) {
-                ('+', None, None) => {
-                    value = Some(0);
-                    sign = Some(true);
-                }
-                ('-', None, None) => {
-                    value = Some(0);
-                    sign = Some(false);
-                }
-                (_, Some(digit), Some(true)) => {
-                    value = value.map(|v| v * 10 + digit as i32);
-                }
-                (_, Some(digit), Some(false)) => {
-                    value = value.map(|v| v * 10 - digit as i32);
+    fn part2(&self, _: &()) -> Result<i32, Error> {
+        if self.0.is_empty() {
+            return Err(Error::no_answer("there are no frequency changes"));
+        }
+        let mut seen = std::collections::HashSet::new();
+        let mut cumulative_sum = 0;
+        loop {
+            for num in self.0.iter() {
+                if !seen.insert(cumulative_sum) {
+                    return Ok(cumulative_sum);
                 }
-                _ => {}
-            };
-            if self.chars.peek() == Some(&'+') || self.chars.peek() == Some(&'-') {
-                return value;
+                cumulative_sum += num;
             }
         }
-        value
     }
 }
 
+/// Changes like `+1`, separated by commas or new lines.
+fn parse_changes(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|change| !change.is_empty())
+        .map(|change| aoc_core::finish(input, i32(change)))
+        .collect()
+}
+
 #[cfg(test)]
 mod frequency {
-    use frequency;
+    use crate::frequency;
     #[test]
     fn worked_example() {
         assert_eq!(frequency("+1, -2, +3, +1"), 3);
@@ -81,14 +74,16 @@ mod frequency {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(frequency(include_str!("../input.txt")), 493);
+        let input = aoc_input::challenge(2018, 1);
+        assert_eq!(frequency(&input), 493);
     }
 }
 
 #[cfg(test)]
 mod repeated_frequency {
-    use repeated_frequency;
+    use crate::repeated_frequency;
     #[test]
     fn worked_example() {
         assert_eq!(repeated_frequency("+1, -2, +3, +1"), 2);
@@ -115,7 +110,9 @@ mod repeated_frequency {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(repeated_frequency(include_str!("../input.txt")), 413);
+        let input = aoc_input::challenge(2018, 1);
+        assert_eq!(repeated_frequency(&input), 413);
     }
 }
@@ -1,35 +1,53 @@
+use aoc_core::{Error, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
 pub fn checksum(list: &str) -> u32 {
-    let (doubles, triples) = list.split_whitespace().map(id_analysis).fold(
-        (0, 0),
-        |(doubles, triples), (double, triple)| {
-            (
-                doubles + if double { 1 } else { 0 },
-                triples + if triple { 1 } else { 0 },
-            )
-        },
-    );
-    doubles * triples
+    aoc_core::part1::<BoxIds>(list).unwrap()
 }
 
 pub fn common_letters(list: &str) -> Option<String> {
-    let list_entries: Vec<&str> = list.split_whitespace().collect();
-    for (index, entry_a) in list_entries.iter().enumerate() {
-        for entry_b in list_entries.iter().skip(index + 1) {
-            if hamming_distance_of_one(entry_a, entry_b) {
-                return Some(
-                    entry_a
+    aoc_core::part2::<BoxIds>(list).ok()
+}
+
+pub struct BoxIds(Vec<String>);
+
+impl Solution for BoxIds {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(list: &str) -> Result<Self, Error> {
+        Ok(BoxIds(list.split_whitespace().map(String::from).collect()))
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        let (doubles, triples) = self.0.iter().map(|id| id_analysis(id)).fold(
+            (0, 0),
+            |(doubles, triples), (double, triple)| {
+                (
+                    doubles + if double { 1 } else { 0 },
+                    triples + if triple { 1 } else { 0 },
+                )
+            },
+        );
+        Ok(doubles * triples)
+    }
+
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        for (index, entry_a) in self.0.iter().enumerate() {
+            for entry_b in self.0.iter().skip(index + 1) {
+                if hamming_distance_of_one(entry_a, entry_b) {
+                    return Ok(entry_a
                         .chars()
                         .zip(entry_b.chars())
                         .filter_map(|(a, b)| if a == b { Some(a) } else { None })
-                        .collect(),
-                );
+                        .collect());
+                }
             }
         }
+        Err(Error::no_answer("no two box IDs differ by one letter"))
     }
-    None
 }
 
 fn id_analysis(id: &str) -> (bool, bool) {
@@ -52,7 +70,7 @@ fn hamming_distance_of_one(a: &str, b: &str) -> bool {
 
 #[cfg(test)]
 mod checksum {
-    use checksum;
+    use crate::checksum;
     #[test]
     fn worked_example() {
         let list = "abcdef bababc abbcde abcccd aabcdd abcdee ababab";
@@ -60,14 +78,16 @@ mod checksum {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(checksum(include_str!("../input.txt")), 7410);
+        let input = aoc_input::challenge(2018, 2);
+        assert_eq!(checksum(&input), 7410);
     }
 }
 
 #[cfg(test)]
 mod common_letters {
-    use common_letters;
+    use crate::common_letters;
 
     #[test]
     fn worked_example() {
@@ -76,9 +96,11 @@ mod common_letters {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
+        let input = aoc_input::challenge(2018, 2);
         assert_eq!(
-            common_letters(include_str!("../input.txt")),
+            common_letters(&input),
             Some("cnjxoritzhvbosyewrmqhgkul".to_string())
         );
     }
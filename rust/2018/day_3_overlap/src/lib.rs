@@ -1,34 +1,63 @@
-#[macro_use]
-extern crate nom;
-
+use aoc_core::{Error, ParseError, Solution};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, space0},
+    combinator::{map, map_res},
+    sequence::{delimited, tuple},
+    IResult,
+};
 use std::collections::HashMap;
-use std::str::FromStr;
 
 pub fn overlap(input: &str) -> usize {
-    used_cloth(input).values().filter(|&&s| s).count()
+    aoc_core::part1::<Claims>(input).unwrap()
 }
 
 pub fn no_overlap(input: &str) -> Option<u16> {
-    let used_cloth = used_cloth(input);
-    claims(input)
-        .find(|claim| {
-            claim
-                .cords()
-                .all(|cord| used_cloth.get(&cord) == Some(&false))
-        }).map(|claim| claim.id)
+    aoc_core::part2::<Claims>(input).ok()
 }
 
-fn used_cloth(input: &str) -> HashMap<(u16, u16), bool> {
-    let mut used_cloth = HashMap::with_capacity(1000 * 1000);
-    for claim in claims(input) {
-        for (x, y) in claim.cords() {
-            used_cloth
-                .entry((x, y))
-                .and_modify(|s| *s = true)
-                .or_insert(false);
+pub struct Claims(Vec<Claim>);
+
+impl Solution for Claims {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = u16;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Claims(claims(input)?))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.used_cloth().values().filter(|&&s| s).count())
+    }
+
+    fn part2(&self, _: &()) -> Result<u16, Error> {
+        let used_cloth = self.used_cloth();
+        self.0
+            .iter()
+            .find(|claim| {
+                claim
+                    .cords()
+                    .all(|cord| used_cloth.get(&cord) == Some(&false))
+            })
+            .map(|claim| claim.id)
+            .ok_or_else(|| Error::no_answer("every claim overlaps another"))
+    }
+}
+
+impl Claims {
+    fn used_cloth(&self) -> HashMap<(u16, u16), bool> {
+        let mut used_cloth = HashMap::with_capacity(1000 * 1000);
+        for claim in self.0.iter() {
+            for (x, y) in claim.cords() {
+                used_cloth
+                    .entry((x, y))
+                    .and_modify(|s| *s = true)
+                    .or_insert(false);
+            }
         }
+        used_cloth
     }
-    used_cloth
 }
 
 #[derive(PartialEq, Debug)]
@@ -40,54 +69,53 @@ struct Claim {
     height: u16,
 }
 
-impl<'a> Claim {
-    fn cords(&'a self) -> impl Iterator<Item = (u16, u16)> + 'a {
+impl Claim {
+    fn cords(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
         (self.from_left..self.from_left + self.width)
             .flat_map(move |x| (self.from_top..self.from_top + self.height).map(move |y| (x, y)))
     }
 }
 
-named!(claim<&str,Claim>,
-    do_parse!(
-        tag_s!("#") >>
-        id: map_res!(nom::digit, FromStr::from_str) >>
-        ws!(tag_s!("@")) >>
-        from_left: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!(",") >>
-        from_top: map_res!(nom::digit, FromStr::from_str) >>
-        ws!(tag_s!(":")) >>
-        width: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!("x") >>
-        height: map_res!(nom::digit, FromStr::from_str) >>
-
-        (Claim{ id, from_left, from_top, width, height})
-    )
-);
-
-struct ClaimsIterator<'a> {
-    input: &'a str,
+fn number(input: &str) -> IResult<&str, u16> {
+    map_res(digit1, str::parse)(input)
 }
 
-impl<'a> Iterator for ClaimsIterator<'a> {
-    type Item = Claim;
-    fn next(&mut self) -> Option<Self::Item> {
-        match claim(self.input.trim_start()) {
-            Ok((remaining, claim)) => {
-                self.input = remaining;
-                Some(claim)
-            }
-            Err(_) => None,
-        }
-    }
+fn claim(input: &str) -> IResult<&str, Claim> {
+    map(
+        tuple((
+            tag("#"),
+            number,
+            delimited(space0, tag("@"), space0),
+            number,
+            tag(","),
+            number,
+            delimited(space0, tag(":"), space0),
+            number,
+            tag("x"),
+            number,
+        )),
+        |(_, id, _, from_left, _, from_top, _, width, _, height)| Claim {
+            id,
+            from_left,
+            from_top,
+            width,
+            height,
+        },
+    )(input)
 }
 
-fn claims<'a>(input: &'a str) -> impl Iterator<Item = Claim> + 'a {
-    ClaimsIterator { input }
+fn claims(input: &str) -> Result<Vec<Claim>, ParseError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| aoc_core::finish(input, claim(line)))
+        .collect()
 }
 
 #[cfg(test)]
 mod overlap_tests {
-    use overlap;
+    use crate::overlap;
     #[test]
     fn worked_example() {
         let input = r#"
@@ -99,15 +127,16 @@ mod overlap_tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(overlap(include_str!("../input.txt")), 100595);
+        let input = aoc_input::challenge(2018, 3);
+        assert_eq!(overlap(&input), 100595);
     }
-
 }
 
 #[cfg(test)]
 mod no_overlap_tests {
-    use no_overlap;
+    use crate::no_overlap;
     #[test]
     fn worked_example() {
         let input = r#"
@@ -119,7 +148,9 @@ mod no_overlap_tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(no_overlap(include_str!("../input.txt")), Some(415));
+        let input = aoc_input::challenge(2018, 3);
+        assert_eq!(no_overlap(&input), Some(415));
     }
 }
@@ -1,59 +1,103 @@
-#[macro_use]
-extern crate nom;
-extern crate chrono;
-
+use aoc_core::{Error, ParseError, Solution};
 use chrono::naive::NaiveDate;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map, map_opt, map_res, value, verify},
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
 use std::collections::HashMap;
 use std::str::FromStr;
 
 pub fn most_asleep_guard(input: &str) -> Option<u16> {
-    let nights = parse_nights(input);
-    let mut sleeping_guards = HashMap::new();
-    for night in nights.iter() {
-        *sleeping_guards.entry(night.guard_id).or_insert(0) += u16::from(night.time_asleep());
-    }
-    sleeping_guards
-        .iter()
-        .max_by_key(|(_id, &time)| time)
-        .map(|(&id, _time)| id)
+    Nights::parse(input).ok()?.most_asleep_guard()
 }
 
 pub fn most_asleep_minute(input: &str, guard_id: u16) -> Option<u8> {
-    let nights = parse_nights(input);
-    let nights_on_duty = nights.iter().filter(|night| night.guard_id == guard_id);
-    let mut minutes = HashMap::new();
-    for night in nights_on_duty {
-        for (minute, &asleep) in night.sleeps.iter().enumerate() {
-            if asleep {
-                *minutes.entry(minute as u8).or_insert(0) += 1;
+    Nights::parse(input).ok()?.most_asleep_minute(guard_id)
+}
+
+pub fn most_consistently_asleep_guard(input: &str) -> Option<u16> {
+    Nights::parse(input).ok()?.most_consistently_asleep_guard()
+}
+
+pub struct Nights(Vec<Night>);
+
+impl Nights {
+    fn most_asleep_guard(&self) -> Option<u16> {
+        let mut sleeping_guards = HashMap::new();
+        for night in self.0.iter() {
+            *sleeping_guards.entry(night.guard_id).or_insert(0) += u16::from(night.time_asleep());
+        }
+        sleeping_guards
+            .iter()
+            .max_by_key(|(_id, &time)| time)
+            .map(|(&id, _time)| id)
+    }
+
+    fn most_asleep_minute(&self, guard_id: u16) -> Option<u8> {
+        let nights_on_duty = self.0.iter().filter(|night| night.guard_id == guard_id);
+        let mut minutes = HashMap::new();
+        for night in nights_on_duty {
+            for (minute, &asleep) in night.sleeps.iter().enumerate() {
+                if asleep {
+                    *minutes.entry(minute as u8).or_insert(0) += 1;
+                }
             }
         }
+        minutes
+            .iter()
+            .max_by_key(|(_minute, &time)| time)
+            .map(|(&minute, _time)| minute)
     }
-    minutes
-        .iter()
-        .max_by_key(|(_minute, &time)| time)
-        .map(|(&minute, _time)| minute)
-}
 
-pub fn most_consistently_asleep_guard(input: &str) -> Option<u16> {
-    let nights = parse_nights(input);
-    let mut guard_minutes = HashMap::new();
-    for night in nights.iter() {
-        for (minute, &asleep) in night.sleeps.iter().enumerate() {
-            if asleep {
-                *guard_minutes
-                    .entry((night.guard_id, minute as u8))
-                    .or_insert(0) += 1;
+    fn most_consistently_asleep_guard(&self) -> Option<u16> {
+        let mut guard_minutes = HashMap::new();
+        for night in self.0.iter() {
+            for (minute, &asleep) in night.sleeps.iter().enumerate() {
+                if asleep {
+                    *guard_minutes
+                        .entry((night.guard_id, minute as u8))
+                        .or_insert(0) += 1;
+                }
             }
         }
+        guard_minutes
+            .iter()
+            .max_by_key(|(_guard_minute, &time)| time)
+            .map(|(guard_minute, _time)| guard_minute.0)
+    }
+
+    fn strategy(&self, guard_id: Option<u16>) -> Result<u32, Error> {
+        let guard_id = guard_id.ok_or_else(|| Error::no_answer("no guard fell asleep"))?;
+        let minute = self
+            .most_asleep_minute(guard_id)
+            .ok_or_else(|| Error::no_answer("the guard never fell asleep"))?;
+        Ok(u32::from(guard_id) * u32::from(minute))
     }
-    guard_minutes
-        .iter()
-        .max_by_key(|(_guard_minute, &time)| time)
-        .map(|(guard_minute, _time)| guard_minute.0)
 }
 
-#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+impl Solution for Nights {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Nights(parse_nights(input)?))
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        self.strategy(self.most_asleep_guard())
+    }
+
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        self.strategy(self.most_consistently_asleep_guard())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord)]
 enum Event {
     Begin { guard_id: u16 },
     FallAsleep,
@@ -68,19 +112,8 @@ struct Record {
     event: Event,
 }
 
-impl Record {
-    fn night_of(&self) -> NaiveDate {
-        if self.hour == 0 {
-            self.date
-        } else {
-            self.date.succ()
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Night {
-    date: NaiveDate,
     guard_id: u16,
     sleeps: Vec<bool>,
 }
@@ -91,8 +124,8 @@ impl Night {
     }
 }
 
-fn parse_nights(input: &str) -> Vec<Night> {
-    let mut records = parse_records(input);
+fn parse_nights(input: &str) -> Result<Vec<Night>, ParseError> {
+    let mut records = parse_records(input)?;
     records.sort_unstable();
 
     let mut nights = Vec::new();
@@ -105,7 +138,6 @@ fn parse_nights(input: &str) -> Vec<Night> {
                     nights.push(night);
                 }
                 some_night = Some(Night {
-                    date: record.night_of(),
                     guard_id,
                     sleeps: (0..60).map(|_minute| false).collect(),
                 });
@@ -125,64 +157,67 @@ fn parse_nights(input: &str) -> Vec<Night> {
     if let Some(night) = some_night {
         nights.push(night);
     }
-    nights
+    Ok(nights)
 }
 
-fn parse_records(mut input: &str) -> Vec<Record> {
-    let mut records = Vec::new();
-    loop {
-        match parse_record(input.trim_start()) {
-            Ok((remaining, record)) => {
-                input = remaining;
-                records.push(record);
-            }
-            Err(_) => return records,
-        }
-    }
+fn parse_records(input: &str) -> Result<Vec<Record>, ParseError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| aoc_core::finish(input, parse_record(line)))
+        .collect()
+}
+
+fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+fn parse_event(input: &str) -> IResult<&str, Event> {
+    alt((
+        map(
+            delimited(tag("Guard #"), number, tag(" begins shift")),
+            |guard_id| Event::Begin { guard_id },
+        ),
+        value(Event::FallAsleep, tag("falls asleep")),
+        value(Event::WakeUp, tag("wakes up")),
+    ))(input)
 }
 
-named!(parse_record<&str, Record>,
-    do_parse!(
-        tag_s!("[1518-") >>
-        month: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!("-") >>
-        day: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!(" ") >>
-        hour: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!(":") >>
-        minute: map_res!(nom::digit, FromStr::from_str) >>
-        tag_s!("] ") >>
-        event: alt!(
-            do_parse!(
-                tag_s!("Guard #") >>
-                guard_id: map_res!(nom::digit, FromStr::from_str) >>
-                tag_s!(" begins shift") >>
-                (Event::Begin { guard_id})
-            ) |
-            do_parse!(
-                tag_s!("falls asleep") >>
-                (Event::FallAsleep)
-            ) |
-            do_parse!(
-                tag_s!("wakes up") >>
-                (Event::WakeUp)
-            )
-        ) >>
-        (Record { date: NaiveDate::from_ymd(1518, month, day), hour, minute, event })
-    )
-);
+fn parse_record(input: &str) -> IResult<&str, Record> {
+    map(
+        tuple((
+            tag("[1518-"),
+            map_opt(separated_pair(number, tag("-"), number), |(month, day)| {
+                NaiveDate::from_ymd_opt(1518, month, day)
+            }),
+            tag(" "),
+            number,
+            tag(":"),
+            verify(number, |&m: &u8| m < 60),
+            tag("] "),
+            parse_event,
+        )),
+        |(_, date, _, hour, _, minute, _, event)| Record {
+            date,
+            hour,
+            minute,
+            event,
+        },
+    )(input)
+}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn most_asleep_guard_test() {
-        use most_asleep_guard;
         assert_eq!(most_asleep_guard(include_str!("../example.txt")), Some(10));
     }
 
     #[test]
     fn most_asleep_minute_test() {
-        use most_asleep_minute;
         assert_eq!(
             most_asleep_minute(include_str!("../example.txt"), 10),
             Some(24)
@@ -191,7 +226,6 @@ mod tests {
 
     #[test]
     fn most_consistently_asleep_guard_test() {
-        use most_consistently_asleep_guard;
         assert_eq!(
             most_consistently_asleep_guard(include_str!("../example.txt")),
             Some(99)
@@ -199,43 +233,30 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle_one() {
+        let input = aoc_input::challenge(2018, 4);
         static GUARD_ID: u16 = 3209;
         static ASLEEP_MINUTE: u8 = 32;
 
-        use most_asleep_guard;
-        assert_eq!(
-            most_asleep_guard(include_str!("../input.txt")),
-            Some(GUARD_ID)
-        );
+        assert_eq!(most_asleep_guard(&input), Some(GUARD_ID));
 
-        use most_asleep_minute;
-        assert_eq!(
-            most_asleep_minute(include_str!("../input.txt"), GUARD_ID),
-            Some(ASLEEP_MINUTE)
-        );
+        assert_eq!(most_asleep_minute(&input, GUARD_ID), Some(ASLEEP_MINUTE));
 
         assert_eq!(GUARD_ID as u32 * ASLEEP_MINUTE as u32, 102688);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle_two() {
+        let input = aoc_input::challenge(2018, 4);
         static GUARD_ID: u16 = 1459;
         static ASLEEP_MINUTE: u8 = 39;
 
-        use most_consistently_asleep_guard;
-        assert_eq!(
-            most_consistently_asleep_guard(include_str!("../input.txt")),
-            Some(GUARD_ID)
-        );
+        assert_eq!(most_consistently_asleep_guard(&input), Some(GUARD_ID));
 
-        use most_asleep_minute;
-        assert_eq!(
-            most_asleep_minute(include_str!("../input.txt"), GUARD_ID),
-            Some(ASLEEP_MINUTE)
-        );
+        assert_eq!(most_asleep_minute(&input, GUARD_ID), Some(ASLEEP_MINUTE));
 
         assert_eq!(GUARD_ID as u32 * ASLEEP_MINUTE as u32, 56901);
     }
-
 }
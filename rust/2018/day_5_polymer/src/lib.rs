@@ -1,3 +1,5 @@
+use aoc_core::{Error, Solution};
+
 pub fn fully_react<T: Iterator<Item = char>>(input: T) -> String {
     let mut reacted = String::new();
     for b in input {
@@ -24,17 +26,39 @@ pub fn improved_react(input: &str) -> String {
                     .chars()
                     .filter(|t| t.to_ascii_lowercase() != l as char),
             )
-        }).min_by_key(|reacted| reacted.len())
+        })
+        .min_by_key(|reacted| reacted.len())
         .unwrap_or_else(String::new)
 }
 
+/// The polymer's units, with surrounding whitespace removed.
+pub struct Polymer(String);
+
+impl Solution for Polymer {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Polymer(input.trim().to_string()))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(fully_react(self.0.chars()).len())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(improved_react(&self.0).len())
+    }
+}
+
 fn letters_react(a: char, b: char) -> bool {
-    a != b && a.to_ascii_lowercase() == b.to_ascii_lowercase()
+    a != b && a.eq_ignore_ascii_case(&b)
 }
 
 #[cfg(test)]
 mod fully_react {
-    use fully_react;
+    use crate::fully_react;
 
     #[test]
     fn worked_example() {
@@ -46,17 +70,16 @@ mod fully_react {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(
-            fully_react(include_str!("../input.txt").trim().chars()).len(),
-            9386
-        );
+        let input = aoc_input::challenge(2018, 5);
+        assert_eq!(fully_react(input.trim().chars()).len(), 9386);
     }
 }
 
 #[cfg(test)]
 mod improved_react {
-    use improved_react;
+    use crate::improved_react;
 
     #[test]
     fn worked_example() {
@@ -65,10 +88,9 @@ mod improved_react {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(
-            improved_react(include_str!("../input.txt").trim()).len(),
-            4876
-        );
+        let input = aoc_input::challenge(2018, 5);
+        assert_eq!(improved_react(input.trim()).len(), 4876);
     }
 }
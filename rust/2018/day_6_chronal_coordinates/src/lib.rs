@@ -1,175 +1,190 @@
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec2;
+use nom::{
+    bytes::complete::tag, character::complete::i16, combinator::map, sequence::separated_pair,
+    IResult,
+};
 use std::collections::HashSet;
 
 pub fn largest_finite(input: &str) -> usize {
-    let coordinates = Coordinate::parse(input);
-
-    coordinates
-        .iter()
-        .filter(|c| !c.is_infinite(coordinates.iter()))
-        .map(|c| c.area(&coordinates))
-        .max()
-        .unwrap_or(0)
+    aoc_core::part1::<Coordinates>(input).unwrap()
 }
 
 pub fn cluster_size(input: &str, within: i16) -> usize {
-    let coordinates = Coordinate::parse(input);
+    Coordinates::parse(input)
+        .and_then(|coordinates| coordinates.part2(&Params { within }))
+        .unwrap()
+}
 
-    if let Some(center) = Coordinate::manhattan_center(coordinates.iter()) {
-        center.expanding_search(|s, _search_distance| {
-            s.distance_between_all_others(coordinates.iter()) < within
-        })
-    } else {
-        0
+pub struct Coordinates(HashSet<Coordinate>);
+
+/// The safe region is every location whose total distance to all coordinates is under
+/// `within`.
+pub struct Params {
+    pub within: i16,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { within: 10000 }
     }
 }
 
-#[derive(Eq, PartialEq, Hash, Debug)]
-struct Coordinate {
-    x: i16,
-    y: i16,
-}
-
-impl Coordinate {
-    fn parse(input: &str) -> HashSet<Self> {
-        input
-            .lines()
-            .flat_map(|line| {
-                let mut parts = line.split(", ");
-                let first = parts.next();
-                let second = parts.next();
-                match (
-                    first.and_then(|v| v.parse().ok()),
-                    second.and_then(|v| v.parse().ok()),
-                ) {
-                    (Some(x), Some(y)) => Some(Coordinate { x, y }),
-                    _ => None,
-                }
-            }).collect()
+impl Solution for Coordinates {
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Coordinates(parse_coordinates(input)?))
     }
 
-    fn manhattan_center<'a, T: Iterator<Item = &'a Coordinate>>(all: T) -> Option<Coordinate> {
-        let mut count = 0;
-        let mut sum = (0, 0);
-        for coordinate in all {
-            count += 1;
-            sum.0 += coordinate.x;
-            sum.1 += coordinate.y;
-        }
-        if count > 0 {
-            Some(Coordinate {
-                x: sum.0 / count,
-                y: sum.1 / count,
-            })
+    fn part1(&self, _: &Params) -> Result<usize, Error> {
+        let coordinates = &self.0;
+
+        Ok(coordinates
+            .iter()
+            .filter(|&&c| !is_infinite(c, coordinates.iter()))
+            .map(|&c| area(c, coordinates))
+            .max()
+            .unwrap_or(0))
+    }
+
+    fn part2(&self, params: &Params) -> Result<usize, Error> {
+        let coordinates = &self.0;
+
+        if let Some(center) = manhattan_center(coordinates.iter()) {
+            Ok(expanding_search(center, |s, _search_distance| {
+                coordinates.iter().map(|&o| s.manhattan(o)).sum::<i16>() < params.within
+            }))
         } else {
-            None
+            Ok(0)
         }
     }
+}
 
-    fn in_cone_up(&self, other: &Coordinate) -> bool {
-        self.y > other.y && (self.x - other.x).abs() <= self.y - other.y
-    }
+type Coordinate = Vec2<i16>;
 
-    fn in_cone_right(&self, other: &Coordinate) -> bool {
-        self.x < other.x && (self.y - other.y).abs() <= other.x - self.x
-    }
+fn parse_coordinate(input: &str) -> IResult<&str, Coordinate> {
+    map(separated_pair(i16, tag(", "), i16), |(x, y)| {
+        Coordinate::new(x, y)
+    })(input)
+}
 
-    fn in_cone_down(&self, other: &Coordinate) -> bool {
-        self.y < other.y && (self.x - other.x).abs() <= other.y - self.y
-    }
+fn parse_coordinates(input: &str) -> Result<HashSet<Coordinate>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| aoc_core::finish(input, parse_coordinate(line)))
+        .collect()
+}
 
-    fn in_cone_left(&self, other: &Coordinate) -> bool {
-        self.x > other.x && (self.y - other.y).abs() <= self.x - other.x
+fn manhattan_center<'a, T: Iterator<Item = &'a Coordinate>>(all: T) -> Option<Coordinate> {
+    let mut count = 0;
+    let mut sum = Coordinate::ZERO;
+    for &coordinate in all {
+        count += 1;
+        sum += coordinate;
     }
-
-    fn is_infinite<'a, T: Iterator<Item = &'a Coordinate>>(&self, all: T) -> bool {
-        let mut infinite_up = true;
-        let mut infinite_right = true;
-        let mut infinite_down = true;
-        let mut infinite_left = true;
-        for other in all.filter(|&o| o != self) {
-            infinite_up = infinite_up && !self.in_cone_up(&other);
-            infinite_right = infinite_right && !self.in_cone_right(&other);
-            infinite_down = infinite_down && !self.in_cone_down(&other);
-            infinite_left = infinite_left && !self.in_cone_left(&other);
-            if !infinite_up && !infinite_right && !infinite_down && !infinite_left {
-                return false;
-            }
-        }
-        true
+    if count > 0 {
+        Some(sum / count)
+    } else {
+        None
     }
+}
 
-    fn coordinates_at_distance(&self, distance: i16) -> Vec<Coordinate> {
-        let mut coordinates = Vec::new();
-        for x in self.x - distance..=self.x + distance {
-            let x_distance = (x - self.x).abs();
-            let y_distance = distance - x_distance;
-            coordinates.push(Coordinate {
-                x,
-                y: self.y - y_distance,
-            });
-
-            if y_distance != 0 {
-                coordinates.push(Coordinate {
-                    x,
-                    y: self.y + y_distance,
-                });
-            }
+fn in_cone_up(c: Coordinate, other: Coordinate) -> bool {
+    c.y > other.y && (c.x - other.x).abs() <= c.y - other.y
+}
+
+fn in_cone_right(c: Coordinate, other: Coordinate) -> bool {
+    c.x < other.x && (c.y - other.y).abs() <= other.x - c.x
+}
+
+fn in_cone_down(c: Coordinate, other: Coordinate) -> bool {
+    c.y < other.y && (c.x - other.x).abs() <= other.y - c.y
+}
+
+fn in_cone_left(c: Coordinate, other: Coordinate) -> bool {
+    c.x > other.x && (c.y - other.y).abs() <= c.x - other.x
+}
+
+fn is_infinite<'a, T: Iterator<Item = &'a Coordinate>>(c: Coordinate, all: T) -> bool {
+    let mut infinite_up = true;
+    let mut infinite_right = true;
+    let mut infinite_down = true;
+    let mut infinite_left = true;
+    for &other in all.filter(|&&o| o != c) {
+        infinite_up = infinite_up && !in_cone_up(c, other);
+        infinite_right = infinite_right && !in_cone_right(c, other);
+        infinite_down = infinite_down && !in_cone_down(c, other);
+        infinite_left = infinite_left && !in_cone_left(c, other);
+        if !infinite_up && !infinite_right && !infinite_down && !infinite_left {
+            return false;
         }
-        coordinates
     }
+    true
+}
 
-    fn distance_between(&self, other: &Coordinate) -> i16 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
+fn coordinates_at_distance(c: Coordinate, distance: i16) -> Vec<Coordinate> {
+    let mut coordinates = Vec::new();
+    for x in c.x - distance..=c.x + distance {
+        let x_distance = (x - c.x).abs();
+        let y_distance = distance - x_distance;
+        coordinates.push(Coordinate::new(x, c.y - y_distance));
 
-    fn distance_between_all_others<'a, T: Iterator<Item = &'a Coordinate>>(&self, all: T) -> i16 {
-        all.map(|o| self.distance_between(o)).sum()
+        if y_distance != 0 {
+            coordinates.push(Coordinate::new(x, c.y + y_distance));
+        }
     }
+    coordinates
+}
 
-    fn expanding_search<F: Fn(&Coordinate, i16) -> bool>(&self, search_condition: F) -> usize {
-        let mut area = 0;
-        let mut search_distance = 0;
-        let mut found = true;
-        while found {
-            found = false;
-            for s in self.coordinates_at_distance(search_distance).iter() {
-                if search_condition(s, search_distance) {
-                    area += 1;
-                    found = true
-                }
+fn expanding_search<F: Fn(Coordinate, i16) -> bool>(c: Coordinate, search_condition: F) -> usize {
+    let mut area = 0;
+    let mut search_distance = 0;
+    let mut found = true;
+    while found {
+        found = false;
+        for &s in coordinates_at_distance(c, search_distance).iter() {
+            if search_condition(s, search_distance) {
+                area += 1;
+                found = true
             }
-            search_distance += 1;
         }
-        area
+        search_distance += 1;
     }
+    area
+}
 
-    fn area(&self, all_coordinates: &HashSet<Coordinate>) -> usize {
-        self.expanding_search(|s, search_distance| {
-            all_coordinates
-                .iter()
-                .filter(|&c| c != self)
-                .all(|c| c.distance_between(s) > search_distance)
-        })
-    }
+fn area(c: Coordinate, all_coordinates: &HashSet<Coordinate>) -> usize {
+    expanding_search(c, |s, search_distance| {
+        all_coordinates
+            .iter()
+            .filter(|&&o| o != c)
+            .all(|o| o.manhattan(s) > search_distance)
+    })
 }
 
 #[cfg(test)]
 mod largest_finite {
-    use largest_finite;
+    use crate::largest_finite;
     #[test]
     fn worked_example() {
         assert_eq!(largest_finite(include_str!("../example.txt")), 17);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(largest_finite(include_str!("../input.txt")), 5365);
+        let input = aoc_input::challenge(2018, 6);
+        assert_eq!(largest_finite(&input), 5365);
     }
 }
 
 #[cfg(test)]
 mod cluster_size {
-    use cluster_size;
+    use crate::cluster_size;
 
     #[test]
     fn worked_example() {
@@ -177,8 +192,9 @@ mod cluster_size {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(cluster_size(include_str!("../input.txt"), 10000), 42513);
+        let input = aoc_input::challenge(2018, 6);
+        assert_eq!(cluster_size(&input, 10000), 42513);
     }
-
 }
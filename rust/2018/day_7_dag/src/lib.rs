@@ -1,120 +1,109 @@
-#[macro_use]
-extern crate nom;
-
-use std::collections::BTreeSet;
-use std::collections::HashSet;
-use std::iter::repeat;
-use std::iter::FromIterator;
+use aoc_core::{Error, ParseError, Solution};
+use aoc_graph::Dependencies;
+use nom::{
+    bytes::complete::tag,
+    character::complete::satisfy,
+    combinator::map,
+    sequence::{delimited, pair, terminated},
+    IResult,
+};
 
 pub fn instruction_order(input: &str) -> String {
-    let mut prereqs = Prerequisite::parse_multiple(input).collect::<HashSet<_>>();
-    let mut unvisited = BTreeSet::new();
-    for p in prereqs.iter() {
-        unvisited.insert(p.requirement);
-        unvisited.insert(p.unblocks);
-    }
-    let mut output = String::with_capacity(unvisited.len());
-    while let Some(&next_step) = unvisited
-        .iter()
-        .find(|&&step| !prereqs.iter().any(|prereq| prereq.unblocks == step))
-    {
-        output.push(next_step as char);
-        prereqs.retain(|p| p.requirement != next_step);
-        unvisited.remove(&next_step);
+    aoc_core::part1::<Instructions>(input).unwrap()
+}
+
+pub fn parallelized_time(input: &str, workers: usize, a_time: u8) -> Option<u64> {
+    Instructions::parse(input)
+        .and_then(|instructions| instructions.part2(&Params { workers, a_time }))
+        .ok()
+}
+
+pub struct Instructions(Dependencies<u8>);
+
+/// How many workers build the sleigh, and how long step A takes them.
+pub struct Params {
+    pub workers: usize,
+    pub a_time: u8,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            workers: 5,
+            a_time: 61,
+        }
     }
-    output
 }
 
-pub fn parallelized_time(input: &str, workers: usize, a_time: u8) -> Option<usize> {
-    let mut prereqs = Prerequisite::parse_multiple(input).collect::<HashSet<_>>();
-    let mut workers = Vec::from_iter(repeat(None).take(workers));
-    let mut unvisited = BTreeSet::new();
-    let mut not_built = HashSet::new();
-    let mut time = 0usize;
-    for p in prereqs.iter() {
-        unvisited.insert(p.requirement);
-        unvisited.insert(p.unblocks);
-        not_built.insert(p.requirement);
-        not_built.insert(p.unblocks);
+impl Solution for Instructions {
+    type Params = Params;
+    type Part1 = String;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Instructions(
+            Prerequisite::parse_multiple(input)?
+                .into_iter()
+                .map(|p| (p.requirement, p.unblocks))
+                .collect(),
+        ))
     }
-    while !not_built.is_empty() {
-        for (&step, worker) in unvisited
-            .iter()
-            .filter(|&&step| !prereqs.iter().any(|prereq| prereq.unblocks == step))
-            .zip(workers.iter_mut().filter(|worker| worker.is_none()))
-        {
-            *worker = Some(Work {
-                what: step,
-                time_left: step - b'A' + a_time,
-            });
-        }
-        for step in workers.iter().flatten().map(|w| w.what) {
-            unvisited.remove(&step);
-        }
-        if let Some(time_jump) = workers.iter().flatten().map(|w| w.time_left).min() {
-            time += time_jump as usize;
-            for maybe_worker in workers.iter_mut() {
-                let mut done = false;
-                if let Some(worker) = maybe_worker {
-                    worker.time_left -= time_jump;
-                    if worker.time_left == 0 {
-                        done = true;
-                        prereqs.retain(|p| p.requirement != worker.what);
-                        not_built.remove(&worker.what);
-                    }
-                }
-                if done {
-                    *maybe_worker = None;
-                }
-            }
-        } else {
-            return None;
-        }
+
+    fn part1(&self, _: &Params) -> Result<String, Error> {
+        let order = self
+            .0
+            .order()
+            .map_err(|cycle| Error::no_answer(cycle.to_string()))?;
+        Ok(order.into_iter().map(char::from).collect())
     }
 
-    Some(time)
+    fn part2(&self, params: &Params) -> Result<u64, Error> {
+        if params.workers == 0 {
+            return Err(Error::no_answer("no step can be started"));
+        }
+        let schedule = self
+            .0
+            .schedule(params.workers, |&step| {
+                u64::from(step - b'A' + params.a_time)
+            })
+            .map_err(|cycle| Error::no_answer(cycle.to_string()))?;
+        Ok(schedule.finish)
+    }
 }
 
-#[derive(Debug, Eq, Hash, PartialEq)]
+#[derive(Debug)]
 struct Prerequisite {
     requirement: u8,
     unblocks: u8,
 }
 
 impl Prerequisite {
-    fn parse_multiple<'a>(input: &'a str) -> impl Iterator<Item = Prerequisite> + 'a {
+    fn parse_multiple(input: &str) -> Result<Vec<Prerequisite>, ParseError> {
         input
             .lines()
-            .map(str::as_bytes)
-            .flat_map(Self::parse)
-            .map(|(_r, p)| p)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| aoc_core::finish(input, Self::parse(line)))
+            .collect()
     }
 
-    named!(
-        parse<&[u8], Self>,
-        do_parse!(
-            tag_s!("Step ")
-                >> requirement: map_opt!(take!(1), (|bs: &[u8]| bs.get(0).cloned()))
-                >> tag_s!(" must be finished before step ")
-                >> unblocks: map_opt!(take!(1), (|bs: &[u8]| bs.get(0).cloned()))
-                >> tag_s!(" can begin.")
-                >> (Self {
-                    requirement,
-                    unblocks
-                })
-        )
-    );
-}
-
-#[derive(Clone, PartialEq, Debug)]
-struct Work {
-    what: u8,
-    time_left: u8,
+    fn parse(input: &str) -> IResult<&str, Self> {
+        let step = || map(satisfy(|c| c.is_ascii_uppercase()), |c| c as u8);
+        map(
+            pair(
+                delimited(tag("Step "), step(), tag(" must be finished before step ")),
+                terminated(step(), tag(" can begin.")),
+            ),
+            |(requirement, unblocks)| Self {
+                requirement,
+                unblocks,
+            },
+        )(input)
+    }
 }
 
 #[cfg(test)]
 mod instruction_order_tests {
-    use instruction_order;
+    use crate::instruction_order;
 
     #[test]
     fn worked_example() {
@@ -125,9 +114,11 @@ mod instruction_order_tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
+        let input = aoc_input::challenge(2018, 7);
         assert_eq!(
-            instruction_order(include_str!("../input.txt")),
+            instruction_order(&input),
             "ADEFKLBVJQWUXCNGORTMYSIHPZ".to_string()
         );
     }
@@ -135,7 +126,7 @@ mod instruction_order_tests {
 
 #[cfg(test)]
 mod parallelized_time_tests {
-    use parallelized_time;
+    use crate::parallelized_time;
 
     #[test]
     fn worked_example() {
@@ -146,11 +137,9 @@ mod parallelized_time_tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(
-            parallelized_time(include_str!("../input.txt"), 5, 61),
-            Some(1120)
-        );
+        let input = aoc_input::challenge(2018, 7);
+        assert_eq!(parallelized_time(&input, 5, 61), Some(1120));
     }
-
 }
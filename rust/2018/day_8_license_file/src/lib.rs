@@ -1,39 +1,34 @@
-#[macro_use]
-extern crate nom;
+use aoc_core::{Error, Solution};
+use nom::{
+    character::complete::{digit1, multispace0},
+    combinator::map_res,
+    multi::count,
+    sequence::preceded,
+    IResult,
+};
 
 pub fn metadata_sum(input: &str) -> Option<u32> {
-    if let Ok((_rest, node)) = Node::parse(&to_byte_array(input)) {
-        Some(node.metadata_sum())
-    } else {
-        None
-    }
+    aoc_core::part1::<Node>(input).ok()
 }
 
 pub fn value(input: &str) -> Option<u32> {
-    if let Ok((_rest, node)) = Node::parse(&to_byte_array(input)) {
-        Some(node.value())
-    } else {
-        None
-    }
+    aoc_core::part2::<Node>(input).ok()
 }
 
 #[derive(Debug)]
-struct Node {
+pub struct Node {
     children: Vec<Node>,
     metadata: Vec<u32>,
 }
 
 impl Node {
-    named!(
-        parse<&[u8], Self>,
-        do_parse!(
-            child_count: map_opt!(take!(1), to_num::<usize>)
-            >> metadata_count: map_opt!(take!(1), to_num::<usize>)
-            >> children: count!( Self::parse, child_count )
-            >> metadata: count!( map_opt!(take!(1), to_num::<u32>), metadata_count)
-            >> (Self { children, metadata })
-        )
-    );
+    fn parse_node(input: &str) -> IResult<&str, Self> {
+        let (input, child_count) = number(input)?;
+        let (input, metadata_count) = number(input)?;
+        let (input, children) = count(Self::parse_node, child_count)(input)?;
+        let (input, metadata) = count(number, metadata_count)(input)?;
+        Ok((input, Self { children, metadata }))
+    }
 
     fn metadata_sum(&self) -> u32 {
         self.metadata.iter().cloned().sum::<u32>()
@@ -52,23 +47,31 @@ impl Node {
     }
 }
 
-fn to_byte_array(input: &str) -> Vec<u8> {
-    input
-        .split_whitespace()
-        .flat_map(|v| u8::from_str_radix(v, 10))
-        .collect()
+impl Solution for Node {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Node::parse_node(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.metadata_sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.value())
+    }
 }
 
-fn to_num<T>(v: &[u8]) -> Option<T>
-where
-    T: From<u8>,
-{
-    v.get(0).map(|v| T::from(*v))
+fn number<T: std::str::FromStr>(input: &str) -> IResult<&str, T> {
+    preceded(multispace0, map_res(digit1, str::parse))(input)
 }
 
 #[cfg(test)]
 mod metadata_sum_test {
-    use metadata_sum;
+    use crate::metadata_sum;
 
     #[test]
     fn worked_example() {
@@ -76,14 +79,16 @@ mod metadata_sum_test {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(metadata_sum(include_str!("../input.txt")), Some(41028));
+        let input = aoc_input::challenge(2018, 8);
+        assert_eq!(metadata_sum(&input), Some(41028));
     }
 }
 
 #[cfg(test)]
 mod value_test {
-    use value;
+    use crate::value;
 
     #[test]
     fn worked_example() {
@@ -91,7 +96,9 @@ mod value_test {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn puzzle() {
-        assert_eq!(value(include_str!("../input.txt")), Some(20849));
+        let input = aoc_input::challenge(2018, 8);
+        assert_eq!(value(&input), Some(20849));
     }
 }
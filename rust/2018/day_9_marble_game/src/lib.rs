@@ -1,5 +1,12 @@
+use aoc_core::{Error, Solution};
+use nom::{
+    bytes::complete::tag,
+    character::complete::u32,
+    combinator::{map, verify},
+    sequence::{separated_pair, terminated},
+    IResult,
+};
 use std::collections::LinkedList;
-use std::iter::repeat;
 
 pub fn winning_score(players: usize, rounds: u32) -> u32 {
     let mut state = GameState::new(players);
@@ -9,6 +16,44 @@ pub fn winning_score(players: usize, rounds: u32) -> u32 {
     *state.scores.iter().max().unwrap_or(&0)
 }
 
+/// The game described by a line like "10 players; last marble is worth 1618 points".
+pub struct Game {
+    players: usize,
+    last_marble: u32,
+}
+
+fn parse_game(input: &str) -> IResult<&str, Game> {
+    map(
+        separated_pair(
+            verify(u32, |&players| players > 0),
+            tag(" players; last marble is worth "),
+            terminated(u32, tag(" points")),
+        ),
+        |(players, last_marble)| Game {
+            players: players as usize,
+            last_marble,
+        },
+    )(input)
+}
+
+impl Solution for Game {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, parse_game(input.trim_start()))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        Ok(winning_score(self.players, self.last_marble))
+    }
+
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        Ok(winning_score(self.players, self.last_marble * 100))
+    }
+}
+
 #[derive(Debug)]
 struct GameState {
     circle: LinkedList<u32>,
@@ -21,12 +66,12 @@ impl GameState {
         Self {
             circle: LinkedList::new(),
             turn: 0,
-            scores: repeat(0).take(players).collect(),
+            scores: std::iter::repeat_n(0, players).collect(),
         }
     }
 
     fn play_move(&mut self) {
-        match (self.turn == 0, self.turn % 23 == 0) {
+        match (self.turn == 0, self.turn.is_multiple_of(23)) {
             (true, _) => self.circle.push_front(self.turn),
             (false, true) => {
                 self.move_head(7, false);
@@ -56,7 +101,7 @@ impl GameState {
 
 #[cfg(test)]
 mod winning_score_tests {
-    use winning_score;
+    use crate::winning_score;
 
     #[test]
     fn worked_example() {
@@ -97,5 +142,4 @@ mod winning_score_tests {
     fn puzzle_part_2() {
         assert_eq!(winning_score(476, 71431 * 100), 3066307353);
     }
-
 }
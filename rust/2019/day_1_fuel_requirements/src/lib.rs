@@ -0,0 +1,100 @@
+use aoc_core::{Error, Solution};
+
+pub fn fuel_required(mass: u64) -> u64 {
+    (mass / 3).saturating_sub(2)
+}
+
+pub fn accumulative_fuel_required(mut mass: u64) -> u64 {
+    let mut sum = 0;
+    while mass != 0 {
+        mass = fuel_required(mass);
+        sum += mass;
+    }
+    sum
+}
+
+pub struct Modules {
+    masses: Vec<u64>,
+}
+
+impl Solution for Modules {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let masses = input
+            .lines()
+            .map(|line| {
+                line.parse()
+                    .map_err(|_| Error::parse(input, line, "a mass"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { masses })
+    }
+
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        Ok(self.masses.iter().copied().map(fuel_required).sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        Ok(self
+            .masses
+            .iter()
+            .copied()
+            .map(accumulative_fuel_required)
+            .sum())
+    }
+}
+
+pub fn part_one(input: &str) -> u64 {
+    aoc_core::part1::<Modules>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    aoc_core::part2::<Modules>(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuel_required() {
+        assert_eq!(fuel_required(12), 2);
+        assert_eq!(fuel_required(14), 2);
+        assert_eq!(fuel_required(1969), 654);
+        assert_eq!(fuel_required(100756), 33583);
+    }
+
+    #[test]
+    fn test_accumulative_fuel_required() {
+        assert_eq!(accumulative_fuel_required(14), 2);
+        assert_eq!(accumulative_fuel_required(1969), 966);
+        assert_eq!(accumulative_fuel_required(100756), 50346);
+    }
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(part_one(include_str!("../example_1.txt")), 34241);
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn challenge_part_one() {
+        let input = aoc_input::challenge(2019, 1);
+        assert_eq!(part_one(&input), 3254441);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(part_two(include_str!("../example_1.txt")), 51316);
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn challenge_part_two() {
+        let input = aoc_input::challenge(2019, 1);
+        assert_eq!(part_two(&input), 4878818);
+    }
+}
@@ -0,0 +1,102 @@
+use aoc_core::{Error, Solution};
+use intcode::{Fault, Machine, Value};
+
+/// Runs the program, returning its memory once it halts.
+pub fn evaluate(memory: &[Value]) -> Result<Vec<Value>, Fault> {
+    let mut computer = Machine::new(memory);
+    computer.run()?;
+    Ok(computer.memory().to_vec())
+}
+
+pub struct GravityAssist {
+    program: Vec<Value>,
+}
+
+impl GravityAssist {
+    /// What the program leaves at address 0 when run with the given noun and verb.
+    fn output(&self, noun: Value, verb: Value) -> Result<Value, Error> {
+        let mut memory = self.program.clone();
+        if memory.len() < 3 {
+            return Err(Error::no_answer(
+                "the program has no room for a noun and verb",
+            ));
+        }
+        memory[1] = noun;
+        memory[2] = verb;
+        evaluate(&memory)
+            .map(|memory| memory[0])
+            .map_err(|fault| Error::no_answer(fault.to_string()))
+    }
+}
+
+impl Solution for GravityAssist {
+    type Params = ();
+    type Part1 = Value;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            program: intcode::parse(input)?,
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<Value, Error> {
+        self.output(12, 2)
+    }
+
+    fn part2(&self, _: &()) -> Result<Value, Error> {
+        for noun in 0..=99 {
+            for verb in 0..=99 {
+                if self.output(noun, verb)? == 19690720 {
+                    return Ok(100 * noun + verb);
+                }
+            }
+        }
+        Err(Error::no_answer("no noun and verb produce 19690720"))
+    }
+}
+
+pub fn part_one(input: &str) -> Value {
+    aoc_core::part1::<GravityAssist>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> Value {
+    aoc_core::part2::<GravityAssist>(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(
+            evaluate(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]),
+            Ok(vec!(3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50))
+        );
+        assert_eq!(evaluate(&[1, 0, 0, 0, 99]), Ok(vec!(2, 0, 0, 0, 99)));
+        assert_eq!(evaluate(&[2, 3, 0, 3, 99]), Ok(vec!(2, 3, 0, 6, 99)));
+        assert_eq!(
+            evaluate(&[2, 4, 4, 5, 99, 0]),
+            Ok(vec!(2, 4, 4, 5, 99, 9801))
+        );
+        assert_eq!(
+            evaluate(&[1, 1, 1, 4, 99, 5, 6, 0, 99]),
+            Ok(vec!(30, 1, 1, 4, 2, 5, 6, 0, 99))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn challenge_part_one() {
+        let input = aoc_input::challenge(2019, 2);
+        assert_eq!(part_one(&input), 6087827);
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
+    fn challenge_part_two() {
+        let input = aoc_input::challenge(2019, 2);
+        assert_eq!(part_two(&input), 5379);
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+}
+
+impl Opcode {
+    fn from_value(value: Value) -> Option<Self> {
+        Some(match value {
+            1 => Self::Add,
+            2 => Self::Multiply,
+            3 => Self::Input,
+            4 => Self::Output,
+            5 => Self::JumpIfTrue,
+            6 => Self::JumpIfFalse,
+            7 => Self::LessThan,
+            8 => Self::Equals,
+            9 => Self::AdjustRelativeBase,
+            99 => Self::Halt,
+            _ => return None,
+        })
+    }
+
+    /// How many parameters follow the opcode.
+    pub fn arity(self) -> usize {
+        match self {
+            Self::Add | Self::Multiply | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Multiply => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jnz",
+            Self::JumpIfFalse => "jz",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::AdjustRelativeBase => "arb",
+            Self::Halt => "halt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The parameter is an address.
+    Position,
+    /// The parameter is the value itself.
+    Immediate,
+    /// The parameter is an address relative to the relative base.
+    Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub mode: Mode,
+    pub value: Value,
+}
+
+/// `[a]` for a position, `a` for an immediate and `[rb+a]` for a relative address.
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            Mode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub parameters: Vec<Parameter>,
+}
+
+impl Instruction {
+    /// Decodes the instruction at the start of `memory`. Returns `None` if the first value
+    /// isn't an opcode with valid modes, and treats memory past the end as zeros.
+    pub fn decode(memory: &[Value]) -> Option<Self> {
+        let &first = memory.first()?;
+        let opcode = Opcode::from_value(first.rem_euclid(100))?;
+        let mut modes = first / 100;
+        let mut parameters = Vec::with_capacity(opcode.arity());
+        for i in 1..=opcode.arity() {
+            let mode = match modes % 10 {
+                0 => Mode::Position,
+                1 => Mode::Immediate,
+                2 => Mode::Relative,
+                _ => return None,
+            };
+            modes /= 10;
+            let value = memory.get(i).copied().unwrap_or(0);
+            parameters.push(Parameter { mode, value });
+        }
+        (modes == 0).then_some(Self { opcode, parameters })
+    }
+
+    /// How many values the instruction takes up.
+    pub fn size(&self) -> usize {
+        1 + self.parameters.len()
+    }
+}
+
+/// The mnemonic then the parameters, with the one written to after an arrow.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        let writes = matches!(
+            self.opcode,
+            Opcode::Add | Opcode::Multiply | Opcode::Input | Opcode::LessThan | Opcode::Equals
+        );
+        let (reads, written) = match self.parameters.split_last() {
+            Some((last, reads)) if writes => (reads, Some(last)),
+            _ => (self.parameters.as_slice(), None),
+        };
+        for (i, parameter) in reads.iter().enumerate() {
+            write!(f, "{}{parameter}", if i == 0 { " " } else { ", " })?;
+        }
+        if let Some(written) = written {
+            write!(f, " -> {written}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the program one instruction per line, each after its address. Anything that doesn't
+/// decode is listed as data, one value at a time, so a listing goes wrong where code and data
+/// are mixed but carries on.
+pub fn disassemble(program: &[Value]) -> String {
+    let mut listing = String::new();
+    let mut address = 0;
+    while address < program.len() {
+        match Instruction::decode(&program[address..]) {
+            Some(instruction) => {
+                listing += &format!("{address:>5}: {instruction}\n");
+                address += instruction.size();
+            }
+            None => {
+                listing += &format!("{address:>5}: data {}\n", program[address]);
+                address += 1;
+            }
+        }
+    }
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_modes() {
+        let instruction = Instruction::decode(&[1002, 4, 3, 4, 33]).unwrap();
+        assert_eq!(instruction.opcode, Opcode::Multiply);
+        assert_eq!(
+            instruction
+                .parameters
+                .iter()
+                .map(|p| p.mode)
+                .collect::<Vec<_>>(),
+            [Mode::Position, Mode::Immediate, Mode::Position]
+        );
+        assert_eq!(instruction.to_string(), "mul [4], 3 -> [4]");
+        assert_eq!(
+            Instruction::decode(&[204, -3]).unwrap().to_string(),
+            "out [rb-3]"
+        );
+        assert_eq!(Instruction::decode(&[42]), None);
+        assert_eq!(Instruction::decode(&[11104, 0]), None);
+    }
+
+    #[test]
+    fn lists_code_and_data() {
+        assert_eq!(
+            disassemble(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]),
+            "    0: add [9], [10] -> [3]\n    \
+                 4: mul [3], [11] -> [0]\n    \
+                 8: halt\n    \
+                 9: data 30\n   \
+                10: data 40\n   \
+                11: data 50\n"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! The Intcode computer that 2019 keeps coming back to.
+//!
+//! A [`Machine`] runs a program until it halts or wants input it hasn't been given, so several
+//! can be chained, or run round robin as a network, by moving each one's output to the next
+//! one's input. [`disassemble`] lists a program's instructions, and [`Machine::trace`] runs
+//! one while reporting each instruction as it goes.
+mod instruction;
+mod machine;
+
+pub use instruction::{disassemble, Instruction, Mode, Opcode, Parameter};
+pub use machine::{Fault, Machine, Status, Step};
+
+use aoc_core::ParseError;
+
+pub type Value = i64;
+
+/// Reads a program's comma separated values.
+pub fn parse(input: &str) -> Result<Vec<Value>, ParseError> {
+    input
+        .trim_end()
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse()
+                .map_err(|_| ParseError::new(input, v, "a number"))
+        })
+        .collect()
+}
+
+/// Runs a program to the end on the given input, returning everything it output.
+pub fn run(program: &[Value], input: impl IntoIterator<Item = Value>) -> Result<Vec<Value>, Fault> {
+    let mut machine = Machine::new(program);
+    machine.extend_input(input);
+    match machine.run()? {
+        Status::Halted => Ok(machine.take_output()),
+        _ => Err(Fault::OutOfInput { ip: machine.ip() }),
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::instruction::{Instruction, Mode, Opcode, Parameter};
+use crate::Value;
+
+/// Why a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// It's ready to carry on.
+    Running,
+    /// It's waiting for input. Give it some and run it again.
+    AwaitingInput,
+    /// It reached a halt instruction.
+    Halted,
+}
+
+/// Something a program did that it can't have meant to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The value at `ip` isn't an instruction.
+    BadInstruction { ip: usize, value: Value },
+    /// The instruction at `ip` used a negative address.
+    NegativeAddress { ip: usize, address: Value },
+    /// The instruction at `ip` tried to write to an immediate parameter.
+    ImmediateWrite { ip: usize },
+    /// The program at `ip` wanted input when there wasn't going to be any more.
+    OutOfInput { ip: usize },
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadInstruction { ip, value } => write!(f, "{value} at {ip} isn't an instruction"),
+            Self::NegativeAddress { ip, address } => {
+                write!(
+                    f,
+                    "the instruction at {ip} used the negative address {address}"
+                )
+            }
+            Self::ImmediateWrite { ip } => {
+                write!(f, "the instruction at {ip} wrote to an immediate parameter")
+            }
+            Self::OutOfInput { ip } => write!(f, "the instruction at {ip} ran out of input"),
+        }
+    }
+}
+
+impl std::error::Error for Fault {}
+
+/// An instruction as it's about to run, for [`Machine::trace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub relative_base: Value,
+    pub instruction: Instruction,
+    /// What each parameter read as, or the address for the one it writes to.
+    pub operands: Vec<Value>,
+}
+
+/// The address, the instruction, then what its parameters came to.
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5}: {:<24}", self.ip, self.instruction.to_string())?;
+        let operands: Vec<_> = self.operands.iter().map(Value::to_string).collect();
+        write!(f, "({}) rb={}", operands.join(", "), self.relative_base)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Machine {
+    memory: Vec<Value>,
+    ip: usize,
+    relative_base: Value,
+    input: VecDeque<Value>,
+    output: VecDeque<Value>,
+}
+
+impl Machine {
+    pub fn new(program: &[Value]) -> Self {
+        Self {
+            memory: program.to_vec(),
+            ..Self::default()
+        }
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Memory as far as it's been used. Everything past the end is zero.
+    pub fn memory(&self) -> &[Value] {
+        &self.memory
+    }
+
+    pub fn read(&self, address: usize) -> Value {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    /// Writes a value, growing memory to fit.
+    pub fn write(&mut self, address: usize, value: Value) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+    }
+
+    pub fn push_input(&mut self, value: Value) {
+        self.input.push_back(value);
+    }
+
+    pub fn extend_input(&mut self, values: impl IntoIterator<Item = Value>) {
+        self.input.extend(values);
+    }
+
+    /// The oldest output not taken yet.
+    pub fn pop_output(&mut self) -> Option<Value> {
+        self.output.pop_front()
+    }
+
+    /// All the output not taken yet.
+    pub fn take_output(&mut self) -> Vec<Value> {
+        self.output.drain(..).collect()
+    }
+
+    /// Runs until the program halts or needs more input.
+    pub fn run(&mut self) -> Result<Status, Fault> {
+        self.trace(|_| {})
+    }
+
+    /// Runs until the program outputs something, halts or needs more input. Returns the output
+    /// if that's why it stopped.
+    pub fn run_until_output(&mut self) -> Result<Option<Value>, Fault> {
+        loop {
+            if let Some(value) = self.pop_output() {
+                return Ok(Some(value));
+            }
+            if self.step()? != Status::Running {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Runs like [`Machine::run`], passing each instruction to `log` before it runs.
+    pub fn trace(&mut self, mut log: impl FnMut(&Step)) -> Result<Status, Fault> {
+        loop {
+            let status = self.step_with(&mut log)?;
+            if status != Status::Running {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Runs one instruction. A machine that has halted, or is waiting for input it doesn't
+    /// have, stays where it is.
+    pub fn step(&mut self) -> Result<Status, Fault> {
+        self.step_with(|_| {})
+    }
+
+    /// The instruction that runs next.
+    pub fn next_instruction(&self) -> Result<Instruction, Fault> {
+        let memory = self.memory.get(self.ip..).unwrap_or_default();
+        Instruction::decode(memory).ok_or(Fault::BadInstruction {
+            ip: self.ip,
+            value: self.read(self.ip),
+        })
+    }
+
+    fn step_with(&mut self, mut log: impl FnMut(&Step)) -> Result<Status, Fault> {
+        let instruction = self.next_instruction()?;
+        match instruction.opcode {
+            Opcode::Halt => return Ok(Status::Halted),
+            Opcode::Input if self.input.is_empty() => return Ok(Status::AwaitingInput),
+            _ => {}
+        }
+        let operands = self.operands(&instruction)?;
+        log(&Step {
+            ip: self.ip,
+            relative_base: self.relative_base,
+            instruction: instruction.clone(),
+            operands: operands.clone(),
+        });
+        let target = || operands[operands.len() - 1] as usize;
+        let mut next = self.ip + instruction.size();
+        match instruction.opcode {
+            Opcode::Add => self.write(target(), operands[0] + operands[1]),
+            Opcode::Multiply => self.write(target(), operands[0] * operands[1]),
+            Opcode::Input => {
+                let value = self.input.pop_front().expect("checked above");
+                self.write(target(), value);
+            }
+            Opcode::Output => self.output.push_back(operands[0]),
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                if (operands[0] != 0) == (instruction.opcode == Opcode::JumpIfTrue) {
+                    next = self.address(operands[1])?;
+                }
+            }
+            Opcode::LessThan => self.write(target(), (operands[0] < operands[1]).into()),
+            Opcode::Equals => self.write(target(), (operands[0] == operands[1]).into()),
+            Opcode::AdjustRelativeBase => self.relative_base += operands[0],
+            Opcode::Halt => unreachable!("handled above"),
+        }
+        self.ip = next;
+        Ok(Status::Running)
+    }
+
+    /// The values of the parameters it reads, then the address of the one it writes to.
+    fn operands(&self, instruction: &Instruction) -> Result<Vec<Value>, Fault> {
+        let writes = matches!(
+            instruction.opcode,
+            Opcode::Add | Opcode::Multiply | Opcode::Input | Opcode::LessThan | Opcode::Equals
+        );
+        let count = instruction.parameters.len();
+        instruction
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(i, &parameter)| {
+                if writes && i == count - 1 {
+                    if parameter.mode == Mode::Immediate {
+                        return Err(Fault::ImmediateWrite { ip: self.ip });
+                    }
+                    self.parameter_address(parameter).map(|a| a as Value)
+                } else {
+                    self.parameter_value(parameter)
+                }
+            })
+            .collect()
+    }
+
+    fn parameter_value(&self, parameter: Parameter) -> Result<Value, Fault> {
+        match parameter.mode {
+            Mode::Immediate => Ok(parameter.value),
+            _ => self.parameter_address(parameter).map(|a| self.read(a)),
+        }
+    }
+
+    fn parameter_address(&self, parameter: Parameter) -> Result<usize, Fault> {
+        match parameter.mode {
+            Mode::Position => self.address(parameter.value),
+            Mode::Relative => self.address(self.relative_base + parameter.value),
+            Mode::Immediate => unreachable!("immediate parameters have no address"),
+        }
+    }
+
+    fn address(&self, address: Value) -> Result<usize, Fault> {
+        usize::try_from(address).map_err(|_| Fault::NegativeAddress {
+            ip: self.ip,
+            address,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run;
+
+    #[test]
+    fn multiplies_with_an_immediate() {
+        let mut machine = Machine::new(&[1002, 4, 3, 4, 33]);
+        assert_eq!(machine.run(), Ok(Status::Halted));
+        assert_eq!(machine.memory(), [1002, 4, 3, 4, 99]);
+    }
+
+    #[test]
+    fn compares_its_input() {
+        // each outputs whether its input is equal to, or less than, 8
+        let position_equal = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        let immediate_less = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run(&position_equal, [8]), Ok(vec![1]));
+        assert_eq!(run(&position_equal, [7]), Ok(vec![0]));
+        assert_eq!(run(&immediate_less, [7]), Ok(vec![1]));
+        assert_eq!(run(&immediate_less, [9]), Ok(vec![0]));
+    }
+
+    #[test]
+    fn jumps() {
+        let program = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(run(&program, [7]), Ok(vec![999]));
+        assert_eq!(run(&program, [8]), Ok(vec![1000]));
+        assert_eq!(run(&program, [9]), Ok(vec![1001]));
+    }
+
+    #[test]
+    fn copies_itself_using_relative_addresses() {
+        let quine = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run(&quine, []), Ok(quine.to_vec()));
+    }
+
+    #[test]
+    fn handles_large_numbers() {
+        assert_eq!(
+            run(&[1102, 34915192, 34463338, 7, 4, 7, 99, 0], []),
+            Ok(vec![34915192 * 34463338])
+        );
+        assert_eq!(
+            run(&[104, 1125899906842624, 99], []),
+            Ok(vec![1125899906842624])
+        );
+    }
+
+    #[test]
+    fn waits_for_input() {
+        // echoes its input until it reads a zero
+        let mut machine = Machine::new(&[3, 11, 4, 11, 1005, 11, 0, 99, 0, 0, 0, 0]);
+        assert_eq!(machine.run(), Ok(Status::AwaitingInput));
+        machine.extend_input([5, 6]);
+        assert_eq!(machine.run(), Ok(Status::AwaitingInput));
+        assert_eq!(machine.take_output(), [5, 6]);
+        machine.push_input(0);
+        assert_eq!(machine.run_until_output(), Ok(Some(0)));
+        assert_eq!(machine.run_until_output(), Ok(None));
+        assert_eq!(machine.run(), Ok(Status::Halted));
+    }
+
+    #[test]
+    fn chains_machines() {
+        // doubles its input
+        let program = [3, 9, 1002, 9, 2, 9, 4, 9, 99, 0];
+        let mut machines = vec![Machine::new(&program); 4];
+        let mut signal = 1;
+        for machine in &mut machines {
+            machine.push_input(signal);
+            machine.run().unwrap();
+            signal = machine.pop_output().unwrap();
+        }
+        assert_eq!(signal, 16);
+    }
+
+    #[test]
+    fn reports_faults() {
+        assert_eq!(
+            Machine::new(&[1, 0, 0, 0, 42]).run(),
+            Err(Fault::BadInstruction { ip: 4, value: 42 })
+        );
+        assert_eq!(
+            Machine::new(&[4, -1, 99]).run(),
+            Err(Fault::NegativeAddress { ip: 0, address: -1 })
+        );
+        assert_eq!(
+            Machine::new(&[11101, 1, 1, 0, 99]).run(),
+            Err(Fault::ImmediateWrite { ip: 0 })
+        );
+        assert_eq!(run(&[3, 0, 99], []), Err(Fault::OutOfInput { ip: 0 }));
+    }
+
+    #[test]
+    fn traces_each_instruction() {
+        let mut lines = Vec::new();
+        let mut machine = Machine::new(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
+        machine.trace(|step| lines.push(step.to_string())).unwrap();
+        assert_eq!(
+            lines,
+            [
+                "    0: add [9], [10] -> [3]    (30, 40, 3) rb=0",
+                "    4: mul [3], [11] -> [0]    (70, 50, 0) rb=0",
+            ]
+        );
+        assert_eq!(machine.read(0), 3500);
+    }
+}
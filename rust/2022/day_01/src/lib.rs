@@ -1,20 +1,41 @@
 use std::collections::BinaryHeap;
 
+use aoc_core::{Error, Solution};
+
+pub struct Elves {
+    totals: Vec<u64>,
+}
+
+impl Solution for Elves {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let totals = input
+            .split("\n\n")
+            .map(|elf| elf.lines().map(|item| item.parse().unwrap_or(0)).sum())
+            .collect();
+        Ok(Self { totals })
+    }
+
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        Ok(self.totals.iter().copied().max().unwrap_or(0))
+    }
+
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        let mut totals: BinaryHeap<_> = self.totals.iter().copied().collect();
+
+        Ok((0..3).map(|_| totals.pop().unwrap_or(0)).sum())
+    }
+}
+
 pub fn part_one(input: &str) -> u64 {
-    input
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|item| item.parse().unwrap_or(0)).sum())
-        .max()
-        .unwrap_or(0)
+    aoc_core::part1::<Elves>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> u64 {
-    let mut totals: BinaryHeap<_> = input
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|item| item.parse().unwrap_or(0)).sum())
-        .collect();
-
-    (0..3).map(|_| totals.pop().unwrap_or(0)).sum()
+    aoc_core::part2::<Elves>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -27,8 +48,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge_1.txt")), 72602);
+        let input = aoc_input::challenge(2022, 1);
+        assert_eq!(part_one(&input), 72602);
     }
 
     #[test]
@@ -37,7 +60,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge_1.txt")), 207410);
+        let input = aoc_input::challenge(2022, 1);
+        assert_eq!(part_two(&input), 207410);
     }
 }
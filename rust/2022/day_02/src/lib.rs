@@ -6,24 +6,28 @@
 // Draw: 1
 // Win: 2
 
+use aoc_core::{Error, Solution};
+use nom::{
+    character::complete::{char, one_of},
+    combinator::map,
+    sequence::separated_pair,
+    IResult,
+};
+
 struct Round {
     outcome: u8,
     ours: u8,
 }
 
 impl Round {
-    fn new_from_our_move(input: &[u8]) -> Option<Self> {
-        let theirs = input.first()? - b'A';
-        let ours = input.get(2)? - b'X';
+    fn new_from_our_move(theirs: u8, ours: u8) -> Self {
         let outcome = (ours + 4 - theirs) % 3;
-        Some(Self { outcome, ours })
+        Self { outcome, ours }
     }
 
-    fn new_from_round_outcome(input: &[u8]) -> Option<Self> {
-        let theirs = input.first()? - b'A';
-        let outcome = input.get(2)? - b'X';
+    fn new_from_round_outcome(theirs: u8, outcome: u8) -> Self {
         let ours = (outcome + 2 + theirs) % 3;
-        Some(Self { ours, outcome })
+        Self { ours, outcome }
     }
 
     fn score(&self) -> u64 {
@@ -31,20 +35,56 @@ impl Round {
     }
 }
 
+/// A line of the guide: their move as `A`, `B` or `C`, then `X`, `Y` or `Z`, each as 0 to 2.
+fn parse_round(input: &str) -> IResult<&str, (u8, u8)> {
+    map(
+        separated_pair(one_of("ABC"), char(' '), one_of("XYZ")),
+        |(theirs, second)| (theirs as u8 - b'A', second as u8 - b'X'),
+    )(input)
+}
+
+/// Their move, and the second column that part one and two read differently.
+pub struct StrategyGuide {
+    rounds: Vec<(u8, u8)>,
+}
+
+impl StrategyGuide {
+    fn total_score(&self, round: fn(u8, u8) -> Round) -> u64 {
+        self.rounds
+            .iter()
+            .map(|&(theirs, second)| round(theirs, second).score())
+            .sum()
+    }
+}
+
+impl Solution for StrategyGuide {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let rounds = input
+            .lines()
+            .map(|line| aoc_core::finish(input, parse_round(line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rounds })
+    }
+
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        Ok(self.total_score(Round::new_from_our_move))
+    }
+
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        Ok(self.total_score(Round::new_from_round_outcome))
+    }
+}
+
 pub fn part_one(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|line| Round::new_from_our_move(line.as_bytes()))
-        .map(|r| Round::score(&r))
-        .sum()
+    aoc_core::part1::<StrategyGuide>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|line| Round::new_from_round_outcome(line.as_bytes()))
-        .map(|r| Round::score(&r))
-        .sum()
+    aoc_core::part2::<StrategyGuide>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -57,8 +97,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge_1.txt")), 12855);
+        let input = aoc_input::challenge(2022, 2);
+        assert_eq!(part_one(&input), 12855);
     }
 
     #[test]
@@ -67,7 +109,20 @@ mod tests {
     }
 
     #[test]
+    fn rejects_moves_outside_the_guide() {
+        let error = |input| match StrategyGuide::parse(input) {
+            Err(Error::Parse(e)) => (e.line, e.column),
+            _ => panic!("{input:?} should not parse"),
+        };
+        assert_eq!(error("A Y\n1 2 3"), (2, 1));
+        assert_eq!(error("A Y\nB W"), (2, 3));
+        assert_eq!(error("garbage"), (1, 1));
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge_1.txt")), 13726);
+        let input = aoc_input::challenge(2022, 2);
+        assert_eq!(part_two(&input), 13726);
     }
 }
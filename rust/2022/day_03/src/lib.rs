@@ -1,5 +1,7 @@
 use std::collections::BTreeSet;
 
+use aoc_core::{Error, Solution};
+
 fn item_priority(item: u8) -> u64 {
     (match item {
         b'a'..=b'z' => item - b'a' + 1,
@@ -8,43 +10,63 @@ fn item_priority(item: u8) -> u64 {
     }) as u64
 }
 
-pub fn part_one(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|backpack| {
-            let (a, b) = backpack.as_bytes().split_at(backpack.len() / 2);
-            a.iter()
-                .collect::<BTreeSet<_>>()
-                .intersection(&b.iter().collect())
-                .map(|&&item| item_priority(item))
-                .next()
-        })
-        .sum()
+pub struct Backpacks {
+    backpacks: Vec<String>,
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut backpacks_iter = input.lines();
-    let mut sum = 0;
+impl Solution for Backpacks {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            backpacks: input.lines().map(str::to_owned).collect(),
+        })
+    }
 
-    while let (Some(a), Some(b), Some(c)) = (
-        backpacks_iter.next(),
-        backpacks_iter.next(),
-        backpacks_iter.next(),
-    ) {
-        sum += a
-            .bytes()
-            .collect::<BTreeSet<_>>()
-            .intersection(&b.bytes().collect())
-            .cloned()
-            .collect::<BTreeSet<_>>()
-            .intersection(&c.bytes().collect())
-            .cloned()
-            .map(item_priority)
-            .next()
-            .unwrap_or(0);
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        Ok(self
+            .backpacks
+            .iter()
+            .filter_map(|backpack| {
+                let (a, b) = backpack.as_bytes().split_at(backpack.len() / 2);
+                a.iter()
+                    .collect::<BTreeSet<_>>()
+                    .intersection(&b.iter().collect())
+                    .map(|&&item| item_priority(item))
+                    .next()
+            })
+            .sum())
     }
 
-    sum
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        Ok(self
+            .backpacks
+            .chunks_exact(3)
+            .map(|group| {
+                group[0]
+                    .bytes()
+                    .collect::<BTreeSet<_>>()
+                    .intersection(&group[1].bytes().collect())
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+                    .intersection(&group[2].bytes().collect())
+                    .cloned()
+                    .map(item_priority)
+                    .next()
+                    .unwrap_or(0)
+            })
+            .sum())
+    }
+}
+
+pub fn part_one(input: &str) -> u64 {
+    aoc_core::part1::<Backpacks>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    aoc_core::part2::<Backpacks>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -57,8 +79,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 7903);
+        let input = aoc_input::challenge(2022, 3);
+        assert_eq!(part_one(&input), 7903);
     }
 
     #[test]
@@ -67,7 +91,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2548);
+        let input = aoc_input::challenge(2022, 3);
+        assert_eq!(part_two(&input), 2548);
     }
 }
@@ -1,5 +1,4 @@
-#![no_std]
-
+use aoc_core::{Error, Solution};
 use nom::{
     character::complete::{char, digit1},
     combinator::{map, map_res},
@@ -39,20 +38,42 @@ fn parse_elf_pair(input: &str) -> IResult<&str, (SectionAssignment, SectionAssig
     )(input)
 }
 
-fn elf_pairs(input: &str) -> impl Iterator<Item = (SectionAssignment, SectionAssignment)> + '_ {
-    input
-        .lines()
-        .filter_map(|line| parse_elf_pair(line).ok().map(|(_, pair)| pair))
+pub struct ElfPairs {
+    pairs: Vec<(SectionAssignment, SectionAssignment)>,
+}
+
+impl Solution for ElfPairs {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let pairs = input
+            .lines()
+            .map(|line| aoc_core::finish(input, parse_elf_pair(line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { pairs })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .pairs
+            .iter()
+            .filter(|(a, b)| a.fully_contains(b) || b.fully_contains(a))
+            .count())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.pairs.iter().filter(|(a, b)| a.overlaps(b)).count())
+    }
 }
 
 pub fn part_one(input: &str) -> usize {
-    elf_pairs(input)
-        .filter(|(a, b)| a.fully_contains(b) || b.fully_contains(a))
-        .count()
+    aoc_core::part1::<ElfPairs>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    elf_pairs(input).filter(|(a, b)| a.overlaps(b)).count()
+    aoc_core::part2::<ElfPairs>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -65,8 +86,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 584);
+        let input = aoc_input::challenge(2022, 4);
+        assert_eq!(part_one(&input), 584);
     }
 
     #[test]
@@ -75,7 +98,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 933);
+        let input = aoc_input::challenge(2022, 4);
+        assert_eq!(part_two(&input), 933);
     }
 }
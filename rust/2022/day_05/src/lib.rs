@@ -1,3 +1,4 @@
+use aoc_core::{Error, ParseError, Solution};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while},
@@ -75,25 +76,58 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     )(input)
 }
 
-fn crane(input: &str, follow_instruction: impl Fn(&mut Vec<char>)) -> String {
-    let (input, mut stacks) = parse_starting_stacks(input).unwrap();
+pub struct Procedure {
+    stacks: Vec<Vec<char>>,
+    instructions: Vec<Instruction>,
+}
+
+impl Procedure {
+    fn crane(&self, follow_instruction: impl Fn(&mut Vec<char>)) -> String {
+        let mut stacks = self.stacks.clone();
+
+        for instruction in &self.instructions {
+            let from_stack = &mut stacks[instruction.from - 1];
+            let mut lift_stack = from_stack.split_off(from_stack.len() - instruction.num);
+            follow_instruction(&mut lift_stack);
+            stacks[instruction.to - 1].append(&mut lift_stack);
+        }
+        stacks.iter().filter_map(|stack| stack.last()).collect()
+    }
+}
+
+impl Solution for Procedure {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (rest, stacks) =
+            parse_starting_stacks(input).map_err(|e| ParseError::from_nom(input, e))?;
+        let instructions = rest
+            .lines()
+            .map(|line| aoc_core::finish(input, parse_instruction(line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        Ok(self.crane(|lift_stack| lift_stack.reverse()))
+    }
 
-    for instruction_line in input.lines() {
-        let instruction = parse_instruction(instruction_line).unwrap().1;
-        let from_stack = &mut stacks[instruction.from - 1];
-        let mut lift_stack = from_stack.split_off(from_stack.len() - instruction.num);
-        follow_instruction(&mut lift_stack);
-        stacks[instruction.to - 1].append(&mut lift_stack);
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        Ok(self.crane(|_| {}))
     }
-    stacks.iter().filter_map(|stack| stack.last()).collect()
 }
 
 pub fn part_one(input: &str) -> String {
-    crane(input, |lift_stack| lift_stack.reverse())
+    aoc_core::part1::<Procedure>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> String {
-    crane(input, |_| {})
+    aoc_core::part2::<Procedure>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -106,8 +140,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), "QNHWJVJZW");
+        let input = aoc_input::challenge(2022, 5);
+        assert_eq!(part_one(&input), "QNHWJVJZW");
     }
 
     #[test]
@@ -116,7 +152,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), "BPCZJLFJW");
+        let input = aoc_input::challenge(2022, 5);
+        assert_eq!(part_two(&input), "BPCZJLFJW");
     }
 }
@@ -1,25 +1,54 @@
-fn unique_window_index(input: &str, size: usize) -> Option<usize> {
-    let input: Vec<char> = input.chars().collect();
-    let mut index = 0;
-    while let Some(window) = input.get(index..index + size) {
-        if window
-            .iter()
-            .enumerate()
-            .all(|(i, v)| window.iter().skip(i + 1).all(|w| w != v))
-        {
-            return Some(index + size);
+use aoc_core::{Error, Solution};
+
+pub struct Datastream {
+    chars: Vec<char>,
+}
+
+impl Datastream {
+    fn unique_window_index(&self, size: usize) -> Result<usize, Error> {
+        let mut index = 0;
+        while let Some(window) = self.chars.get(index..index + size) {
+            if window
+                .iter()
+                .enumerate()
+                .all(|(i, v)| window.iter().skip(i + 1).all(|w| w != v))
+            {
+                return Ok(index + size);
+            }
+            index += 1;
         }
-        index += 1;
+        Err(Error::no_answer(format!(
+            "no {size} unique characters in a row"
+        )))
+    }
+}
+
+impl Solution for Datastream {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            chars: input.chars().collect(),
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        self.unique_window_index(4)
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        self.unique_window_index(14)
     }
-    None
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    unique_window_index(input, 4)
+    aoc_core::part1::<Datastream>(input).ok()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    unique_window_index(input, 14)
+    aoc_core::part2::<Datastream>(input).ok()
 }
 
 #[cfg(test)]
@@ -44,8 +73,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn part_one_challenge() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), Some(1892));
+        let input = aoc_input::challenge(2022, 6);
+        assert_eq!(part_one(&input), Some(1892));
     }
 
     #[test]
@@ -58,7 +89,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn part_two_challenge() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), Some(2313));
+        let input = aoc_input::challenge(2022, 6);
+        assert_eq!(part_two(&input), Some(2313));
     }
 }
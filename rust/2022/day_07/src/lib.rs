@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
+use aoc_core::{Error, ParseError, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{char, digit1, line_ending, not_line_ending},
-    combinator::{iterator, map, map_res},
-    sequence::{preceded, terminated, tuple},
+    character::complete::{char, digit1, not_line_ending},
+    combinator::{map, map_res},
+    sequence::{preceded, tuple},
     IResult,
 };
 
@@ -17,7 +18,7 @@ enum ConsoleLine<'a> {
     File { size: u64 },
 }
 
-fn parse_command(input: &str) -> IResult<&str, ConsoleLine> {
+fn parse_command(input: &str) -> IResult<&str, ConsoleLine<'_>> {
     alt((
         preceded(tag("$ cd "), map(not_line_ending, ConsoleLine::Cd)),
         map(tag("$ ls"), |_| ConsoleLine::Ls),
@@ -40,22 +41,18 @@ struct FileSystem<'a> {
 }
 
 impl<'a> FileSystem<'a> {
-    fn dir_at_index(&mut self, index: usize) -> Result<&mut FSTreeDirectory<'a>, &'static str> {
-        self.nodes
-            .get_mut(index)
-            .ok_or("Failed to find node in filesystem")
-    }
-
-    fn new_from_observations(input: &'a str) -> Result<Self, &'static str> {
-        let mut command_iter = iterator(input, terminated(parse_command, line_ending));
+    fn new_from_observations(input: &'a str) -> Result<Self, ParseError> {
         let mut filesystem = FileSystem {
             nodes: vec![FSTreeDirectory::default()],
         };
         let mut stack = vec![0];
-        for command in &mut command_iter {
+        for line in input.lines() {
+            let command = aoc_core::finish(input, parse_command(line))?;
             let dir_count = filesystem.nodes.len();
-            let current_index = *stack.last().ok_or("Expected stack of directories")?;
-            let current_directory = filesystem.dir_at_index(current_index)?;
+            let &current_index = stack
+                .last()
+                .ok_or_else(|| ParseError::new(input, line, "a current directory"))?;
+            let current_directory = &mut filesystem.nodes[current_index];
 
             match command {
                 ConsoleLine::Cd("/") => {
@@ -67,10 +64,9 @@ impl<'a> FileSystem<'a> {
                 }
                 ConsoleLine::Cd(name) => {
                     stack.push(
-                        *current_directory
-                            .children
-                            .get(name)
-                            .ok_or("Failed to find directory by name")?,
+                        *current_directory.children.get(name).ok_or_else(|| {
+                            ParseError::new(input, line, "a directory listed by ls")
+                        })?,
                     );
                 }
                 ConsoleLine::Directory(name) => {
@@ -78,9 +74,8 @@ impl<'a> FileSystem<'a> {
                     filesystem.nodes.push(FSTreeDirectory::default());
                 }
                 ConsoleLine::File { size } => {
-                    for index in stack.iter() {
-                        let dir = filesystem.dir_at_index(*index)?;
-                        dir.size += size;
+                    for &index in stack.iter() {
+                        filesystem.nodes[index].size += size;
                     }
                 }
             }
@@ -93,23 +88,49 @@ impl<'a> FileSystem<'a> {
     }
 }
 
-pub fn part_one(input: &str) -> Result<u64, &'static str> {
-    let tree = FileSystem::new_from_observations(input)?;
-    Ok(tree
-        .traverse_dir_sizes()
-        .filter(|&size| size <= 100000)
-        .sum())
+/// The total size of every directory, starting with the root.
+pub struct DirectorySizes {
+    sizes: Vec<u64>,
 }
 
-pub fn part_two(input: &str) -> Result<u64, &'static str> {
-    let tree = FileSystem::new_from_observations(input)?;
-    let root = tree.nodes.first().ok_or("Failed to find root node")?;
-    let space_needed = 30000000 - (70000000 - root.size);
+impl Solution for DirectorySizes {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let tree = FileSystem::new_from_observations(input)?;
+        Ok(Self {
+            sizes: tree.traverse_dir_sizes().collect(),
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        Ok(self.sizes.iter().filter(|&&size| size <= 100000).sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        let root = self
+            .sizes
+            .first()
+            .ok_or_else(|| Error::no_answer("Failed to find root node"))?;
+        let space_needed = 30000000 - (70000000 - root);
+
+        self.sizes
+            .iter()
+            .copied()
+            .filter(|&size| size >= space_needed)
+            .min()
+            .ok_or_else(|| Error::no_answer("Failed to find big enough directory"))
+    }
+}
+
+pub fn part_one(input: &str) -> Result<u64, Error> {
+    aoc_core::part1::<DirectorySizes>(input)
+}
 
-    tree.traverse_dir_sizes()
-        .filter(|&size| size >= space_needed)
-        .min()
-        .ok_or("Failed to find big enough directory")
+pub fn part_two(input: &str) -> Result<u64, Error> {
+    aoc_core::part2::<DirectorySizes>(input)
 }
 
 #[cfg(test)]
@@ -122,8 +143,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(1367870));
+        let input = aoc_input::challenge(2022, 7);
+        assert_eq!(part_one(&input), Ok(1367870));
     }
 
     #[test]
@@ -132,7 +155,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(549173));
+        let input = aoc_input::challenge(2022, 7);
+        assert_eq!(part_two(&input), Ok(549173));
     }
 }
@@ -1,126 +1,74 @@
-#[derive(Debug)]
-struct Forest {
-    width: usize,
-    heights: Vec<u8>,
-}
-
-#[derive(Clone, Copy)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+use aoc_core::{Error, Solution};
+use aoc_grid::{Coord, Direction, Grid};
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::East,
-    Direction::South,
-    Direction::West,
-];
+#[derive(Debug)]
+pub struct Forest(Grid<u8>);
 
-struct TreesIter<'a> {
-    forest: &'a Forest,
-    direction: Direction,
-    index: usize,
+impl Forest {
+    fn line_of_sight(
+        &self,
+        position: Coord,
+        direction: Direction,
+    ) -> impl Iterator<Item = u8> + '_ {
+        self.0
+            .ray(position, direction.offset())
+            .map(|(_, &height)| height)
+    }
 }
 
-impl<'a> Iterator for TreesIter<'a> {
-    type Item = u8;
+impl Solution for Forest {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let i = self.index;
-        let w = self.forest.width;
-        let y = i / w;
-        let x = i % w;
-        self.index = match self.direction {
-            Direction::North => {
-                if y == 0 {
-                    None
-                } else {
-                    Some(i - w)
-                }
-            }
-            Direction::East => {
-                if x + 1 == w {
-                    None
-                } else {
-                    Some(i + 1)
-                }
-            }
-            Direction::South => {
-                if y + 1 == w {
-                    None
-                } else {
-                    Some(i + w)
-                }
-            }
-            Direction::West => {
-                if x == 0 {
-                    None
-                } else {
-                    Some(i - 1)
-                }
-            }
-        }?;
-        Some(self.forest.heights[self.index])
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Forest(Grid::parse(input, |c| {
+            c.to_digit(10).map(|h| h as u8).ok_or("a tree height")
+        })?))
     }
-}
 
-impl Forest {
-    fn new_from_str(input: &str) -> Self {
-        Forest {
-            width: input.lines().count(),
-            heights: input.lines().flat_map(|l| l.bytes()).collect(),
-        }
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|&(position, &h)| {
+                Direction::ALL
+                    .iter()
+                    .any(|&d| self.line_of_sight(position, d).all(|other| h > other))
+            })
+            .count())
     }
 
-    fn line_of_sight(&self, index: usize, direction: Direction) -> impl Iterator<Item = u8> + '_ {
-        TreesIter {
-            forest: self,
-            index,
-            direction,
-        }
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .iter()
+            .map(|(position, &h)| {
+                Direction::ALL
+                    .iter()
+                    .map(|&d| {
+                        let mut count = 0;
+                        for other in self.line_of_sight(position, d) {
+                            count += 1;
+                            if other >= h {
+                                break;
+                            }
+                        }
+                        count
+                    })
+                    .product()
+            })
+            .max()
+            .unwrap_or(0))
     }
 }
 
 pub fn part_one(input: &str) -> usize {
-    let forest = Forest::new_from_str(input);
-    forest
-        .heights
-        .iter()
-        .enumerate()
-        .filter(|&(i, &h)| {
-            DIRECTIONS
-                .iter()
-                .any(|&d| forest.line_of_sight(i, d).all(|other| h > other))
-        })
-        .count()
+    aoc_core::part1::<Forest>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    let forest = Forest::new_from_str(input);
-    forest
-        .heights
-        .iter()
-        .enumerate()
-        .map(|(i, &h)| {
-            DIRECTIONS
-                .iter()
-                .map(|&d| {
-                    let mut count = 0;
-                    for other in forest.line_of_sight(i, d) {
-                        count += 1;
-                        if other >= h {
-                            break;
-                        }
-                    }
-                    count
-                })
-                .product()
-        })
-        .max()
-        .unwrap_or(0)
+    aoc_core::part2::<Forest>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -133,8 +81,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1805);
+        let input = aoc_input::challenge(2022, 8);
+        assert_eq!(part_one(&input), 1805);
     }
 
     #[test]
@@ -143,7 +93,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 444528);
+        let input = aoc_input::challenge(2022, 8);
+        assert_eq!(part_two(&input), 444528);
     }
 }
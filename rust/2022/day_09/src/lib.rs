@@ -1,24 +1,16 @@
-use std::{cmp::Ordering::*, collections::BTreeSet};
+use std::collections::BTreeSet;
 
+use aoc_core::{Error, Solution};
+use aoc_geometry::{Direction, Vec2};
 use nom::{
     branch::alt,
-    character::complete::{char, digit1, line_ending},
-    combinator::{iterator, map, map_res, value},
-    sequence::{terminated, tuple},
+    character::complete::{char, digit1},
+    combinator::{map, map_res, value},
+    sequence::tuple,
     IResult,
 };
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-use Direction::*;
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Motion {
     direction: Direction,
     steps: usize,
@@ -37,10 +29,10 @@ fn parse_motion(input: &str) -> IResult<&str, Motion> {
     map(
         tuple((
             alt((
-                value(Up, char('U')),
-                value(Right, char('R')),
-                value(Down, char('D')),
-                value(Left, char('L')),
+                value(Direction::North, char('U')),
+                value(Direction::East, char('R')),
+                value(Direction::South, char('D')),
+                value(Direction::West, char('L')),
             )),
             char(' '),
             map_res(digit1, str::parse),
@@ -49,69 +41,64 @@ fn parse_motion(input: &str) -> IResult<&str, Motion> {
     )(input)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
-struct Position {
-    x: i16,
-    y: i16,
+type Position = Vec2<i16>;
+
+pub struct Motions {
+    motions: Vec<Motion>,
 }
 
-impl Position {
-    fn move_direction(&mut self, direction: Direction) {
-        match direction {
-            Up => self.y += 1,
-            Right => self.x += 1,
-            Down => self.y -= 1,
-            Left => self.x -= 1,
+impl Motions {
+    fn rope_simulation(&self, rope: &mut [Position]) -> usize {
+        let mut visited = BTreeSet::new();
+        for direction in self.motions.iter().cloned().flatten() {
+            let mut previous_knot = None;
+            for knot in rope.iter_mut() {
+                match previous_knot {
+                    None => *knot += direction.offset(),
+                    Some(&mut previous_knot) => {
+                        if knot.chebyshev(previous_knot) > 1 {
+                            *knot += (previous_knot - *knot).signum();
+                        }
+                    }
+                };
+                previous_knot = Some(knot);
+            }
+            if let Some(last_knot) = previous_knot {
+                visited.insert(*last_knot);
+            }
         }
+        visited.len()
     }
+}
 
-    fn is_touching(&mut self, other: &Self) -> bool {
-        (-1..=1).contains(&(self.x - other.x)) && (-1..=1).contains(&(self.y - other.y))
+impl Solution for Motions {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let motions = input
+            .lines()
+            .map(|line| aoc_core::finish(input, parse_motion(line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { motions })
     }
 
-    fn move_towards(&mut self, other: &Self) {
-        self.x += match self.x.cmp(&other.x) {
-            Less => 1,
-            Equal => 0,
-            Greater => -1,
-        };
-        self.y += match self.y.cmp(&other.y) {
-            Less => 1,
-            Equal => 0,
-            Greater => -1,
-        }
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.rope_simulation(&mut [Position::default(); 2]))
     }
-}
 
-fn rope_simulation(input: &str, rope: &mut [Position]) -> usize {
-    let mut motions_iterator = iterator(input, terminated(parse_motion, line_ending));
-    let mut visited = BTreeSet::new();
-    for direction in &mut motions_iterator.flatten() {
-        let mut previous_knot = None;
-        for knot in rope.iter_mut() {
-            match previous_knot {
-                None => knot.move_direction(direction),
-                Some(previous_knot) => {
-                    if !knot.is_touching(previous_knot) {
-                        knot.move_towards(previous_knot);
-                    }
-                }
-            };
-            previous_knot = Some(knot);
-        }
-        if let Some(last_knot) = previous_knot {
-            visited.insert(*last_knot);
-        }
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.rope_simulation(&mut [Position::default(); 10]))
     }
-    visited.len()
 }
 
 pub fn part_one(input: &str) -> usize {
-    rope_simulation(input, &mut [Position::default(); 2])
+    aoc_core::part1::<Motions>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    rope_simulation(input, &mut [Position::default(); 10])
+    aoc_core::part2::<Motions>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -124,8 +111,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 5735);
+        let input = aoc_input::challenge(2022, 9);
+        assert_eq!(part_one(&input), 5735);
     }
 
     #[test]
@@ -139,7 +128,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2478);
+        let input = aoc_input::challenge(2022, 9);
+        assert_eq!(part_two(&input), 2478);
     }
 }
@@ -1,11 +1,11 @@
 use std::iter::{once, zip};
 
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
-    character::complete::line_ending,
-    combinator::{iterator, map, map_res, value},
-    sequence::{preceded, terminated},
+    combinator::{map, map_res, value},
+    sequence::preceded,
     IResult,
 };
 
@@ -39,7 +39,7 @@ impl Instruction {
                 preceded(
                     tag("addx "),
                     map_res(
-                        take_while1(|c| c == '-' || ('0'..='9').contains(&c)),
+                        take_while1(|c: char| c == '-' || c.is_ascii_digit()),
                         str::parse,
                     ),
                 ),
@@ -57,58 +57,79 @@ impl Instruction {
     }
 }
 
-fn internal_instruction(input: &str) -> Vec<InternalInstruction> {
-    let mut instructions_iterator =
-        iterator(input, terminated(Instruction::nom_parse, line_ending));
-    (&mut instructions_iterator)
-        .flat_map(|i| i.internal_instruction_iter())
-        .collect()
+/// The program, split into one instruction per cycle.
+pub struct Program {
+    internal_instructions: Vec<InternalInstruction>,
 }
 
-fn register_x_values(
-    internal_instruction: &[InternalInstruction],
-) -> impl Iterator<Item = i32> + '_ {
-    let mut register_x = 1;
-    once(register_x).chain(
-        internal_instruction
-            .iter()
-            .map(move |internal_instruction| {
-                internal_instruction.run(&mut register_x);
-                register_x
-            }),
-    )
+impl Program {
+    fn register_x_values(&self) -> impl Iterator<Item = i32> + '_ {
+        let mut register_x = 1;
+        once(register_x).chain(
+            self.internal_instructions
+                .iter()
+                .map(move |internal_instruction| {
+                    internal_instruction.run(&mut register_x);
+                    register_x
+                }),
+        )
+    }
+
+    /// Which of the 40 by 6 pixels the sprite lights up as the beam passes.
+    fn screen(&self) -> Vec<[bool; 40]> {
+        let mut screen = vec![[false; 40]; 6];
+        for (x, cycle) in zip(self.register_x_values(), 0..240) {
+            let i = cycle % 40;
+            screen[cycle as usize / 40][i as usize] = (x - 1..=x + 1).contains(&i);
+        }
+        screen
+    }
 }
 
-pub fn part_one(input: &str) -> i32 {
-    zip(register_x_values(&internal_instruction(input)), 1..)
-        .filter_map(|(register_x, cycle)| match cycle {
-            20 | 60 | 100 | 140 | 180 | 220 => Some(cycle * register_x),
-            _ => None,
+impl Solution for Program {
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let internal_instructions = input
+            .lines()
+            .map(|line| aoc_core::finish(input, Instruction::nom_parse(line)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flat_map(|i| i.internal_instruction_iter())
+            .collect();
+        Ok(Self {
+            internal_instructions,
         })
-        .sum()
+    }
+
+    fn part1(&self, _: &()) -> Result<i32, Error> {
+        Ok(zip(self.register_x_values(), 1..)
+            .filter_map(|(register_x, cycle)| match cycle {
+                20 | 60 | 100 | 140 | 180 | 220 => Some(cycle * register_x),
+                _ => None,
+            })
+            .sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        aoc_ocr::read(&self.screen())
+    }
+}
+
+pub fn part_one(input: &str) -> i32 {
+    aoc_core::part1::<Program>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> String {
-    let mut output = String::new();
-    for (x, cycle) in zip(register_x_values(&internal_instruction(input)), 0..240) {
-        let i = cycle % 40;
-        output.push(if x - 1 == i || x == i || x + 1 == i {
-            '#'
-        } else {
-            '.'
-        });
-        if i == 39 {
-            output.push('\n');
-        }
-    }
-    output
+    aoc_core::part2::<Program>(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use image::ImageBuffer;
 
     #[test]
     fn example_part_one() {
@@ -116,30 +137,30 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 16880);
+        let input = aoc_input::challenge(2022, 10);
+        assert_eq!(part_one(&input), 16880);
     }
 
     #[test]
-    fn example_part_two() {
-        assert_eq!(
-            part_two(include_str!("../example.txt")),
-            include_str!("../example_answer_part_2.txt")
-        );
+    fn example_screen() {
+        let program = Program::parse(include_str!("../example.txt")).unwrap();
+        let drawn: String = program
+            .screen()
+            .iter()
+            .map(|row| {
+                let row: String = row.iter().map(|&p| if p { '#' } else { '.' }).collect();
+                row + "\n"
+            })
+            .collect();
+        assert_eq!(drawn, include_str!("../example_answer_part_2.txt"));
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        let s = part_two(include_str!("../challenge.txt"));
-        let lines: Vec<&[u8]> = s.lines().map(|s| s.as_bytes()).collect();
-        let img = ImageBuffer::from_fn(40, 6, |x, y| {
-            if lines[y as usize][x as usize] == b'#' {
-                image::Luma([0u8])
-            } else {
-                image::Luma([255u8])
-            }
-        });
-        img.save("challenge_answer_part_2.png").unwrap();
-        assert_eq!(&s, include_str!("../challenge_answer_part_2.txt"));
+        let input = aoc_input::challenge(2022, 10);
+        assert_eq!(part_two(&input), "RKAZAJBR");
     }
 }
@@ -1,5 +1,6 @@
 use std::{collections::VecDeque, mem::take};
 
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -25,7 +26,7 @@ enum Operator {
 use Operand::*;
 use Operator::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Monkey {
     items: VecDeque<u64>,
     operation_operator: Operator,
@@ -86,23 +87,13 @@ impl Monkey {
     }
 }
 
-#[derive(Debug)]
-struct Monkeys {
+#[derive(Debug, Clone)]
+pub struct Monkeys {
     troop: Vec<Monkey>,
     common_divisor: u64,
 }
 
 impl Monkeys {
-    fn parse_all(input: &str) -> Self {
-        let troop = separated_list1(tuple((line_ending, line_ending)), Monkey::parse_nom)(input)
-            .unwrap()
-            .1;
-        Self {
-            common_divisor: troop.iter().map(|m| m.test_divisor).product(),
-            troop,
-        }
-    }
-
     fn round(&mut self, divider: u64) {
         for i in 0..self.troop.len() {
             let monkey = &mut self.troop[i];
@@ -136,22 +127,49 @@ impl Monkeys {
     }
 }
 
-fn monkey_business(input: &str, rounds: u16, divisor: u64) -> usize {
-    let mut monkeys = Monkeys::parse_all(input);
-    for _ in 0..rounds {
-        monkeys.round(divisor);
+impl Monkeys {
+    fn monkey_business(&self, rounds: u16, divisor: u64) -> usize {
+        let mut monkeys = self.clone();
+        for _ in 0..rounds {
+            monkeys.round(divisor);
+        }
+        let mut inspections: Vec<_> = monkeys.troop.iter().map(|m| m.inspections).collect();
+        inspections.sort();
+        inspections.iter().rev().take(2).product()
+    }
+}
+
+impl Solution for Monkeys {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let troop = aoc_core::finish(
+            input,
+            separated_list1(tuple((line_ending, line_ending)), Monkey::parse_nom)(input),
+        )?;
+        Ok(Self {
+            common_divisor: troop.iter().map(|m| m.test_divisor).product(),
+            troop,
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.monkey_business(20, 3))
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.monkey_business(10000, 1))
     }
-    let mut inspections: Vec<_> = monkeys.troop.iter().map(|m| m.inspections).collect();
-    inspections.sort();
-    inspections.iter().rev().take(2).product()
 }
 
 pub fn part_one(input: &str) -> usize {
-    monkey_business(input, 20, 3)
+    aoc_core::part1::<Monkeys>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    monkey_business(input, 10000, 1)
+    aoc_core::part2::<Monkeys>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -164,8 +182,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 100345);
+        let input = aoc_input::challenge(2022, 11);
+        assert_eq!(part_one(&input), 100345);
     }
 
     #[test]
@@ -174,7 +194,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 28537348205);
+        let input = aoc_input::challenge(2022, 11);
+        assert_eq!(part_two(&input), 28537348205);
     }
 }
@@ -1,98 +1,80 @@
-use std::{collections::BTreeSet, mem::take};
+use aoc_core::{Error, ParseError, Solution};
+use aoc_grid::{Coord, Grid};
 
 #[derive(Debug)]
-struct HeightMap {
-    heights: Vec<Vec<u8>>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct HeightMap {
+    heights: Grid<u8>,
+    start: Coord,
+    end: Coord,
 }
 
 impl TryFrom<&str> for HeightMap {
-    type Error = &'static str;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let mut start = None;
-        let mut end = None;
-        let heights = s
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.bytes()
-                    .enumerate()
-                    .map(|(x, b)| match b {
-                        b'S' => {
-                            start = Some((x, y));
-                            0
-                        }
-                        b'E' => {
-                            end = Some((x, y));
-                            25
-                        }
-                        _ => b - b'a',
-                    })
-                    .collect()
-            })
-            .collect();
+        let squares = Grid::parse(s, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c),
+            _ => Err("a height from a to z"),
+        })?;
         Ok(Self {
-            start: start.ok_or("Couldn't find Start")?,
-            end: end.ok_or("Couldn't find End")?,
-            heights,
+            start: squares
+                .find(|&c| c == 'S')
+                .ok_or_else(|| ParseError::at_end(s, "a start square S"))?,
+            end: squares
+                .find(|&c| c == 'E')
+                .ok_or_else(|| ParseError::at_end(s, "an end square E"))?,
+            heights: squares.map(|&c| match c {
+                'S' => 0,
+                'E' => 25,
+                _ => c as u8 - b'a',
+            }),
         })
     }
 }
 
+/// Walks down from the end, as climbing back up is never more than one step higher, until
+/// `reached_start` says where it has got to would do.
 fn traverse_backwards(
     height_map: &HeightMap,
-    reached_start: impl Fn(usize, usize, u8) -> bool,
+    reached_start: impl Fn(Coord, u8) -> bool,
 ) -> Result<usize, &'static str> {
-    let mut visited: BTreeSet<(usize, usize)> = [height_map.end].iter().cloned().collect();
-    let mut recently_visited: Vec<(usize, usize)> = visited.iter().cloned().collect();
-    let mut steps = 0;
-    loop {
-        steps += 1;
-        if recently_visited.is_empty() {
-            return Err("No where left to walk");
-        }
-        for &(x, y) in take(&mut recently_visited).iter() {
-            if let Some(&height) = height_map.heights.get(y).and_then(|row| row.get(x)) {
-                for &neighbour in [
-                    (x, y.wrapping_sub(1)),
-                    (x + 1, y),
-                    (x, y + 1),
-                    (x.wrapping_sub(1), y),
-                ]
-                .iter()
-                {
-                    if let Some(&neighbour_height) = height_map
-                        .heights
-                        .get(neighbour.1)
-                        .and_then(|row| row.get(neighbour.0))
-                    {
-                        if neighbour_height + 1 >= height && visited.insert(neighbour) {
-                            recently_visited.push(neighbour);
-                            if reached_start(neighbour.0, neighbour.1, neighbour_height) {
-                                return Ok(steps);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let heights = &height_map.heights;
+    aoc_search::bfs(|&position: &Coord| {
+        let height = heights[position];
+        heights
+            .neighbours_4(position)
+            .filter(move |&neighbour| heights[neighbour] + 1 >= height)
+    })
+    .start(height_map.end)
+    .shortest(|&position| reached_start(position, heights[position]))
+    .map(|shortest| shortest.cost)
+    .ok_or("No where left to walk")
 }
 
-pub fn part_one(input: &str) -> Result<usize, &'static str> {
-    let height_map = HeightMap::try_from(input)?;
+impl Solution for HeightMap {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
 
-    traverse_backwards(&height_map, |x, y, _| {
-        x == height_map.start.0 && y == height_map.start.1
-    })
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(HeightMap::try_from(input)?)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        traverse_backwards(self, |position, _| position == self.start).map_err(Error::no_answer)
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        traverse_backwards(self, |_, height| height == 0).map_err(Error::no_answer)
+    }
 }
 
-pub fn part_two(input: &str) -> Result<usize, &'static str> {
-    let height_map = HeightMap::try_from(input)?;
+pub fn part_one(input: &str) -> Result<usize, Error> {
+    aoc_core::part1::<HeightMap>(input)
+}
 
-    traverse_backwards(&height_map, |_, _, height| height == 0)
+pub fn part_two(input: &str) -> Result<usize, Error> {
+    aoc_core::part2::<HeightMap>(input)
 }
 
 #[cfg(test)]
@@ -105,8 +87,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")).unwrap(), 528);
+        let input = aoc_input::challenge(2022, 12);
+        assert_eq!(part_one(&input).unwrap(), 528);
     }
     #[test]
     fn example_part_two() {
@@ -114,7 +98,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")).unwrap(), 522);
+        let input = aoc_input::challenge(2022, 12);
+        assert_eq!(part_two(&input).unwrap(), 522);
     }
 }
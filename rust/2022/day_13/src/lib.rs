@@ -1,5 +1,6 @@
 use std::iter::zip;
 
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::take_while_m_n,
@@ -36,19 +37,6 @@ impl Packet {
     fn parse(input: &str) -> IResult<&str, Self> {
         alt((Self::parse_integer, Self::parse_list))(input)
     }
-
-    fn parse_all_iterator<'a>(
-        input: &'a str,
-    ) -> ParserIterator<
-        &'a str,
-        nom::error::Error<&'a str>,
-        impl FnMut(&'a str) -> IResult<&'a str, Self>,
-    > {
-        iterator(
-            input,
-            terminated(Self::parse, take_while_m_n(1, 2, |c| c == '\n')),
-        )
-    }
 }
 
 impl PartialOrd for Packet {
@@ -106,29 +94,58 @@ impl PacketPair {
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    zip(1.., &mut PacketPair::parser_iterator(input))
-        .filter_map(|(i, pair)| pair.is_ordered().then_some(i))
-        .sum()
+pub struct DistressSignal {
+    pairs: Vec<PacketPair>,
 }
 
-pub fn part_two(input: &str) -> usize {
-    let divider_a = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
-    let divider_b = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
-
-    let mut packets: Vec<Packet> = Packet::parse_all_iterator(input).collect();
-    packets.push(divider_a.clone());
-    packets.push(divider_b.clone());
-
-    packets.sort_unstable();
-    packets
-        .binary_search(&divider_a)
-        .map(|i| i + 1)
-        .unwrap_or(0)
-        * packets
-            .binary_search(&divider_b)
+impl Solution for DistressSignal {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut pairs_iterator = PacketPair::parser_iterator(input);
+        let pairs = (&mut pairs_iterator).collect();
+        aoc_core::finish(input, pairs_iterator.finish())?;
+        Ok(Self { pairs })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(zip(1.., &self.pairs)
+            .filter_map(|(i, pair)| pair.is_ordered().then_some(i))
+            .sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let divider_a = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
+        let divider_b = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
+
+        let mut packets: Vec<&Packet> = self
+            .pairs
+            .iter()
+            .flat_map(|pair| [&pair.left, &pair.right])
+            .collect();
+        packets.push(&divider_a);
+        packets.push(&divider_b);
+
+        packets.sort_unstable();
+        Ok(packets
+            .binary_search(&&divider_a)
             .map(|i| i + 1)
             .unwrap_or(0)
+            * packets
+                .binary_search(&&divider_b)
+                .map(|i| i + 1)
+                .unwrap_or(0))
+    }
+}
+
+pub fn part_one(input: &str) -> usize {
+    aoc_core::part1::<DistressSignal>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> usize {
+    aoc_core::part2::<DistressSignal>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -141,8 +158,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 5882);
+        let input = aoc_input::challenge(2022, 13);
+        assert_eq!(part_one(&input), 5882);
     }
 
     #[test]
@@ -151,7 +170,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 24948);
+        let input = aoc_input::challenge(2022, 13);
+        assert_eq!(part_two(&input), 24948);
     }
 }
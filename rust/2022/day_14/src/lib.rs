@@ -1,3 +1,6 @@
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
+use aoc_render::{Animate, Backend, Cell, Colour, Raster, Render};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, newline},
@@ -6,77 +9,30 @@ use nom::{
     sequence::separated_pair,
     IResult,
 };
-use std::{cmp::Ordering::*, collections::BTreeSet};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Coordinate {
-    x: u16,
-    y: u16,
+use std::collections::BTreeSet;
+
+type Coordinate = Vec2<u16>;
+
+fn parse_coordinate(input: &str) -> IResult<&str, Coordinate> {
+    map(
+        separated_pair(
+            map_res(digit1, str::parse),
+            char(','),
+            map_res(digit1, str::parse),
+        ),
+        |(x, y)| Coordinate { x, y },
+    )(input)
 }
 
-impl Coordinate {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            separated_pair(
-                map_res(digit1, str::parse),
-                char(','),
-                map_res(digit1, str::parse),
-            ),
-            |(x, y)| Self { x, y },
-        )(input)
-    }
-
-    fn iter_straight_line_to_other(self, other: Self) -> impl Iterator<Item = Self> {
-        (0..).map_while(
-            move |i| match (self.x.cmp(&other.x), self.y.cmp(&other.y)) {
-                (Less, Less) => None,
-                (Less, Equal) => {
-                    if self.x + i <= other.x {
-                        Some(Self {
-                            x: self.x + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Less, Greater) => None,
-                (Equal, Less) => {
-                    if self.y + i <= other.y {
-                        Some(Self {
-                            y: self.y + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Equal, Equal) => (i == 0).then_some(self),
-                (Equal, Greater) => {
-                    if other.y + i <= self.y {
-                        Some(Self {
-                            y: other.y + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Greater, Less) => None,
-                (Greater, Equal) => {
-                    if other.x + i <= self.x {
-                        Some(Self {
-                            x: other.x + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Greater, Greater) => None,
-            },
-        )
-    }
+/// Every coordinate from `from` to `to` inclusive, or none if they aren't in line.
+fn straight_line(from: Coordinate, to: Coordinate) -> impl Iterator<Item = Coordinate> {
+    let (start, end) = (from.min(to), from.max(to));
+    (start.x == end.x || start.y == end.y)
+        .then(move || {
+            (start.y..=end.y).flat_map(move |y| (start.x..=end.x).map(move |x| Coordinate { x, y }))
+        })
+        .into_iter()
+        .flatten()
 }
 
 const SAND_SOURCE: Coordinate = Coordinate { x: 500, y: 0 };
@@ -89,7 +45,7 @@ struct RockStructure {
 impl RockStructure {
     fn parse(input: &str) -> IResult<&str, Self> {
         map(
-            separated_list1(tag(" -> "), Coordinate::parse),
+            separated_list1(tag(" -> "), parse_coordinate),
             |coordinates| Self { coordinates },
         )(input)
     }
@@ -100,14 +56,14 @@ impl RockStructure {
             .scan(None, |state, &coord| {
                 let previous_state = state.unwrap_or(coord);
                 *state = Some(coord);
-                Some(coord.iter_straight_line_to_other(previous_state))
+                Some(straight_line(coord, previous_state))
             })
             .flatten()
     }
 }
 
-#[derive(Debug)]
-struct Cave {
+#[derive(Debug, Clone)]
+pub struct Cave {
     rocks: BTreeSet<Coordinate>,
     sand: BTreeSet<Coordinate>,
     max_y: u16,
@@ -126,7 +82,7 @@ impl Cave {
         }
     }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
+    fn parse_nom(input: &str) -> IResult<&str, Self> {
         let (rest, rock_structures) = separated_list1(newline, RockStructure::parse)(input)?;
         let rocks = rock_structures
             .iter()
@@ -161,73 +117,82 @@ impl Cave {
         None
     }
 
-    fn draw(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
-        for y in 0..=self.max_y {
-            for x in self.min_x..=self.max_x {
-                let c = Coordinate { x, y };
-                write!(
-                    w,
-                    "{}",
-                    if self.rocks.contains(&c) {
-                        '#'
-                    } else if self.sand.contains(&c) {
-                        'o'
-                    } else if c == SAND_SOURCE {
-                        'p'
-                    } else {
-                        ' '
-                    }
-                )?;
+    /// Drops a grain of sand from the source until it comes to rest. Returns false instead if
+    /// it would fall past the lowest rock, when there's no floor to catch it.
+    fn drop_sand(&mut self, floor: bool) -> bool {
+        let mut c = SAND_SOURCE;
+        while let Some(n) = self.next_sand_position(c) {
+            if !floor && n.y > self.max_y {
+                return false;
             }
-            writeln!(w)?;
+            c = n;
         }
-        Ok(())
+        self.sand.insert(c)
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut cave = Cave::parse(input).unwrap().1;
-    let mut counter = 0;
-    loop {
-        let mut c = SAND_SOURCE;
-        loop {
-            match cave.next_sand_position(c) {
-                Some(n) => c = n,
-                None => {
-                    cave.sand.insert(c);
-                    counter += 1;
-                    break;
-                }
-            }
-            if c.y > cave.max_y {
-                return counter;
+impl Render for Cave {
+    fn render(&self) -> Raster {
+        let width = usize::from(self.max_x - self.min_x) + 1;
+        Raster::from_fn(width, usize::from(self.max_y) + 1, |p| {
+            let c = Coordinate::new(self.min_x + p.x as u16, p.y as u16);
+            if self.rocks.contains(&c) {
+                Cell::new('#', Colour::new(128, 128, 128))
+            } else if self.sand.contains(&c) {
+                Cell::new('o', Colour::new(194, 178, 128))
+            } else if c == SAND_SOURCE {
+                Cell::new('+', Colour::WHITE)
+            } else {
+                Cell::new('.', Colour::BLACK)
             }
+        })
+    }
+}
+
+/// The sand of part one, a frame per grain.
+impl Animate for Cave {
+    fn animate(&self, backend: &mut dyn Backend) -> std::io::Result<()> {
+        let mut cave = self.clone();
+        backend.frame(&cave.render())?;
+        while cave.drop_sand(false) {
+            backend.frame(&cave.render())?;
         }
+        Ok(())
     }
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut cave = Cave::parse(input).unwrap().1;
-    let mut counter = 0;
-    loop {
-        let mut c = SAND_SOURCE;
-        loop {
-            if cave.sand.contains(&SAND_SOURCE) {
-                cave.draw(std::io::stdout()).unwrap();
-                return counter;
-            }
-            match cave.next_sand_position(c) {
-                Some(n) => c = n,
-                None => {
-                    cave.sand.insert(c);
-                    counter += 1;
-                    break;
-                }
-            }
+impl Solution for Cave {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Cave::parse_nom(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let mut cave = self.clone();
+        while cave.drop_sand(false) {}
+        Ok(cave.sand.len())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let mut cave = self.clone();
+        while !cave.sand.contains(&SAND_SOURCE) {
+            cave.drop_sand(true);
         }
+        Ok(cave.sand.len())
     }
 }
 
+pub fn part_one(input: &str) -> usize {
+    aoc_core::part1::<Cave>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> usize {
+    aoc_core::part2::<Cave>(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,8 +203,24 @@ mod tests {
     }
 
     #[test]
+    fn example_animation() {
+        let mut frames = Vec::new();
+        Cave::parse(include_str!("../example.txt"))
+            .unwrap()
+            .animate(&mut frames)
+            .unwrap();
+        assert_eq!(frames.len(), 25);
+        assert_eq!(
+            frames[24].to_string(),
+            include_str!("../example_part_one_sand.txt")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 737);
+        let input = aoc_input::challenge(2022, 14);
+        assert_eq!(part_one(&input), 737);
     }
 
     #[test]
@@ -248,7 +229,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 28145);
+        let input = aoc_input::challenge(2022, 14);
+        assert_eq!(part_two(&input), 28145);
     }
 }
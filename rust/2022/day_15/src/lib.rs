@@ -1,3 +1,6 @@
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
+use aoc_interval::IntervalSet;
 use nom::{
     bytes::{complete::tag, streaming::take_while1},
     character::complete::newline,
@@ -5,39 +8,21 @@ use nom::{
     sequence::{terminated, tuple},
     IResult,
 };
-use rayon::prelude::*;
 use std::{collections::BTreeSet, ops::RangeInclusive};
 
-#[derive(Debug, Clone, Copy)]
-struct Location {
-    x: i64,
-    y: i64,
-}
+type Location = Vec2<i64>;
 
-impl Location {
-    fn parse_nom(input: &str) -> IResult<&str, Self> {
-        fn parse_num(input: &str) -> IResult<&str, i64> {
-            map_res(
-                take_while1(|c: char| c == '-' || ('0'..='9').contains(&c)),
-                str::parse,
-            )(input)
-        }
-        map(
-            tuple((tag("x="), &parse_num, tag(", y="), &parse_num)),
-            |(_, x, _, y)| Self { x, y },
+fn parse_location(input: &str) -> IResult<&str, Location> {
+    fn parse_num(input: &str) -> IResult<&str, i64> {
+        map_res(
+            take_while1(|c: char| c == '-' || c.is_ascii_digit()),
+            str::parse,
         )(input)
     }
-
-    fn manhatten_distance(&self, other: &Self) -> u64 {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-    }
-
-    fn manhatten_points_at_row(&self, distance: u64, row: i64) -> RangeInclusive<i64> {
-        let vertical_distance = self.y.abs_diff(row);
-        let horizontal_distance: i64 = TryInto::<i64>::try_into(distance).unwrap()
-            - TryInto::<i64>::try_into(vertical_distance).unwrap(); // can be negative, which would give a empty range
-        (self.x - horizontal_distance)..=(self.x + horizontal_distance)
-    }
+    map(
+        tuple((tag("x="), &parse_num, tag(", y="), &parse_num)),
+        |(_, x, _, y)| Location { x, y },
+    )(input)
 }
 
 #[derive(Debug)]
@@ -51,9 +36,9 @@ impl Sensor {
         map(
             tuple((
                 tag("Sensor at "),
-                Location::parse_nom,
+                parse_location,
                 tag(": closest beacon is at "),
-                Location::parse_nom,
+                parse_location,
             )),
             |(_, location, _, beacon)| Self { location, beacon },
         )(input)
@@ -69,68 +54,130 @@ impl Sensor {
         iterator(input, terminated(Self::parse_nom, newline))
     }
 
-    fn beacon_distance(&self) -> u64 {
-        self.location.manhatten_distance(&self.beacon)
+    /// The positions in `row` at least as close to the sensor as its beacon. The range is
+    /// empty when the row is out of reach.
+    fn covered_at_row(&self, row: i64) -> RangeInclusive<i64> {
+        let distance = self.location.manhattan(self.beacon);
+        let horizontal_distance = distance - self.location.y.abs_diff(row) as i64;
+        (self.location.x - horizontal_distance)..=(self.location.x + horizontal_distance)
     }
+
+    /// The positions the sensor covers, turned 45° so they make a square: the ranges of
+    /// `x + y` and of `x - y`.
+    fn covered_rotated(&self) -> [RangeInclusive<i64>; 2] {
+        let distance = self.location.manhattan(self.beacon);
+        let Location { x, y } = self.location;
+        [
+            (x + y - distance)..=(x + y + distance),
+            (x - y - distance)..=(x - y + distance),
+        ]
+    }
+}
+
+pub struct Sensors {
+    sensors: Vec<Sensor>,
 }
 
-fn normalize_ranges(mut ranges: Vec<RangeInclusive<i64>>) -> Vec<RangeInclusive<i64>> {
-    ranges.sort_by_key(|r| *r.start());
-    let mut normalized: Vec<RangeInclusive<i64>> = Vec::new();
-    for new_range in ranges.drain(..) {
-        if new_range.is_empty() {
-            continue;
+/// Where to look; the examples use a smaller area than the real puzzle.
+pub struct Params {
+    /// The row to count covered positions in for part one.
+    pub row: i64,
+    /// The largest coordinate the distress beacon can be at for part two.
+    pub search_limit: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: 2000000,
+            search_limit: 4000000,
         }
-        if let Some(normalized_range) = normalized.last_mut() {
-            if new_range.start() <= normalized_range.end() {
-                *normalized_range =
-                    *normalized_range.start()..=i64::max(*normalized_range.end(), *new_range.end())
-            } else {
-                normalized.push(new_range);
+    }
+}
+
+impl Solution for Sensors {
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut sensors_iterator = Sensor::parse_all_iterator(input);
+        let sensors = (&mut sensors_iterator).collect();
+        aoc_core::finish(input, sensors_iterator.finish())?;
+        Ok(Self { sensors })
+    }
+
+    fn part1(&self, params: &Params) -> Result<usize, Error> {
+        let row = params.row;
+        let mut covered = IntervalSet::new();
+        let mut beacons_in_row = BTreeSet::new();
+        for sensor in &self.sensors {
+            covered.insert(sensor.covered_at_row(row));
+            if sensor.beacon.y == row {
+                beacons_in_row.insert(sensor.beacon.x);
             }
-        } else {
-            normalized.push(new_range);
         }
+
+        Ok(covered.len() as usize - beacons_in_row.len())
     }
-    normalized
-}
 
-pub fn part_one(input: &str, row: i64) -> usize {
-    let mut covered_ranges = Vec::new();
-    let mut beacons_in_row = BTreeSet::new();
-    for sensor in &mut Sensor::parse_all_iterator(input) {
-        covered_ranges.push(
-            sensor
-                .location
-                .manhatten_points_at_row(sensor.beacon_distance(), row),
-        );
-        if sensor.beacon.y == row {
-            beacons_in_row.insert(sensor.beacon.x);
+    /// Turned 45°, every sensor covers a square. The one uncovered position has covered
+    /// neighbours, so it's on a line just past the edge of some square, along one of the two
+    /// diagonal axes. Along each such line, the covered squares crossing it leave a gap.
+    fn part2(&self, params: &Params) -> Result<i64, Error> {
+        let limit = params.search_limit;
+        let squares: Vec<_> = self.sensors.iter().map(Sensor::covered_rotated).collect();
+        for axis in 0..2 {
+            let lines: BTreeSet<i64> = squares
+                .iter()
+                .flat_map(|square| [square[axis].start() - 1, square[axis].end() + 1])
+                .collect();
+            for line in lines {
+                let covered: IntervalSet<i64> = squares
+                    .iter()
+                    .filter(|square| square[axis].contains(&line))
+                    .map(|square| square[1 - axis].clone())
+                    .collect();
+                // where the line crosses the search area, which is a diamond once turned
+                let across = if axis == 0 {
+                    (-line).max(line - 2 * limit)..=line.min(2 * limit - line)
+                } else {
+                    (-line).max(line)..=(2 * limit - line).min(2 * limit + line)
+                };
+                for gap in covered.gaps(across) {
+                    // x + y and x - y are both odd or both even
+                    let first = gap.start + (gap.start - line).rem_euclid(2);
+                    if first < gap.end {
+                        let (sum, difference) = if axis == 0 {
+                            (line, first)
+                        } else {
+                            (first, line)
+                        };
+                        let (x, y) = ((sum + difference) / 2, (sum - difference) / 2);
+                        return Ok(x * 4000000 + y);
+                    }
+                }
+            }
         }
+        Err(Error::no_answer("every position is covered by a sensor"))
     }
-    let covered_ranges = normalize_ranges(covered_ranges);
+}
 
-    covered_ranges
-        .iter()
-        .map(|r| r.clone().count())
-        .sum::<usize>()
-        - beacons_in_row.len()
+pub fn part_one(input: &str, row: i64) -> usize {
+    let params = Params {
+        row,
+        ..Params::default()
+    };
+    Sensors::parse(input).unwrap().part1(&params).unwrap()
 }
 
 pub fn part_two(input: &str, search_limit: i64) -> i64 {
-    let sensors: Vec<Sensor> = Sensor::parse_all_iterator(input).collect();
-    (0..=search_limit)
-        .into_par_iter()
-        .find_map_first(|row| {
-            let covered_ranges = sensors
-                .iter()
-                .map(|s| s.location.manhatten_points_at_row(s.beacon_distance(), row))
-                .collect();
-            let covered_ranges = normalize_ranges(covered_ranges);
-            covered_ranges.iter().find_map(|r| {
-                ((-1..search_limit).contains(r.end())).then(|| (*r.end() + 1) * 4000000 + row)
-            })
-        })
+    let params = Params {
+        search_limit,
+        ..Params::default()
+    };
+    Sensors::parse(input)
+        .and_then(|sensors| sensors.part2(&params))
         .unwrap_or(0)
 }
 
@@ -144,8 +191,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt"), 2000000), 4793062);
+        let input = aoc_input::challenge(2022, 15);
+        assert_eq!(part_one(&input, 2000000), 4793062);
     }
 
     #[test]
@@ -154,10 +203,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(
-            part_two(include_str!("../challenge.txt"), 4000000),
-            10826395253551
-        );
+        let input = aoc_input::challenge(2022, 15);
+        assert_eq!(part_two(&input, 4000000), 10826395253551);
     }
 }
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_input::check_challenge_input(2022, 16);
+}
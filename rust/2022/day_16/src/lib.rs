@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while},
@@ -9,9 +10,9 @@ use nom::{
 };
 
 use std::{
-    collections::{hash_map, HashMap, HashSet},
+    cmp::Reverse,
+    collections::{hash_map, HashMap, VecDeque},
     iter::once,
-    mem::take,
 };
 
 const START_VALVE: &str = "AA";
@@ -25,7 +26,7 @@ struct ValveData<'a> {
 
 impl<'a> ValveData<'a> {
     fn parse_nom(input: &'a str) -> IResult<&'a str, ValveData<'a>> {
-        let is_valve_name_char: fn(char) -> bool = |c| ('A'..='Z').contains(&c);
+        let is_valve_name_char: fn(char) -> bool = |c: char| c.is_ascii_uppercase();
 
         map(
             tuple((
@@ -48,187 +49,242 @@ impl<'a> ValveData<'a> {
     }
 }
 
+/// The tunnels, cut down to the valves worth opening and how far apart they are.
 #[derive(Debug)]
-struct Valve<'a> {
-    flow_rate: u16,
-    neighbour_distance: HashMap<&'a str, u16>,
+pub struct World {
+    /// The valves worth opening, in order of name.
+    names: Vec<String>,
+    flow_rates: Vec<u32>,
+    /// Minutes from each valve worth opening to each other, then from the start,
+    /// `u16::MAX` if there's no way through.
+    distances: Vec<Vec<u16>>,
 }
 
-#[derive(Debug)]
-struct World<'a> {
-    valves: HashMap<&'a str, Valve<'a>>,
+/// A valve opened, and the minute it was opened in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u16,
 }
 
-impl<'a> World<'a> {
-    fn prepare(input: &'a str) -> Self {
-        let valve_datas = separated_list1(newline, ValveData::parse_nom)(input)
-            .unwrap()
-            .1;
-        let mut valves = HashMap::new();
+/// The most pressure that can be released, and which valves each agent opens to do it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub pressure: u32,
+    pub schedules: Vec<Vec<Opening>>,
+}
 
-        for vd in valve_datas
+impl World {
+    fn prepare(input: &str) -> Result<Self, Error> {
+        let valve_datas =
+            aoc_core::finish(input, separated_list1(newline, ValveData::parse_nom)(input))?;
+        let mut useful: Vec<&ValveData> =
+            valve_datas.iter().filter(|vd| vd.flow_rate > 0).collect();
+        useful.sort_unstable_by_key(|vd| vd.name);
+        if useful.len() > 64 {
+            return Err(Error::no_answer("more than 64 valves are worth opening"));
+        }
+        if !valve_datas.iter().any(|vd| vd.name == START_VALVE) {
+            return Err(Error::no_answer(format!("there's no valve {START_VALVE}")));
+        }
+        let neighbours: HashMap<&str, &[&str]> = valve_datas
             .iter()
-            .filter(|&vd| vd.name == START_VALVE || vd.flow_rate > 0)
-        {
-            let mut neighbour_distance = HashMap::new();
-            let mut visited = HashSet::new();
-            visited.insert(vd.name);
-            let mut recently_visited = vec![vd.name];
-            for distance in 1.. {
-                if recently_visited.is_empty() {
-                    break;
-                }
-                for &v in take(&mut recently_visited).iter() {
-                    if let Some(vd) = valve_datas.iter().find(|vd| vd.name == v) {
-                        for &neighbour in vd.neighbours.iter() {
-                            if visited.insert(neighbour) {
-                                recently_visited.push(neighbour);
-                                if valve_datas
-                                    .iter()
-                                    .any(|vd| vd.name == neighbour && vd.flow_rate > 0)
-                                {
-                                    neighbour_distance.insert(neighbour, distance);
-                                }
-                            }
+            .map(|vd| (vd.name, vd.neighbours.as_slice()))
+            .collect();
+
+        let distances = useful
+            .iter()
+            .map(|vd| vd.name)
+            .chain(once(START_VALVE))
+            .map(|from| {
+                let mut distance = HashMap::from([(from, 0)]);
+                let mut queue = VecDeque::from([from]);
+                while let Some(valve) = queue.pop_front() {
+                    let next = distance[valve] + 1;
+                    for &neighbour in neighbours.get(valve).copied().unwrap_or_default() {
+                        if let hash_map::Entry::Vacant(entry) = distance.entry(neighbour) {
+                            entry.insert(next);
+                            queue.push_back(neighbour);
                         }
                     }
                 }
-            }
-            valves.insert(
-                vd.name,
-                Valve {
-                    flow_rate: vd.flow_rate,
-                    neighbour_distance,
-                },
-            );
-        }
+                useful
+                    .iter()
+                    .map(|to| distance.get(to.name).copied().unwrap_or(u16::MAX))
+                    .collect()
+            })
+            .collect();
 
-        World { valves }
+        Ok(World {
+            names: useful.iter().map(|vd| vd.name.to_owned()).collect(),
+            flow_rates: useful.iter().map(|vd| vd.flow_rate.into()).collect(),
+            distances,
+        })
     }
-}
 
-#[derive(Debug)]
-struct StackItem<'a> {
-    name: &'a str,
-    neighbour_iter: hash_map::Iter<'a, &'a str, u16>,
-    acc_flow: u16,
-    time_remaining: u16,
-}
-fn best_one_person(world: &World, time_remaining: u16, valves_to_avoid: &[&str]) -> u16 {
-    let mut best = 0;
-    let mut stack = vec![StackItem {
-        name: START_VALVE,
-        neighbour_iter: world
-            .valves
-            .get(START_VALVE)
-            .unwrap()
-            .neighbour_distance
-            .iter(),
-        acc_flow: 0,
-        time_remaining,
-    }];
-
-    while let Some(mut bottom) = stack.pop() {
-        let time_remaining = bottom.time_remaining;
-        let acc_flow = bottom.acc_flow;
-
-        if let Some(new_stack_item) = bottom
-            .neighbour_iter
-            .by_ref()
-            .filter_map(|(&neighbour_name, &neighbour_distance)| {
-                if stack.iter().any(|s| s.name == neighbour_name)
-                    || valves_to_avoid.binary_search(&neighbour_name).is_ok()
-                {
-                    return None;
-                }
-                let time_remaining = time_remaining.checked_sub(neighbour_distance + 1)?;
-                let valve = world.valves.get(neighbour_name)?;
-                let acc_flow = acc_flow + time_remaining * valve.flow_rate;
-                Some(StackItem {
-                    name: neighbour_name,
-                    neighbour_iter: valve.neighbour_distance.iter(),
-                    acc_flow,
-                    time_remaining,
-                })
+    /// The best way for agents with these many minutes each, all starting at `AA`, to
+    /// release pressure between them.
+    pub fn plan(&self, budgets: &[u16]) -> Plan {
+        let mut search = Search {
+            world: self,
+            best: 0,
+            best_schedules: vec![Vec::new(); budgets.len()],
+            schedules: vec![Vec::new(); budgets.len()],
+            seen: HashMap::new(),
+        };
+        let mut agents = budgets
+            .iter()
+            .map(|&time| Agent {
+                time,
+                at: self.names.len(),
             })
-            .next()
-        {
-            stack.push(bottom);
-            best = u16::max(best, new_stack_item.acc_flow);
-            stack.push(new_stack_item);
+            .collect::<Vec<_>>();
+        search.search(&mut agents, 0, 0);
+        Plan {
+            pressure: search.best,
+            schedules: search
+                .best_schedules
+                .iter()
+                .zip(budgets)
+                .map(|(schedule, budget)| {
+                    schedule
+                        .iter()
+                        .map(|&(valve, time)| Opening {
+                            valve: self.names[valve].clone(),
+                            minute: budget - time,
+                        })
+                        .collect()
+                })
+                .collect(),
         }
     }
+}
 
-    best
+/// Where an agent is and how many minutes it has left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Agent {
+    time: u16,
+    at: usize,
 }
 
-pub fn part_one(input: &str) -> u16 {
-    let world = World::prepare(input);
-    best_one_person(&world, 30, &[])
+/// A depth first search over which valve each agent opens next, always moving whichever
+/// agent has the most time left.
+struct Search<'a> {
+    world: &'a World,
+    best: u32,
+    best_schedules: Vec<Vec<(usize, u16)>>,
+    /// The valves each agent has opened so far, and the minutes left after opening them.
+    schedules: Vec<Vec<(usize, u16)>>,
+    /// The most pressure found so far for each set of valves opened and agents' positions.
+    seen: HashMap<(u64, Vec<Agent>), u32>,
 }
 
-pub fn part_two(input: &str) -> u16 {
-    let world = World::prepare(input);
-
-    let mut best = 0;
-    let mut stack = vec![StackItem {
-        name: START_VALVE,
-        neighbour_iter: world
-            .valves
-            .get(START_VALVE)
-            .unwrap()
-            .neighbour_distance
-            .iter(),
-        acc_flow: 0,
-        time_remaining: 26,
-    }];
-
-    // an elephant always remembers
-    let mut elephant_memoization = HashMap::<Vec<&str>, u16>::new();
-
-    while let Some(mut bottom) = stack.pop() {
-        let time_remaining = bottom.time_remaining;
-        let acc_flow = bottom.acc_flow;
-
-        if let Some(new_stack_item) = bottom
-            .neighbour_iter
-            .by_ref()
-            .filter_map(|(&neighbour_name, &neighbour_distance)| {
-                if stack.iter().any(|s| s.name == neighbour_name) {
-                    return None;
-                }
-                let time_remaining = time_remaining.checked_sub(neighbour_distance + 1)?;
-                let valve = world.valves.get(neighbour_name)?;
-                let acc_flow = acc_flow + time_remaining * valve.flow_rate;
-                Some(StackItem {
-                    name: neighbour_name,
-                    neighbour_iter: valve.neighbour_distance.iter(),
-                    acc_flow,
-                    time_remaining,
+impl Search<'_> {
+    /// The minutes `agent` would have left after going to and opening `valve`.
+    fn time_after(&self, agent: Agent, valve: usize) -> Option<u16> {
+        agent
+            .time
+            .checked_sub(self.world.distances[agent.at][valve].checked_add(1)?)
+            .filter(|&time| time > 0)
+    }
+
+    /// What could be released at best, if every closed valve were opened by whichever
+    /// agent could get there first.
+    fn upper_bound(&self, agents: &[Agent], opened: u64, pressure: u32) -> u32 {
+        pressure
+            + (0..self.world.names.len())
+                .filter(|v| opened & 1 << v == 0)
+                .map(|v| {
+                    let time = agents
+                        .iter()
+                        .filter_map(|&a| self.time_after(a, v))
+                        .max()
+                        .unwrap_or(0);
+                    self.world.flow_rates[v] * u32::from(time)
                 })
-            })
-            .next()
-        {
-            stack.push(bottom);
-            let mut visited = once(&new_stack_item)
-                .chain(stack.iter())
-                .map(|s| s.name)
-                .collect::<Vec<_>>();
-            visited.sort_unstable();
-            let elephant = *elephant_memoization
-                .entry(visited.clone())
-                .or_insert_with(|| best_one_person(&world, 26, &visited));
-            best = u16::max(best, new_stack_item.acc_flow + elephant);
-            stack.push(new_stack_item);
+                .sum::<u32>()
+    }
+
+    fn search(&mut self, agents: &mut [Agent], opened: u64, pressure: u32) {
+        if pressure > self.best {
+            self.best = pressure;
+            self.best_schedules.clone_from(&self.schedules);
+        }
+        if self.upper_bound(agents, opened, pressure) <= self.best {
+            return;
         }
+        // agents are alike apart from where they are and how long they have
+        let mut key = agents.to_vec();
+        key.sort_unstable();
+        match self.seen.entry((opened, key)) {
+            hash_map::Entry::Occupied(seen) if *seen.get() >= pressure => return,
+            hash_map::Entry::Occupied(mut seen) => *seen.get_mut() = pressure,
+            hash_map::Entry::Vacant(seen) => {
+                seen.insert(pressure);
+            }
+        }
+        let Some((i, &agent)) = agents
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.time > 0)
+            .max_by_key(|&(i, a)| (a.time, Reverse(i)))
+        else {
+            return;
+        };
+
+        let mut moves: Vec<(usize, u16)> = (0..self.world.names.len())
+            .filter(|v| opened & 1 << v == 0)
+            .filter_map(|v| Some((v, self.time_after(agent, v)?)))
+            .collect();
+        moves
+            .sort_unstable_by_key(|&(v, time)| Reverse(self.world.flow_rates[v] * u32::from(time)));
+        for (valve, time) in moves {
+            agents[i] = Agent { time, at: valve };
+            self.schedules[i].push((valve, time));
+            self.search(
+                agents,
+                opened | 1 << valve,
+                pressure + self.world.flow_rates[valve] * u32::from(time),
+            );
+            self.schedules[i].pop();
+        }
+        // or leave the rest of the valves to the others
+        agents[i] = Agent { time: 0, ..agent };
+        self.search(agents, opened, pressure);
+        agents[i] = agent;
+    }
+}
+
+impl Solution for World {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        World::prepare(input)
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.plan(&[30]).pressure)
+    }
+
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.plan(&[26, 26]).pressure)
     }
+}
+
+pub fn part_one(input: &str) -> u32 {
+    aoc_core::part1::<World>(input).unwrap()
+}
 
-    best
+pub fn part_two(input: &str) -> u32 {
+    aoc_core::part2::<World>(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn example_part_one() {
@@ -236,8 +292,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1789);
+        let input = aoc_input::challenge(2022, 16);
+        assert_eq!(part_one(&input), 1789);
     }
 
     #[test]
@@ -246,7 +304,70 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2496);
+        let input = aoc_input::challenge(2022, 16);
+        assert_eq!(part_two(&input), 2496);
+    }
+
+    #[test]
+    fn example_schedule() {
+        let world = World::parse(include_str!("../example.txt")).unwrap();
+        let plan = world.plan(&[30]);
+        let opened: Vec<_> = plan.schedules[0]
+            .iter()
+            .map(|o| (o.valve.as_str(), o.minute))
+            .collect();
+        assert_eq!(
+            opened,
+            [
+                ("DD", 2),
+                ("BB", 5),
+                ("JJ", 9),
+                ("HH", 17),
+                ("EE", 21),
+                ("CC", 24)
+            ]
+        );
+    }
+
+    #[test]
+    fn agents_with_their_own_time() {
+        let world = World::parse(include_str!("../example.txt")).unwrap();
+        let plan = world.plan(&[30, 0]);
+        assert_eq!(plan.pressure, 1651);
+        assert!(plan.schedules[1].is_empty());
+        let plan = world.plan(&[26, 26, 26]);
+        assert_eq!(plan.pressure, 1794);
+        let schedules: Vec<Vec<_>> = plan
+            .schedules
+            .iter()
+            .map(|s| s.iter().map(|o| (o.valve.as_str(), o.minute)).collect())
+            .collect();
+        assert_eq!(
+            schedules,
+            [
+                vec![("JJ", 3)],
+                vec![("DD", 2), ("HH", 7)],
+                vec![("BB", 2), ("CC", 4), ("EE", 7)],
+            ]
+        );
+        let opened: Vec<&str> = plan
+            .schedules
+            .iter()
+            .flatten()
+            .map(|o| o.valve.as_str())
+            .collect();
+        assert_eq!(opened.iter().collect::<HashSet<_>>().len(), opened.len());
+        let released: u32 = plan
+            .schedules
+            .iter()
+            .flatten()
+            .map(|o| {
+                let valve = world.names.iter().position(|n| *n == o.valve).unwrap();
+                world.flow_rates[valve] * u32::from(26 - o.minute)
+            })
+            .sum();
+        assert_eq!(released, plan.pressure);
     }
 }
@@ -1,11 +1,12 @@
-use std::collections::{hash_map, HashMap};
+use aoc_core::{Error, Solution};
+use aoc_cycle::simulate_until;
+use aoc_geometry::Vec2;
+use aoc_render::{Animate, Backend, Cell, Colour, Raster, Render};
 
-#[derive(Clone, Copy)]
-struct RP {
-    x: usize,
-    y: usize,
-}
+/// A rock part's offset from the rock's bottom left corner.
+type RP = Vec2<usize>;
 
+#[derive(Clone, Copy)]
 enum Direction {
     Left,
     Right,
@@ -82,19 +83,12 @@ struct Simulation {
 }
 
 impl Simulation {
-    fn new(input: &str) -> Self {
+    fn new(jets: &Jets) -> Self {
         Self {
             stopped_rocks: Vec::new(),
             rock_cycle: 0,
             wind_cycle: 0,
-            wind_directions: input
-                .chars()
-                .map_while(|c| match c {
-                    '<' => Some(Direction::Left),
-                    '>' => Some(Direction::Right),
-                    _ => None,
-                })
-                .collect(),
+            wind_directions: jets.wind_directions.clone(),
         }
     }
 
@@ -123,12 +117,10 @@ impl Simulation {
                 return false;
             }
             match self.stopped_rocks.get(y) {
-                None => {}
-                Some(row) => {
-                    if row.get(x) != Some(&false) {
-                        return false;
-                    }
+                Some(row) if row.get(x) != Some(&false) => {
+                    return false;
                 }
+                _ => {}
             };
         }
 
@@ -175,56 +167,110 @@ impl Simulation {
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut simulation = Simulation::new(input);
-    for _ in 0..2022 {
-        simulation.drop_rock();
-    }
-    simulation.stopped_rocks.len()
-}
+/// How many rows of the top of the tower a frame shows.
+const WINDOW: usize = 30;
 
-pub fn part_two(input: &str) -> usize {
-    let mut simulation = Simulation::new(input);
-    let mut cycle_detector = HashMap::<(Vec<[bool; 7]>, usize, usize), (usize, u64)>::new();
-    let mut counter: u64 = 0;
-    let mut cave_height_increase = 0;
-    let goal: u64 = 1000000000000;
-    let mut jumped = false;
-    loop {
-        simulation.drop_rock();
-        counter += 1;
-        if !jumped {
-            if let Some(last_rows) = simulation
-                .stopped_rocks
-                .len()
-                .checked_sub(9)
-                .and_then(|s| simulation.stopped_rocks.get(s..))
-                .map(|s| s.to_vec())
-            {
-                match cycle_detector.entry((
-                    last_rows,
-                    simulation.rock_cycle,
-                    simulation.wind_cycle,
-                )) {
-                    hash_map::Entry::Occupied(o) => {
-                        let repeats = (goal - counter) / (counter - o.get().1);
-                        cave_height_increase +=
-                            repeats as usize * (simulation.stopped_rocks.len() - o.get().0);
-                        counter += repeats * (counter - o.get().1);
-                        jumped = true;
-                    }
-                    hash_map::Entry::Vacant(v) => {
-                        v.insert((simulation.stopped_rocks.len(), counter));
+/// The top of the tower, with a couple of empty rows above it and the floor once the tower is
+/// short enough to show it.
+impl Render for Simulation {
+    fn render(&self) -> Raster {
+        let top = (self.stopped_rocks.len() + 2).max(WINDOW - 2) as isize;
+        Raster::from_fn(9, WINDOW, |p| {
+            let y = top - p.y;
+            let edge = p.x == 0 || p.x == 8;
+            match (usize::try_from(y), edge) {
+                (Err(_), true) => Cell::new('+', Colour::WHITE),
+                (Err(_), false) => Cell::new('-', Colour::WHITE),
+                (Ok(_), true) => Cell::new('|', Colour::WHITE),
+                (Ok(y), false) => match self.stopped_rocks.get(y) {
+                    Some(row) if row[p.x as usize - 1] => {
+                        Cell::new('#', Colour::new(128, 128, 128))
                     }
-                }
+                    _ => Cell::new('.', Colour::BLACK),
+                },
             }
+        })
+    }
+}
+
+pub struct Jets {
+    wind_directions: Vec<Direction>,
+}
+
+impl Solution for Jets {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let wind_directions: Vec<_> = input
+            .chars()
+            .map_while(|c| match c {
+                '<' => Some(Direction::Left),
+                '>' => Some(Direction::Right),
+                _ => None,
+            })
+            .collect();
+        if wind_directions.is_empty() {
+            return Err(Error::parse(input, input, "a pattern of < and >"));
+        }
+        Ok(Self { wind_directions })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let mut simulation = Simulation::new(self);
+        for _ in 0..2022 {
+            simulation.drop_rock();
         }
-        if counter == goal {
-            return cave_height_increase + simulation.stopped_rocks.len();
+        Ok(simulation.stopped_rocks.len())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(tower_height_after_a_trillion_rocks(self))
+    }
+}
+
+/// The tower of part one, a frame per rock.
+impl Animate for Jets {
+    fn animate(&self, backend: &mut dyn Backend) -> std::io::Result<()> {
+        let mut simulation = Simulation::new(self);
+        backend.frame(&simulation.render())?;
+        for _ in 0..2022 {
+            simulation.drop_rock();
+            backend.frame(&simulation.render())?;
         }
+        Ok(())
     }
 }
 
+fn tower_height_after_a_trillion_rocks(jets: &Jets) -> usize {
+    simulate_until(
+        Simulation::new(jets),
+        |simulation| {
+            let height = simulation.stopped_rocks.len();
+            simulation.drop_rock();
+            simulation.stopped_rocks.len() - height
+        },
+        |simulation| {
+            let rows = &simulation.stopped_rocks;
+            (
+                rows[rows.len().saturating_sub(9)..].to_vec(),
+                simulation.rock_cycle,
+                simulation.wind_cycle,
+            )
+        },
+        1_000_000_000_000,
+    )
+}
+
+pub fn part_one(input: &str) -> usize {
+    aoc_core::part1::<Jets>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> usize {
+    aoc_core::part2::<Jets>(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,8 +281,22 @@ mod tests {
     }
 
     #[test]
+    fn example_animation() {
+        let mut frames = Vec::new();
+        Jets::parse(include_str!("../example.txt"))
+            .unwrap()
+            .animate(&mut frames)
+            .unwrap();
+        assert_eq!(frames.len(), 2023);
+        let bottom_of_first = frames[1].to_string();
+        assert!(bottom_of_first.ends_with("|..####.|\n+-------+\n"));
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 3048);
+        let input = aoc_input::challenge(2022, 17);
+        assert_eq!(part_one(&input), 3048);
     }
 
     #[test]
@@ -245,7 +305,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 1504093567249);
+        let input = aoc_input::challenge(2022, 17);
+        assert_eq!(part_two(&input), 1504093567249);
     }
 }
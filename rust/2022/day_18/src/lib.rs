@@ -1,17 +1,21 @@
 use std::{collections::BTreeSet, mem::take};
 
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec3;
 use nom::{
-    character::complete::{char, digit1, newline},
-    combinator::{iterator, map_res},
-    sequence::{terminated, tuple},
+    character::complete::{char, digit1},
+    combinator::map_res,
+    sequence::tuple,
     IResult,
 };
 
+type Cube = Vec3<i8>;
+
 #[derive(Debug, Default)]
-struct Scan(BTreeSet<(i8, i8, i8)>);
+pub struct Scan(BTreeSet<Cube>);
 
 impl Scan {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         fn parse_rock(input: &str) -> IResult<&str, (i8, char, i8, char, i8)> {
             tuple((
                 map_res(digit1, str::parse),
@@ -22,106 +26,89 @@ impl Scan {
             ))(input)
         }
         let mut rocks = BTreeSet::new();
-        for (x, _, y, _, z) in &mut iterator(input, terminated(parse_rock, newline)) {
-            rocks.insert((x, y, z));
+        for line in input.lines() {
+            let (x, _, y, _, z) = aoc_core::finish(input, parse_rock(line))?;
+            rocks.insert(Cube::new(x, y, z));
         }
-        Self(rocks)
+        Ok(Self(rocks))
     }
 
     fn surface_area(&self) -> usize {
         self.0
             .iter()
-            .map(|&(x, y, z)| {
-                6 - usize::from(self.0.contains(&(x - 1, y, z)))
-                    - usize::from(self.0.contains(&(x + 1, y, z)))
-                    - usize::from(self.0.contains(&(x, y - 1, z)))
-                    - usize::from(self.0.contains(&(x, y + 1, z)))
-                    - usize::from(self.0.contains(&(x, y, z - 1)))
-                    - usize::from(self.0.contains(&(x, y, z + 1)))
+            .map(|cube| {
+                cube.neighbours_6()
+                    .iter()
+                    .filter(|n| !self.0.contains(n))
+                    .count()
             })
             .sum()
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    Scan::new(input).surface_area()
-}
-
-pub fn part_two(input: &str) -> usize {
-    let rock_scan = Scan::new(input).0;
-    let x_min = rock_scan
-        .iter()
-        .map(|&(x, _, _)| x)
-        .min()
-        .unwrap_or_default()
-        - 1;
-    let x_max = rock_scan
-        .iter()
-        .map(|&(x, _, _)| x)
-        .max()
-        .unwrap_or_default()
-        + 1;
-    let y_min = rock_scan
-        .iter()
-        .map(|&(_, y, _)| y)
-        .min()
-        .unwrap_or_default()
-        - 1;
-    let y_max = rock_scan
-        .iter()
-        .map(|&(_, y, _)| y)
-        .max()
-        .unwrap_or_default()
-        + 1;
-    let z_min = rock_scan
-        .iter()
-        .map(|&(_, _, z)| z)
-        .min()
-        .unwrap_or_default()
-        - 1;
-    let z_max = rock_scan
-        .iter()
-        .map(|&(_, _, z)| z)
-        .max()
-        .unwrap_or_default()
-        + 1;
-    let mut external_scan = Scan::default();
-    external_scan.0.insert((x_min, y_min, z_min));
-    let mut recent = vec![(x_min, y_min, z_min)];
-    let mut visit = |(x, y, z): (i8, i8, i8), recent: &mut Vec<(i8, i8, i8)>| {
-        if !rock_scan.contains(&(x, y, z)) && external_scan.0.insert((x, y, z)) {
-            recent.push((x, y, z));
-        }
-    };
+impl Scan {
+    fn exterior_surface_area(&self) -> usize {
+        let rock_scan = &self.0;
+        let first = rock_scan.first().copied().unwrap_or_default();
+        let (min, max) = rock_scan.iter().fold((first, first), |(min, max), c| {
+            (
+                Cube::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)),
+                Cube::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)),
+            )
+        });
+        let margin = Cube::new(1, 1, 1);
+        let (min, max) = (min - margin, max + margin);
+        let in_bounds = |c: &Cube| {
+            (min.x..=max.x).contains(&c.x)
+                && (min.y..=max.y).contains(&c.y)
+                && (min.z..=max.z).contains(&c.z)
+        };
 
-    while !recent.is_empty() {
-        for &(x, y, z) in take(&mut recent).iter() {
-            if x > x_min {
-                visit((x - 1, y, z), &mut recent);
-            }
-            if x < x_max {
-                visit((x + 1, y, z), &mut recent);
-            }
-            if y > y_min {
-                visit((x, y - 1, z), &mut recent);
-            }
-            if y < y_max {
-                visit((x, y + 1, z), &mut recent);
-            }
-            if z > z_min {
-                visit((x, y, z - 1), &mut recent);
-            }
-            if z < z_max {
-                visit((x, y, z + 1), &mut recent);
+        let mut external_scan = Scan::default();
+        external_scan.0.insert(min);
+        let mut recent = vec![min];
+        while !recent.is_empty() {
+            for cube in take(&mut recent) {
+                for n in cube.neighbours_6() {
+                    if in_bounds(&n) && !rock_scan.contains(&n) && external_scan.0.insert(n) {
+                        recent.push(n);
+                    }
+                }
             }
         }
+
+        let width = (max.x + 1 - min.x) as usize;
+        let height = (max.y + 1 - min.y) as usize;
+        let depth = (max.z + 1 - min.z) as usize;
+
+        external_scan.surface_area() - width * height * 2 - width * depth * 2 - height * depth * 2
     }
+}
 
-    let width = (x_max + 1 - x_min) as usize;
-    let height = (y_max + 1 - y_min) as usize;
-    let depth = (z_max + 1 - z_min) as usize;
+impl Solution for Scan {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self::new(input)?)
+    }
 
-    external_scan.surface_area() - width * height * 2 - width * depth * 2 - height * depth * 2
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.surface_area())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.exterior_surface_area())
+    }
+}
+
+pub fn part_one(input: &str) -> usize {
+    aoc_core::part1::<Scan>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> usize {
+    aoc_core::part2::<Scan>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -134,8 +121,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 3650);
+        let input = aoc_input::challenge(2022, 18);
+        assert_eq!(part_one(&input), 3650);
     }
 
     #[test]
@@ -144,7 +133,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2118);
+        let input = aoc_input::challenge(2022, 18);
+        assert_eq!(part_two(&input), 2118);
     }
 }
@@ -1,5 +1,6 @@
-use std::{collections::HashSet, iter::zip, mem::take};
+use std::iter::zip;
 
+use aoc_core::{Error, Solution};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, multispace1},
@@ -10,10 +11,15 @@ use nom::{
 };
 use rayon::prelude::*;
 
+const ROBOTS: [&str; 4] = ["ore", "clay", "obsidian", "geode"];
+const GEODE: usize = 3;
+
 #[derive(Debug, Hash, Eq, PartialEq)]
 struct Blueprint {
     id: u32,
     costs: [[u32; 4]; 4],
+    /// The most of each resource that can be spent in a minute, so there's no point having
+    /// more robots collecting it. Geode robots are never enough.
     max_robots_required: [u32; 4],
 }
 
@@ -83,161 +89,204 @@ impl Blueprint {
                             .max(geode_robot_cost_ore),
                         obsidian_robot_cost_clay,
                         geode_robot_cost_obsidian,
-                        50,
+                        u32::MAX,
                     ],
                 }
             },
         )(input)
     }
 
-    fn geode_count(&self, rounds: u8) -> u32 {
-        let start = World {
-            robot_counts: [1, 0, 0, 0],
-            resource_counts: [0, 0, 0, 0],
+    /// The most geodes that can be opened in `minutes`, and the robots to build to do it.
+    fn plan(&self, minutes: u32) -> Plan {
+        let mut search = Search {
             blueprint: self,
+            minutes,
+            best: 0,
+            best_builds: Vec::new(),
+            builds: Vec::new(),
         };
-        let mut next_possibilities = vec![start];
-        for i in 0..rounds {
-            #[cfg(feature = "show-progress")]
-            if i >= 23 {
-                println!(
-                    "{} beginning round {}, {}",
-                    self.id,
-                    i,
-                    next_possibilities.len()
-                );
-            }
-            let mut possibilities = HashSet::<World>::new();
-
-            if i <= rounds - 2 {
-                next_possibilities.sort_by_cached_key(|p| {
-                    p.robot_counts.iter().sum::<u32>() + p.resource_counts.iter().sum::<u32>()
-                });
-                next_possibilities = zip(
-                    (0..next_possibilities.len()).rev(),
-                    next_possibilities.iter(),
-                )
-                .filter_map(|(i, p)| {
-                    (i > 10000
-                        || !next_possibilities.iter().rev().take(i).take(5000).any(|o| {
-                            zip(p.robot_counts.iter(), o.robot_counts.iter())
-                                .all(|(p_rc, o_rc)| p_rc <= o_rc)
-                                && zip(p.resource_counts.iter(), o.resource_counts.iter())
-                                    .all(|(p_rc, o_rc)| p_rc <= o_rc)
-                        }))
-                    .then_some(*p)
-                })
-                .collect();
-                #[cfg(feature = "show-progress")]
-                if i >= 23 {
-                    println!(
-                        "{} second part of round {}",
-                        self.id,
-                        next_possibilities.len()
-                    );
-                }
-            }
-            for possibility in take(&mut next_possibilities).iter() {
-                if i >= rounds - 2 {
-                    if let Some(possibility) = possibility.build_robot_and_collect(3) {
-                        next_possibilities.push(possibility);
-                    } else {
-                        let possibility = possibility.collect();
-                        next_possibilities.push(possibility);
-                    }
-                } else if zip(possibility.robot_counts.iter(), self.costs[3].iter())
-                    .all(|(&r, &c)| r >= c)
-                    && zip(possibility.resource_counts.iter(), self.costs[3].iter())
-                        .all(|(&r, &c)| r >= c)
-                {
-                    next_possibilities.push(possibility.build_robot_and_collect(3).unwrap());
-                } else {
-                    if !possibility.can_afford_everything() {
-                        let possibility = possibility.collect();
-                        if possibilities.insert(possibility) {
-                            next_possibilities.push(possibility);
-                        }
-                    }
-                    for robot in 0..4 {
-                        if let Some(possibility) = possibility.build_robot_and_collect(robot) {
-                            if possibilities.insert(possibility) {
-                                next_possibilities.push(possibility);
-                            }
-                        }
-                    }
-                }
-            }
+        search.search(State {
+            time_left: minutes,
+            robot_counts: [1, 0, 0, 0],
+            resource_counts: [0; 4],
+            geodes: 0,
+        });
+        Plan {
+            blueprint: self.id,
+            geodes: search.best,
+            builds: search.best_builds,
         }
-        next_possibilities
-            .iter()
-            .map(|p| p.resource_counts[3])
-            .max()
-            .unwrap_or(0)
     }
 }
 
-#[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
-struct World<'a> {
+/// A robot built, and the minute it was built in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Build {
+    pub robot: &'static str,
+    pub minute: u32,
+}
+
+/// The most geodes a blueprint can open, and the robots to build in order to do it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub blueprint: u32,
+    pub geodes: u32,
+    pub builds: Vec<Build>,
+}
+
+/// The factory between builds. Geodes are counted as soon as the robot that will open them is
+/// built, so geode robots and the geodes they've opened aren't kept track of separately.
+#[derive(Clone, Copy, Debug)]
+struct State {
+    time_left: u32,
     robot_counts: [u32; 4],
     resource_counts: [u32; 4],
+    geodes: u32,
+}
+
+/// A depth first search over which robot to build next, skipping straight to the minute
+/// it's affordable rather than stepping through every minute.
+struct Search<'a> {
     blueprint: &'a Blueprint,
+    minutes: u32,
+    best: u32,
+    best_builds: Vec<Build>,
+    builds: Vec<Build>,
 }
 
-impl<'a> World<'a> {
-    fn collect(&self) -> Self {
-        let mut world = *self;
-        for (resource, robot) in zip(world.resource_counts.iter_mut(), world.robot_counts.iter()) {
-            *resource += *robot;
+impl Search<'_> {
+    /// The geodes that could be opened if ore and clay were free, and an obsidian robot were
+    /// built every minute a geode robot couldn't be.
+    fn upper_bound(&self, state: &State) -> u32 {
+        let cost = self.blueprint.costs[GEODE][2];
+        let mut obsidian = state.resource_counts[2];
+        let mut obsidian_robots = state.robot_counts[2];
+        let mut geodes = state.geodes;
+        for time_left in (0..state.time_left).rev() {
+            if obsidian >= cost {
+                obsidian = obsidian - cost + obsidian_robots;
+                geodes += time_left;
+            } else {
+                obsidian += obsidian_robots;
+                obsidian_robots += 1;
+            }
         }
-        world
+        geodes
     }
 
-    fn can_afford_everything(&self) -> bool {
-        self.blueprint.costs.iter().all(|costs| {
-            zip(costs.iter(), self.resource_counts.iter())
-                .all(|(cost, available)| cost <= available)
-        })
+    /// The state once `robot` has been built, waiting for the resources first, if it can be
+    /// built before the end with time left to use it.
+    fn build(&self, state: &State, robot: usize) -> Option<State> {
+        let costs = &self.blueprint.costs[robot];
+        let mut wait = 0;
+        for ((&cost, &have), &robots) in
+            zip(zip(costs, &state.resource_counts), &state.robot_counts)
+        {
+            if cost > have {
+                if robots == 0 {
+                    return None;
+                }
+                wait = wait.max((cost - have).div_ceil(robots));
+            }
+        }
+        let time_left = state.time_left.checked_sub(wait + 1).filter(|&t| t > 0)?;
+        let mut next = State {
+            time_left,
+            ..*state
+        };
+        for ((resource, robots), cost) in
+            zip(zip(&mut next.resource_counts, &state.robot_counts), costs)
+        {
+            *resource = *resource + robots * (wait + 1) - cost;
+        }
+        if robot == GEODE {
+            next.geodes += time_left;
+        } else {
+            next.robot_counts[robot] += 1;
+        }
+        Some(next)
     }
 
-    fn build_robot_and_collect(&self, robot: usize) -> Option<Self> {
-        let mut world = *self;
-        let costs = self.blueprint.costs.get(robot)?;
-        let max_required = self.blueprint.max_robots_required.get(robot)?;
-        if self.robot_counts.get(robot)? >= max_required {
-            return None;
+    fn search(&mut self, state: State) {
+        if state.geodes > self.best {
+            self.best = state.geodes;
+            self.best_builds.clone_from(&self.builds);
         }
-        for (resource, cost) in zip(world.resource_counts.iter_mut(), costs.iter()) {
-            *resource = resource.checked_sub(*cost)?;
+        if self.upper_bound(&state) <= self.best {
+            return;
         }
-        for (resource, robot) in zip(world.resource_counts.iter_mut(), world.robot_counts.iter()) {
-            *resource += *robot;
+        for robot in (0..4).rev() {
+            let max = self.blueprint.max_robots_required[robot];
+            // enough robots, or enough stockpiled, to spend the most possible every minute
+            if robot != GEODE
+                && (state.robot_counts[robot] >= max
+                    || state.robot_counts[robot] * state.time_left + state.resource_counts[robot]
+                        >= max * state.time_left)
+            {
+                continue;
+            }
+            if let Some(next) = self.build(&state, robot) {
+                self.builds.push(Build {
+                    robot: ROBOTS[robot],
+                    minute: self.minutes - next.time_left,
+                });
+                self.search(next);
+                self.builds.pop();
+            }
         }
-        *world.robot_counts.get_mut(robot)? += 1;
-        Some(world)
     }
 }
 
-fn blueprints(input: &str) -> Vec<Blueprint> {
-    separated_list1(multispace1, Blueprint::parse_nom)(input)
-        .unwrap()
-        .1
+pub struct Blueprints {
+    blueprints: Vec<Blueprint>,
+}
+
+impl Solution for Blueprints {
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let blueprints = aoc_core::finish(
+            input,
+            separated_list1(multispace1, Blueprint::parse_nom)(input),
+        )?;
+        Ok(Self { blueprints })
+    }
+
+    fn part1(&self, _: &()) -> Result<u32, Error> {
+        Ok(self
+            .plans(24)
+            .iter()
+            .map(|plan| plan.blueprint * plan.geodes)
+            .sum())
+    }
+
+    /// Only the first three blueprints are left after the elephants have eaten the rest.
+    fn part2(&self, _: &()) -> Result<u32, Error> {
+        Ok(self.blueprints[..self.blueprints.len().min(3)]
+            .par_iter()
+            .map(|bp| bp.plan(32).geodes)
+            .product())
+    }
+}
+
+impl Blueprints {
+    /// The best plan for each blueprint, worked out in parallel.
+    pub fn plans(&self, minutes: u32) -> Vec<Plan> {
+        self.blueprints
+            .par_iter()
+            .map(|bp| bp.plan(minutes))
+            .collect()
+    }
 }
 
 pub fn part_one(input: &str) -> u32 {
-    let blueprints = blueprints(input);
-    blueprints
-        .par_iter()
-        .map(|bp| bp.id * bp.geode_count(24))
-        .sum()
+    aoc_core::part1::<Blueprints>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> u32 {
-    let blueprints = blueprints(input);
-    blueprints
-        .par_iter()
-        .take(3)
-        .map(|bp| bp.geode_count(32))
-        .product()
+    aoc_core::part2::<Blueprints>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -250,8 +299,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1962);
+        let input = aoc_input::challenge(2022, 19);
+        assert_eq!(part_one(&input), 1962);
     }
 
     #[test]
@@ -260,7 +311,46 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 88160);
+        let input = aoc_input::challenge(2022, 19);
+        assert_eq!(part_two(&input), 88160);
+    }
+
+    /// Runs the factory a minute at a time, checking each robot in `plan` is affordable when
+    /// it's built, and counts the geodes opened.
+    fn replay(blueprint: &Blueprint, plan: &Plan, minutes: u32) -> u32 {
+        let mut robots = [1, 0, 0, 0];
+        let mut resources = [0u32; 4];
+        let mut builds = plan.builds.iter().peekable();
+        for minute in 1..=minutes {
+            let built = builds.next_if(|b| b.minute == minute).map(|b| {
+                let robot = ROBOTS.iter().position(|&r| r == b.robot).unwrap();
+                for (resource, cost) in zip(&mut resources, blueprint.costs[robot]) {
+                    *resource = resource.checked_sub(cost).expect("robot isn't affordable");
+                }
+                robot
+            });
+            for (resource, robots) in zip(&mut resources, robots) {
+                *resource += robots;
+            }
+            if let Some(robot) = built {
+                robots[robot] += 1;
+            }
+        }
+        assert!(builds.next().is_none(), "robots built out of order");
+        resources[GEODE]
+    }
+
+    #[test]
+    fn example_plans() {
+        let blueprints = Blueprints::parse(include_str!("../example.txt")).unwrap();
+        let plans = blueprints.plans(24);
+        assert_eq!(plans.iter().map(|p| p.geodes).collect::<Vec<_>>(), [9, 12]);
+        for (blueprint, plan) in zip(&blueprints.blueprints, &plans) {
+            assert_eq!(replay(blueprint, plan, 24), plan.geodes);
+        }
+        let plan = blueprints.blueprints[0].plan(32);
+        assert_eq!(replay(&blueprints.blueprints[0], &plan, 32), 56);
     }
 }
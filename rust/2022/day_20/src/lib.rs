@@ -1,8 +1,6 @@
 use std::iter::zip;
 
-fn parse(input: &str) -> Vec<i64> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
-}
+use aoc_core::{Error, Solution};
 
 fn mix(input: &[i64], order: &mut Vec<i64>) -> Vec<i64> {
     for (i, &num) in zip(0.., input.iter()) {
@@ -17,29 +15,61 @@ fn mix(input: &[i64], order: &mut Vec<i64>) -> Vec<i64> {
     order.iter().map(|&i| input[i as usize]).collect()
 }
 
-pub fn part_one(input: &str) -> i64 {
-    let nums = parse(input);
-    let mut order = (0..nums.len() as i64).collect::<Vec<_>>();
-    let mixed = mix(&nums, &mut order);
-
-    let p = mixed.iter().position(|&n| n == 0).unwrap();
-    mixed[(p + 1000) % mixed.len()]
+fn grove_coordinates(mixed: &[i64]) -> Result<i64, Error> {
+    let p = mixed
+        .iter()
+        .position(|&n| n == 0)
+        .ok_or_else(|| Error::no_answer("there is no 0 in the file"))?;
+    Ok(mixed[(p + 1000) % mixed.len()]
         + mixed[(p + 2000) % mixed.len()]
-        + mixed[(p + 3000) % mixed.len()]
+        + mixed[(p + 3000) % mixed.len()])
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let nums: Vec<i64> = parse(input).iter().map(|n| n * 811589153).collect();
-    let mut order = (0..nums.len() as i64).collect::<Vec<_>>();
-    let mut mixed = Vec::new();
-    for _ in 0..10 {
-        mixed = mix(&nums, &mut order);
+pub struct EncryptedFile {
+    numbers: Vec<i64>,
+}
+
+impl Solution for EncryptedFile {
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let numbers = input
+            .lines()
+            .map(|line| {
+                line.parse()
+                    .map_err(|_| Error::parse(input, line, "a number"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { numbers })
     }
 
-    let p = mixed.iter().position(|&n| n == 0).unwrap();
-    mixed[(p + 1000) % mixed.len()]
-        + mixed[(p + 2000) % mixed.len()]
-        + mixed[(p + 3000) % mixed.len()]
+    fn part1(&self, _: &()) -> Result<i64, Error> {
+        let mut order = (0..self.numbers.len() as i64).collect::<Vec<_>>();
+        let mixed = mix(&self.numbers, &mut order);
+
+        grove_coordinates(&mixed)
+    }
+
+    fn part2(&self, _: &()) -> Result<i64, Error> {
+        let nums: Vec<i64> = self.numbers.iter().map(|n| n * 811589153).collect();
+        let mut order = (0..nums.len() as i64).collect::<Vec<_>>();
+        let mut mixed = Vec::new();
+        for _ in 0..10 {
+            mixed = mix(&nums, &mut order);
+        }
+
+        grove_coordinates(&mixed)
+    }
+}
+
+pub fn part_one(input: &str) -> i64 {
+    aoc_core::part1::<EncryptedFile>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> i64 {
+    aoc_core::part2::<EncryptedFile>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -53,8 +83,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 8028);
+        let input = aoc_input::challenge(2022, 20);
+        assert_eq!(part_one(&input), 8028);
     }
 
     #[test]
@@ -63,7 +95,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 8798438007673);
+        let input = aoc_input::challenge(2022, 20);
+        assert_eq!(part_two(&input), 8798438007673);
     }
 }
@@ -0,0 +1,274 @@
+use std::fmt;
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    pub const ZERO: Self = Self::integer(0);
+
+    pub const fn integer(n: i128) -> Self {
+        Self {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    fn new(numerator: i128, denominator: i128) -> Result<Self, SolveError> {
+        if denominator == 0 {
+            return Err(SolveError::DivisionByZero);
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Ok(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    fn apply(self, op: Operation, other: Self) -> Result<Self, SolveError> {
+        let overflow = || SolveError::Overflow;
+        let (a, b, c, d) = (
+            self.numerator,
+            self.denominator,
+            other.numerator,
+            other.denominator,
+        );
+        let cross = |sign: i128| -> Option<(i128, i128)> {
+            Some((
+                a.checked_mul(d)?
+                    .checked_add(sign.checked_mul(c)?.checked_mul(b)?)?,
+                b.checked_mul(d)?,
+            ))
+        };
+        let (numerator, denominator) = match op {
+            Operation::Plus => cross(1).ok_or_else(overflow)?,
+            Operation::Minus => cross(-1).ok_or_else(overflow)?,
+            Operation::Mul => (
+                a.checked_mul(c).ok_or_else(overflow)?,
+                b.checked_mul(d).ok_or_else(overflow)?,
+            ),
+            Operation::Div => (
+                a.checked_mul(d).ok_or_else(overflow)?,
+                b.checked_mul(c).ok_or_else(overflow)?,
+            ),
+        };
+        Self::new(numerator, denominator)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_integer() {
+            Some(n) => write!(f, "{n}"),
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Plus,
+    Minus,
+    Mul,
+    Div,
+}
+
+impl Operation {
+    fn symbol(self) -> char {
+        match self {
+            Operation::Plus => '+',
+            Operation::Minus => '-',
+            Operation::Mul => '*',
+            Operation::Div => '/',
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Operation::Plus | Operation::Minus => 1,
+            Operation::Mul | Operation::Div => 2,
+        }
+    }
+}
+
+/// Why an unknown couldn't be solved for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    DivisionByZero,
+    Overflow,
+    /// The unknown is multiplied by itself or divided by.
+    NonLinear,
+    /// The unknown cancels out, leaving two different numbers.
+    NoSolution,
+    /// The unknown cancels out, and any value will do.
+    EverySolution,
+    NotInteger(Rational),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::DivisionByZero => write!(f, "it divides by zero"),
+            SolveError::Overflow => write!(f, "the numbers get too big"),
+            SolveError::NonLinear => write!(f, "the unknown isn't linear"),
+            SolveError::NoSolution => write!(f, "no value makes the sides equal"),
+            SolveError::EverySolution => write!(f, "every value makes the sides equal"),
+            SolveError::NotInteger(value) => write!(f, "the answer {value} isn't a whole number"),
+        }
+    }
+}
+
+/// `coefficient * unknown + constant`.
+#[derive(Clone, Copy, Debug)]
+struct Linear {
+    coefficient: Rational,
+    constant: Rational,
+}
+
+impl Linear {
+    fn scale(self, op: Operation, by: Rational) -> Result<Self, SolveError> {
+        Ok(Linear {
+            coefficient: self.coefficient.apply(op, by)?,
+            constant: self.constant.apply(op, by)?,
+        })
+    }
+
+    fn apply(self, op: Operation, other: Self) -> Result<Self, SolveError> {
+        match op {
+            Operation::Plus | Operation::Minus => Ok(Linear {
+                coefficient: self.coefficient.apply(op, other.coefficient)?,
+                constant: self.constant.apply(op, other.constant)?,
+            }),
+            Operation::Mul if self.coefficient == Rational::ZERO => other.scale(op, self.constant),
+            Operation::Mul | Operation::Div if other.coefficient == Rational::ZERO => {
+                self.scale(op, other.constant)
+            }
+            Operation::Mul | Operation::Div => Err(SolveError::NonLinear),
+        }
+    }
+}
+
+/// A formula of numbers and one unknown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expression {
+    Number(Rational),
+    Unknown(String),
+    Binary(Box<Expression>, Operation, Box<Expression>),
+}
+
+impl Expression {
+    /// Works out every part of the formula that doesn't involve the unknown.
+    pub fn simplify(self) -> Result<Self, SolveError> {
+        match self {
+            Expression::Binary(a, op, b) => match (a.simplify()?, b.simplify()?) {
+                (Expression::Number(a), Expression::Number(b)) => {
+                    Ok(Expression::Number(a.apply(op, b)?))
+                }
+                (a, b) => Ok(Expression::Binary(Box::new(a), op, Box::new(b))),
+            },
+            leaf => Ok(leaf),
+        }
+    }
+
+    fn linear(&self) -> Result<Linear, SolveError> {
+        match self {
+            Expression::Number(n) => Ok(Linear {
+                coefficient: Rational::ZERO,
+                constant: *n,
+            }),
+            Expression::Unknown(_) => Ok(Linear {
+                coefficient: Rational::integer(1),
+                constant: Rational::ZERO,
+            }),
+            Expression::Binary(a, op, b) => a.linear()?.apply(*op, b.linear()?),
+        }
+    }
+
+    /// The value of a formula without the unknown.
+    pub fn value(&self) -> Result<Rational, SolveError> {
+        Ok(self.linear()?.constant)
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter, parent: Operation, right: bool) -> fmt::Result {
+        match self {
+            Expression::Binary(_, op, _)
+                if op.precedence() < parent.precedence()
+                    || (right
+                        && op.precedence() == parent.precedence()
+                        && matches!(parent, Operation::Minus | Operation::Div)) =>
+            {
+                write!(f, "({self})")
+            }
+            _ => write!(f, "{self}"),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Number(n) => write!(f, "{n}"),
+            Expression::Unknown(name) => write!(f, "{name}"),
+            Expression::Binary(a, op, b) => {
+                a.fmt_operand(f, *op, false)?;
+                write!(f, " {} ", op.symbol())?;
+                b.fmt_operand(f, *op, true)
+            }
+        }
+    }
+}
+
+/// Two formulas that are equal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub left: Expression,
+    pub right: Expression,
+}
+
+impl Equation {
+    pub fn simplify(self) -> Result<Self, SolveError> {
+        Ok(Equation {
+            left: self.left.simplify()?,
+            right: self.right.simplify()?,
+        })
+    }
+
+    /// The value of the unknown, wherever it appears, as long as it's only ever added,
+    /// subtracted, multiplied by a number or divided by one.
+    pub fn solve(&self) -> Result<Rational, SolveError> {
+        let difference = self
+            .left
+            .linear()?
+            .apply(Operation::Minus, self.right.linear()?)?;
+        if difference.coefficient != Rational::ZERO {
+            Rational::ZERO
+                .apply(Operation::Minus, difference.constant)?
+                .apply(Operation::Div, difference.coefficient)
+        } else if difference.constant == Rational::ZERO {
+            Err(SolveError::EverySolution)
+        } else {
+            Err(SolveError::NoSolution)
+        }
+    }
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.left, self.right)
+    }
+}
@@ -1,85 +1,34 @@
 use std::collections::HashMap;
 
+mod expression;
+
+use aoc_core::{Error, Solution};
+use expression::{Equation, Expression, Operation, Rational, SolveError};
+
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
-    character::complete::{digit1, newline},
-    combinator::{iterator, map, map_res, value},
-    sequence::{terminated, tuple},
+    character::complete::digit1,
+    combinator::{map, map_res, value},
+    sequence::tuple,
     IResult,
 };
 
-#[derive(Clone, Copy, Debug)]
-enum Operation {
-    Plus,
-    Minus,
-    Mul,
-    Div,
-    Eq,
-}
-
 #[derive(Clone, Debug)]
-enum Job<'a> {
+enum Job {
     Number(i64),
-    Dependent {
-        a: &'a str,
-        op: Operation,
-        b: &'a str,
-    },
-}
-
-impl<'a> Job<'a> {
-    fn get_value(&self, troop: &Troop) -> Option<i64> {
-        match self {
-            Job::Number(n) => Some(*n),
-            Job::Dependent { a, op, b } => {
-                let a = troop.monkeys.get(a)?.get_value(troop)?;
-                let b = troop.monkeys.get(b)?.get_value(troop)?;
-                match op {
-                    Operation::Plus => Some(a + b),
-                    Operation::Minus => Some(a - b),
-                    Operation::Mul => Some(a * b),
-                    Operation::Div => Some(a / b),
-                    Operation::Eq => None,
-                }
-            }
-        }
-    }
-
-    fn missing_value(&self, troop: &Troop, value: i64) -> Option<(i64, &str)> {
-        match self {
-            Job::Number(_) => None,
-            &Job::Dependent { a, op, b } => {
-                let a_val = troop.monkeys.get(a).and_then(|j| j.get_value(troop));
-                let b_val = troop.monkeys.get(b).and_then(|j| j.get_value(troop));
-                match (a_val, op, b_val) {
-                    (Some(_), _, Some(_)) => None,
-                    (None, _, None) => None,
-                    (Some(a_val), Operation::Plus, None) => Some((value - a_val, b)),
-                    (Some(a_val), Operation::Minus, None) => Some((a_val - value, b)),
-                    (Some(a_val), Operation::Div, None) => Some((a_val / value, b)),
-                    (Some(a_val), Operation::Mul, None) => Some((value / a_val, b)),
-                    (Some(a_val), Operation::Eq, None) => Some((a_val, b)),
-                    (None, Operation::Plus, Some(b_val)) => Some((value - b_val, a)),
-                    (None, Operation::Minus, Some(b_val)) => Some((value + b_val, a)),
-                    (None, Operation::Div, Some(b_val)) => Some((value * b_val, a)),
-                    (None, Operation::Mul, Some(b_val)) => Some((value / b_val, a)),
-                    (None, Operation::Eq, Some(b_val)) => Some((b_val, a)),
-                }
-            }
-        }
-    }
+    Dependent { a: String, op: Operation, b: String },
 }
 
 #[derive(Clone, Debug)]
 struct MonkeyJob<'a> {
     name: &'a str,
-    job: Job<'a>,
+    job: Job,
 }
 
 impl<'a> MonkeyJob<'a> {
     fn parse_nom(input: &'a str) -> IResult<&'a str, Self> {
-        let name_cond = |c: char| ('a'..='z').contains(&c);
+        let name_cond = |c: char| c.is_ascii_lowercase();
         map(
             tuple((
                 take_while1(name_cond),
@@ -97,7 +46,11 @@ impl<'a> MonkeyJob<'a> {
                             )),
                             take_while1(name_cond),
                         )),
-                        |(a, op, b)| Job::Dependent { a, op, b },
+                        |(a, op, b): (&str, _, &str)| Job::Dependent {
+                            a: a.to_owned(),
+                            op,
+                            b: b.to_owned(),
+                        },
                     ),
                 )),
             )),
@@ -107,51 +60,106 @@ impl<'a> MonkeyJob<'a> {
 }
 
 #[derive(Clone, Debug)]
-struct Troop<'a> {
-    monkeys: HashMap<&'a str, Job<'a>>,
+pub struct Troop {
+    monkeys: HashMap<String, Job>,
 }
 
-impl<'a> Troop<'a> {
-    fn new(input: &'a str) -> Self {
+impl Solution for Troop {
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
         let mut monkeys = HashMap::new();
-        for monkey_job in &mut iterator(input, terminated(MonkeyJob::parse_nom, newline)) {
-            monkeys.insert(monkey_job.name, monkey_job.job);
+        for line in input.lines() {
+            let monkey_job = aoc_core::finish(input, MonkeyJob::parse_nom(line))?;
+            monkeys.insert(monkey_job.name.to_owned(), monkey_job.job);
         }
-        Self { monkeys }
+        Ok(Self { monkeys })
+    }
+
+    fn part1(&self, _: &()) -> Result<i64, Error> {
+        self.expression("root", None)?
+            .value()
+            .and_then(whole)
+            .map_err(|e| Error::no_answer(format!("root's value can't be calculated: {e}")))
+    }
+
+    fn part2(&self, _: &()) -> Result<i64, Error> {
+        self.solve_for("humn")
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let troop = Troop::new(input);
-    let root = troop.monkeys.get("root")?;
-    root.get_value(&troop)
+/// `value` as an `i64`, if it's a whole number that fits.
+fn whole(value: Rational) -> Result<i64, SolveError> {
+    let n = value.to_integer().ok_or(SolveError::NotInteger(value))?;
+    i64::try_from(n).map_err(|_| SolveError::Overflow)
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let mut troop = Troop::new(input);
-    troop.monkeys.remove("humn");
-    if let &Job::Dependent { a, b, .. } = troop.monkeys.get("root")? {
-        let a_val = troop.monkeys.get(a).and_then(|j| j.get_value(&troop));
-        let b_val = troop.monkeys.get(b).and_then(|j| j.get_value(&troop));
-        let mut job = &Job::Dependent {
-            a,
-            op: Operation::Eq,
-            b,
+impl Troop {
+    /// What `name` yells, as a formula in terms of `unknown` if it's given.
+    fn expression(&self, name: &str, unknown: Option<&str>) -> Result<Expression, Error> {
+        if unknown == Some(name) {
+            return Ok(Expression::Unknown(name.to_owned()));
+        }
+        match self.monkeys.get(name) {
+            None => Err(Error::no_answer(format!(
+                "there is no monkey called {name}"
+            ))),
+            Some(Job::Number(n)) => Ok(Expression::Number(Rational::integer((*n).into()))),
+            Some(Job::Dependent { a, op, b }) => Ok(Expression::Binary(
+                Box::new(self.expression(a, unknown)?),
+                *op,
+                Box::new(self.expression(b, unknown)?),
+            )),
+        }
+    }
+
+    /// What root checks for, with the number `unknown` yells left out and everything else
+    /// worked out.
+    fn equation(&self, unknown: &str) -> Result<Equation, Error> {
+        if !matches!(self.monkeys.get(unknown), Some(Job::Number(_))) {
+            return Err(Error::no_answer(format!(
+                "{unknown} isn't a monkey yelling a number"
+            )));
+        }
+        let Some(Job::Dependent { a, b, .. }) = self.monkeys.get("root") else {
+            return Err(Error::no_answer("root doesn't compare two monkeys"));
         };
-        let mut value = a_val.or(b_val)?;
-        loop {
-            let value_monkey = job.missing_value(&troop, value)?;
-            value = value_monkey.0;
-            let monkey = value_monkey.1;
-            if monkey == "humn" {
-                break Some(value);
-            } else {
-                job = troop.monkeys.get(monkey)?;
-            }
+        Equation {
+            left: self.expression(a, Some(unknown))?,
+            right: self.expression(b, Some(unknown))?,
         }
-    } else {
-        None
+        .simplify()
+        .map_err(|e| Error::no_answer(e.to_string()))
     }
+
+    /// The number `unknown` has to yell for root's check to pass.
+    fn solve_for(&self, unknown: &str) -> Result<i64, Error> {
+        let equation = self.equation(unknown)?;
+        equation
+            .solve()
+            .and_then(whole)
+            .map_err(|e| Error::no_answer(format!("can't solve {equation}: {e}")))
+    }
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    aoc_core::part1::<Troop>(input).ok()
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    aoc_core::part2::<Troop>(input).ok()
+}
+
+/// Root's check written out in terms of `unknown`, with everything else worked out.
+pub fn formula(input: &str, unknown: &str) -> Result<String, Error> {
+    Ok(Troop::parse(input)?.equation(unknown)?.to_string())
+}
+
+/// The number `unknown` has to yell for root's check to pass.
+pub fn solve_for(input: &str, unknown: &str) -> Result<i64, Error> {
+    Troop::parse(input)?.solve_for(unknown)
 }
 
 #[cfg(test)]
@@ -164,11 +172,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(
-            part_one(include_str!("../challenge.txt")),
-            Some(49288254556480)
-        );
+        let input = aoc_input::challenge(2022, 21);
+        assert_eq!(part_one(&input), Some(49288254556480));
     }
 
     #[test]
@@ -177,10 +184,48 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
+        let input = aoc_input::challenge(2022, 21);
+        assert_eq!(part_two(&input), Some(3558714869436));
+    }
+
+    #[test]
+    fn example_formula() {
+        assert_eq!(
+            formula(include_str!("../example.txt"), "humn").unwrap(),
+            "(4 + 2 * (humn - 3)) / 4 = 150"
+        );
+    }
+
+    #[test]
+    fn solves_for_any_number() {
+        assert_eq!(
+            solve_for(include_str!("../example.txt"), "dvpt").unwrap(),
+            -293
+        );
+        assert!(solve_for(include_str!("../example.txt"), "pppw").is_err());
+    }
+
+    #[test]
+    fn solves_with_the_unknown_on_both_sides() {
+        let input =
+            "root: aaaa + bbbb\naaaa: humn * cccc\ncccc: 3\nbbbb: humn + dddd\ndddd: 10\nhumn: 1\n";
+        assert_eq!(formula(input, "humn").unwrap(), "humn * 3 = humn + 10");
+        assert_eq!(solve_for(input, "humn").unwrap(), 5);
+    }
+
+    #[test]
+    fn reports_answers_it_cannot_give() {
+        let squared = "root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 4\nhumn: 1\n";
+        let half = "root: aaaa + bbbb\naaaa: humn * cccc\ncccc: 2\nbbbb: 3\nhumn: 1\n";
+        assert_eq!(
+            solve_for(squared, "humn").unwrap_err().to_string(),
+            "no answer: can't solve humn * humn = 4: the unknown isn't linear"
+        );
         assert_eq!(
-            part_two(include_str!("../challenge.txt")),
-            Some(3558714869436)
+            solve_for(half, "humn").unwrap_err().to_string(),
+            "no answer: can't solve humn * 2 = 3: the answer 3/2 isn't a whole number"
         );
     }
 }
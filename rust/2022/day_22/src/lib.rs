@@ -1,5 +1,8 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::slice;
 
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec3;
 use nom::{
     branch::alt,
     character::complete::{char, digit1, newline},
@@ -23,7 +26,7 @@ enum Direction {
 }
 
 #[derive(Debug)]
-struct Notes {
+pub struct Notes {
     map: Vec<Vec<Option<Tile>>>,
     path: Vec<Direction>,
 }
@@ -53,22 +56,120 @@ impl Notes {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Position {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Position {
     row: usize,
     column: usize,
     facing: usize,
 }
 
+impl Position {
+    /// One step in the direction it's facing, whether or not that's still on the map.
+    fn ahead(self) -> Self {
+        match self.facing {
+            // right (column +)
+            0 => Position {
+                column: self.column + 1,
+                ..self
+            },
+            // down (row +)
+            1 => Position {
+                row: self.row + 1,
+                ..self
+            },
+            // left (column -)
+            2 => Position {
+                column: self.column - 1,
+                ..self
+            },
+            // up (row -)
+            3 => Position {
+                row: self.row - 1,
+                ..self
+            },
+            _ => self,
+        }
+    }
+}
+
+/// How stepping off the edge of the map brings you back onto it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wrapping {
+    /// Round to the far end of the row or column.
+    Flat,
+    /// Onto the face that the edge joins when the map is folded into a cube.
+    Cube,
+}
+
+/// Where each step off the edge of the map lands.
+type Warps = HashMap<Position, Position>;
+
+fn step(warps: &Warps, position: Position) -> Position {
+    warps
+        .get(&position)
+        .copied()
+        .unwrap_or_else(|| position.ahead())
+}
+
+/// Which way a face of the cube points, and which ways its rows and columns run, with the
+/// cube centred on the origin.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    normal: Vec3<i64>,
+    right: Vec3<i64>,
+    down: Vec3<i64>,
+}
+
+impl Face {
+    fn towards(&self, facing: usize) -> Vec3<i64> {
+        [self.right, self.down, -self.right, -self.down][facing]
+    }
+
+    /// The face beyond the edge in the direction `facing`, once the net is folded there.
+    fn fold(&self, facing: usize) -> Face {
+        let Face {
+            normal,
+            right,
+            down,
+        } = *self;
+        match facing {
+            0 => Face {
+                normal: right,
+                right: -normal,
+                down,
+            },
+            1 => Face {
+                normal: down,
+                right,
+                down: -normal,
+            },
+            2 => Face {
+                normal: -right,
+                right: normal,
+                down,
+            },
+            _ => Face {
+                normal: -down,
+                right,
+                down: normal,
+            },
+        }
+    }
+}
+
+fn dot(a: Vec3<i64>, b: Vec3<i64>) -> i64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
 struct Simulation<'a> {
     notes: &'a Notes,
     path_iter: slice::Iter<'a, Direction>,
     position: Position,
-    warping_rules: &'a fn(Position) -> Option<Position>,
+    warps: &'a Warps,
 }
 
 impl<'a> Simulation<'a> {
-    fn new(notes: &'a Notes, warping_rules: &'a fn(Position) -> Option<Position>) -> Self {
+    fn new(notes: &'a Notes, warps: &'a Warps) -> Self {
         Simulation {
             notes,
             path_iter: notes.path.iter(),
@@ -76,7 +177,7 @@ impl<'a> Simulation<'a> {
                 row: 1,
                 column: notes
                     .map
-                    .get(0)
+                    .first()
                     .unwrap()
                     .iter()
                     .position(|&t| t == Some(Tile::Open))
@@ -84,46 +185,13 @@ impl<'a> Simulation<'a> {
                     + 1,
                 facing: 0,
             },
-            warping_rules,
+            warps,
         }
     }
 
-    fn new_coords(&self, old: Position) -> Position {
-        (self.warping_rules)(old).unwrap_or(match old.facing {
-            // right (column +)
-            0 => Position {
-                column: old.column + 1,
-                ..old
-            },
-            // down (row +)
-            1 => Position {
-                row: old.row + 1,
-                ..old
-            },
-            // left (column -)
-            2 => Position {
-                column: old.column - 1,
-                ..old
-            },
-            // up (row -)
-            3 => Position {
-                row: old.row - 1,
-                ..old
-            },
-            _ => old,
-        })
-    }
-
     fn step_forward(&mut self) -> bool {
-        let position = self.new_coords(self.position);
-        match self
-            .notes
-            .map
-            .get(position.row - 1)
-            .and_then(|row| row.get(position.column - 1))
-            .and_then(Option::as_ref)
-            .unwrap()
-        {
+        let position = step(self.warps, self.position);
+        match self.notes.tile(position).unwrap() {
             Tile::Wall => false,
             Tile::Open => {
                 self.position = position;
@@ -157,413 +225,263 @@ impl<'a> Iterator for Simulation<'a> {
     }
 }
 
-pub fn secret(input: &str, warping_rules: fn(Position) -> Option<Position>) -> Option<usize> {
-    let notes = Notes::parse_nom(input).ok()?.1;
-    let simulation = Simulation::new(&notes, &warping_rules);
-    simulation.last()
+impl Notes {
+    /// The tile at a position, counting rows and columns from 1.
+    fn tile(&self, position: Position) -> Option<Tile> {
+        *self
+            .map
+            .get(position.row.checked_sub(1)?)?
+            .get(position.column.checked_sub(1)?)?
+    }
+
+    /// Every position on the map facing off its edge.
+    fn edges(&self) -> impl Iterator<Item = Position> + '_ {
+        self.map.iter().enumerate().flat_map(move |(r, row)| {
+            row.iter().enumerate().flat_map(move |(c, tile)| {
+                (0..4)
+                    .map(move |facing| Position {
+                        row: r + 1,
+                        column: c + 1,
+                        facing,
+                    })
+                    .filter(move |&p| tile.is_some() && self.tile(p.ahead()).is_none())
+            })
+        })
+    }
+
+    fn flat_warps(&self) -> Warps {
+        self.edges()
+            .map(|from| {
+                let back = Position {
+                    facing: (from.facing + 2) % 4,
+                    ..from
+                };
+                let mut to = back;
+                while self.tile(to.ahead()).is_some() {
+                    to = to.ahead();
+                }
+                (
+                    from,
+                    Position {
+                        facing: from.facing,
+                        ..to
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Folds the map into a cube, face by face from the first, and works out where each step
+    /// off an edge lands on the face folded next to it. Positions on the cube are doubled, so
+    /// the centre of every tile is a whole number.
+    fn cube_warps(&self) -> Result<Warps, Error> {
+        let not_a_net = || Error::no_answer("the map doesn't fold into a cube");
+        let tiles = self.map.iter().flatten().flatten().count();
+        let size = (1..).find(|&n| 6 * n * n >= tiles).unwrap();
+        if 6 * size * size != tiles {
+            return Err(not_a_net());
+        }
+        let corner = |(r, c): (usize, usize)| Position {
+            row: r * size + 1,
+            column: c * size + 1,
+            facing: 0,
+        };
+        let first = (0..self.map.first().map_or(0, Vec::len) / size)
+            .map(|c| (0, c))
+            .find(|&block| self.tile(corner(block)).is_some())
+            .ok_or_else(not_a_net)?;
+        let mut faces = HashMap::from([(
+            first,
+            Face {
+                normal: Vec3::new(0, 0, 1),
+                right: Vec3::new(1, 0, 0),
+                down: Vec3::new(0, 1, 0),
+            },
+        )]);
+        let mut unfolded = vec![first];
+        while let Some(block) = unfolded.pop() {
+            let face = faces[&block];
+            let (r, c) = block;
+            let beyond = [
+                Some((r, c + 1)),
+                Some((r + 1, c)),
+                c.checked_sub(1).map(|c| (r, c)),
+                r.checked_sub(1).map(|r| (r, c)),
+            ];
+            for (facing, next) in beyond.into_iter().enumerate() {
+                if let Some(next) = next.filter(|&n| self.tile(corner(n)).is_some()) {
+                    if let Entry::Vacant(entry) = faces.entry(next) {
+                        entry.insert(face.fold(facing));
+                        unfolded.push(next);
+                    }
+                }
+            }
+        }
+        let normals: HashSet<_> = faces.values().map(|f| f.normal).collect();
+        if faces.len() != 6 || normals.len() != 6 {
+            return Err(not_a_net());
+        }
+
+        let n = size as i64;
+        let mut warps = Warps::new();
+        for from in self.edges() {
+            let (r, c) = ((from.row - 1) / size, (from.column - 1) / size);
+            let face = faces.get(&(r, c)).ok_or_else(not_a_net)?;
+            let (i, j) = ((from.row - 1) % size, (from.column - 1) % size);
+            let centre = face.normal * n
+                + face.right * (2 * j as i64 + 1 - n)
+                + face.down * (2 * i as i64 + 1 - n);
+            let out = face.towards(from.facing);
+            let (&(r, c), onto) = faces.iter().find(|(_, f)| f.normal == out).unwrap();
+            let centre = centre + out - face.normal;
+            let facing = (0..4).find(|&f| onto.towards(f) == -face.normal).unwrap();
+            let j = (dot(centre, onto.right) + n - 1) / 2;
+            let i = (dot(centre, onto.down) + n - 1) / 2;
+            warps.insert(
+                from,
+                Position {
+                    row: r * size + i as usize + 1,
+                    column: c * size + j as usize + 1,
+                    facing,
+                },
+            );
+        }
+        Ok(warps)
+    }
+
+    fn secret(&self, wrapping: Wrapping) -> Result<usize, Error> {
+        let warps = match wrapping {
+            Wrapping::Flat => self.flat_warps(),
+            Wrapping::Cube => self.cube_warps()?,
+        };
+        Simulation::new(self, &warps)
+            .last()
+            .ok_or_else(|| Error::no_answer("the path is empty"))
+    }
+}
+
+impl Solution for Notes {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Notes::parse_nom(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        self.secret(Wrapping::Flat)
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        self.secret(Wrapping::Cube)
+    }
+}
+
+pub fn secret(input: &str, wrapping: Wrapping) -> Option<usize> {
+    Notes::parse(input)
+        .and_then(|notes| notes.secret(wrapping))
+        .ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// The 11 nets of a cube, a `#` for each face.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    /// An empty map of the net with faces `size` wide.
+    fn unfold(net: &str, size: usize) -> Notes {
+        let map = net
+            .lines()
+            .flat_map(|line| {
+                let row: Vec<_> = line
+                    .chars()
+                    .flat_map(|c| [(c == '#').then_some(Tile::Open); 1].repeat(size))
+                    .collect();
+                vec![row; size]
+            })
+            .collect();
+        Notes {
+            map,
+            path: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn folds_every_net() {
+        for net in NETS {
+            let notes = unfold(net, 3);
+            let warps = notes.cube_warps().unwrap();
+            for start in notes.edges() {
+                // going straight on round a cube comes back after four faces
+                let end = (0..12).fold(start, |p, _| step(&warps, p));
+                assert_eq!(end, start, "{net}");
+                // and stepping back over an edge undoes stepping over it
+                let over = step(&warps, start);
+                let back = step(
+                    &warps,
+                    Position {
+                        facing: (over.facing + 2) % 4,
+                        ..over
+                    },
+                );
+                assert_eq!(back.facing, (start.facing + 2) % 4, "{net}");
+                assert_eq!((back.row, back.column), (start.row, start.column), "{net}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_maps_that_are_not_nets() {
+        assert!(unfold("###\n###", 2).cube_warps().is_err());
+        assert!(unfold("#....\n#####", 2).cube_warps().is_err());
+        assert!(matches!(
+            unfold("......\n######", 2).cube_warps(),
+            Err(Error::NoAnswer(_))
+        ));
+    }
+
     #[test]
     fn example_part_one() {
         assert_eq!(
-            secret(include_str!("../example.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 9..=12,
-                    facing: 3,
-                } => Some(Position { row: 12, ..p }),
-                Position {
-                    row: 1..=4,
-                    column: 8,
-                    facing: 2,
-                } => Some(Position { column: 12, ..p }),
-                Position {
-                    row: 1..=4,
-                    column: 12,
-                    facing: 0,
-                } => Some(Position { column: 8, ..p }),
-                Position {
-                    row: 5,
-                    column: 0..=8,
-                    facing: 3,
-                } => Some(Position { row: 8, ..p }),
-                Position {
-                    row: 5..=8,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position { column: 12, ..p }),
-                Position {
-                    row: 5..=8,
-                    column: 12,
-                    facing: 0,
-                } => Some(Position { column: 1, ..p }),
-                Position {
-                    row: 8,
-                    column: 1..=8,
-                    facing: 1,
-                } => Some(Position { row: 5, ..p }),
-
-                _ => None,
-            }),
+            secret(include_str!("../example.txt"), Wrapping::Flat),
             Some(6032)
         );
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(
-            secret(include_str!("../challenge.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 51..=100,
-                    facing: 3,
-                } => Some(Position { row: 150, ..p }),
-                Position {
-                    row: 1,
-                    column: 101..=150,
-                    facing: 3,
-                } => Some(Position { row: 50, ..p }),
-                Position {
-                    row: 51..=100,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position { column: 100, ..p }),
-                Position {
-                    row: 1..=50,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position { column: 150, ..p }),
-                Position {
-                    row: 1..=50,
-                    column: 150,
-                    facing: 0,
-                } => Some(Position { column: 51, ..p }),
-                Position {
-                    row: 50,
-                    column: 101..=150,
-                    facing: 1,
-                } => Some(Position { row: 1, ..p }),
-                Position {
-                    row: 51..=100,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position { column: 51, ..p }),
-                Position {
-                    row: 101,
-                    column: 1..=50,
-                    facing: 3,
-                } => Some(Position { row: 200, ..p }),
-                Position {
-                    row: 101..=150,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position { column: 100, ..p }),
-                Position {
-                    row: 101..=150,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position { column: 1, ..p }),
-                Position {
-                    row: 150,
-                    column: 51..=100,
-                    facing: 1,
-                } => Some(Position { row: 1, ..p }),
-                Position {
-                    row: 151..=200,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position { column: 50, ..p }),
-                Position {
-                    row: 151..=200,
-                    column: 50,
-                    facing: 0,
-                } => Some(Position { column: 1, ..p }),
-                Position {
-                    row: 200,
-                    column: 1..=50,
-                    facing: 1,
-                } => Some(Position { row: 101, ..p }),
-
-                _ => None,
-            }),
-            Some(149138)
-        );
+        let input = aoc_input::challenge(2022, 22);
+        assert_eq!(secret(&input, Wrapping::Flat), Some(149138));
     }
 
     #[test]
     fn example_part_two() {
         assert_eq!(
-            secret(include_str!("../example.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 9..=12,
-                    facing: 3,
-                } => Some(Position {
-                    row: 5,
-                    facing: 1,
-                    column: 12 - p.column + 1
-                }),
-                Position {
-                    row: 1..=4,
-                    column: 9,
-                    facing: 2,
-                } => Some(Position {
-                    row: 5,
-                    facing: 1,
-                    column: p.row + 4
-                }),
-                Position {
-                    row: 1..=4,
-                    column: 12,
-                    facing: 0,
-                } => Some(Position {
-                    facing: 2,
-                    column: 16,
-                    row: 4 - p.row + 8 + 1
-                }),
-                Position {
-                    row: 5,
-                    column: 1..=4,
-                    facing: 3,
-                } => Some(Position {
-                    facing: 1,
-                    row: 1,
-                    column: 4 - p.column + 8 + 1
-                }),
-                Position {
-                    row: 5,
-                    column: 5..=8,
-                    facing: 3,
-                } => Some(Position {
-                    facing: 0,
-                    column: 9,
-                    row: p.column - 4
-                }),
-                Position {
-                    row: 5..=8,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position {
-                    facing: 3,
-                    row: 12,
-                    column: 8 - p.row + 13
-                }),
-                Position {
-                    row: 5..=8,
-                    column: 12,
-                    facing: 0,
-                } => Some(Position {
-                    facing: 1,
-                    row: 9,
-                    column: 8 - p.row + 13
-                }),
-                Position {
-                    row: 8,
-                    column: 1..=4,
-                    facing: 1,
-                } => Some(Position {
-                    facing: 3,
-                    row: 12,
-                    column: 4 - p.column + 9
-                }),
-                Position {
-                    row: 8,
-                    column: 5..=9,
-                    facing: 1,
-                } => Some(Position {
-                    facing: 2,
-                    column: 9,
-                    row: 8 - p.column + 9,
-                }),
-                Position {
-                    row: 9,
-                    column: 13..=16,
-                    facing: 3,
-                } => Some(Position {
-                    facing: 0,
-                    column: 12,
-                    row: 16 - p.column + 5,
-                }),
-                Position {
-                    row: 9..=12,
-                    column: 9,
-                    facing: 2,
-                } => Some(Position {
-                    row: 8,
-                    column: 12 - p.row + 5,
-                    facing: 3
-                }),
-                Position {
-                    row: 9..=12,
-                    column: 16,
-                    facing: 0,
-                } => Some(Position {
-                    row: 12 - p.row + 1,
-                    column: 12,
-                    facing: 2
-                }),
-                Position {
-                    row: 12,
-                    column: 9..=12,
-                    facing: 1,
-                } => Some(Position {
-                    row: 8,
-                    column: 12 - p.column + 1,
-                    facing: 3
-                }),
-                Position {
-                    row: 12,
-                    column: 13..=16,
-                    facing: 1,
-                } => Some(Position {
-                    row: 16 - p.column + 5,
-                    column: 1,
-                    facing: 0
-                }),
-                _ => None,
-            }),
+            secret(include_str!("../example.txt"), Wrapping::Cube),
             Some(5031)
         );
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(
-            secret(include_str!("../challenge.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 51..=100,
-                    facing: 3,
-                } => Some(Position {
-                    row: p.column - 51 + 151,
-                    column: 1,
-                    facing: 0
-                }),
-                Position {
-                    row: 1,
-                    column: 101..=150,
-                    facing: 3,
-                } => Some(Position {
-                    row: 200,
-                    column: p.column - 100,
-                    facing: 3
-                }),
-                Position {
-                    row: 1..=50,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position {
-                    row: 50 - p.row + 101,
-                    column: 1,
-                    facing: 0
-                }),
-                Position {
-                    row: 1..=50,
-                    column: 150,
-                    facing: 0,
-                } => Some(Position {
-                    row: 50 - p.row + 101,
-                    column: 100,
-                    facing: 2
-                }),
-                Position {
-                    row: 50,
-                    column: 101..=150,
-                    facing: 1,
-                } => Some(Position {
-                    row: p.column - 50,
-                    column: 100,
-                    facing: 2
-                }),
-                Position {
-                    row: 51..=100,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position {
-                    row: 101,
-                    column: p.row - 50,
-                    facing: 1
-                }),
-                Position {
-                    row: 51..=100,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position {
-                    row: 50,
-                    column: p.row + 50,
-                    facing: 3
-                }),
-                Position {
-                    row: 101,
-                    column: 1..=50,
-                    facing: 3,
-                } => Some(Position {
-                    row: p.column + 50,
-                    column: 51,
-                    facing: 0
-                }),
-                Position {
-                    row: 101..=150,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position {
-                    row: 150 - p.row + 1,
-                    column: 51,
-                    facing: 0
-                }),
-                Position {
-                    row: 101..=150,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position {
-                    row: 150 - p.row + 1,
-                    column: 150,
-                    facing: 2
-                }),
-                Position {
-                    row: 150,
-                    column: 51..=100,
-                    facing: 1,
-                } => Some(Position {
-                    row: 100 + p.column,
-                    column: 50,
-                    facing: 2
-                }),
-                Position {
-                    row: 151..=200,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position {
-                    row: 1,
-                    column: p.row - 100,
-                    facing: 1
-                }),
-                Position {
-                    row: 151..=200,
-                    column: 50,
-                    facing: 0,
-                } => Some(Position {
-                    row: 150,
-                    column: p.row - 100,
-                    facing: 3
-                }),
-                Position {
-                    row: 200,
-                    column: 1..=50,
-                    facing: 1,
-                } => Some(Position {
-                    row: 1,
-                    column: p.column + 100,
-                    facing: 1
-                }),
-
-                _ => None,
-            }),
-            Some(153203)
-        );
+        let input = aoc_input::challenge(2022, 22);
+        assert_eq!(secret(&input, Wrapping::Cube), Some(153203));
     }
 }
@@ -1,152 +1,97 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    iter::zip,
-};
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-struct Position {
-    x: i16,
-    y: i16,
-}
+use aoc_automaton::{Automaton, Board, Sparse};
+use aoc_core::{Error, Solution};
+use aoc_geometry::{Direction, Vec2};
 
-impl Position {
-    fn proposed_move(&self, grove: &Grove) -> Position {
-        let mut free = [[true; 3]; 3];
-        for (y, row) in zip(-1.., free.iter_mut()) {
-            for (x, tile) in zip(-1.., row.iter_mut()) {
-                if x != 0 || y != 0 {
-                    *tile = !grove.elves.contains(&Position {
-                        x: self.x + x,
-                        y: self.y + y,
-                    })
-                }
-            }
-        }
-        if free.iter().all(|row| row.iter().all(|&tile| tile)) {
-            return *self;
-        }
-        for i in 0..4 {
-            match (grove.cycle + i) % 4 {
-                0 => {
-                    // North
-                    if free[0][0] && free[0][1] && free[0][2] {
-                        return Position {
-                            x: self.x,
-                            y: self.y - 1,
-                        };
-                    }
-                }
-                1 => {
-                    // South
-                    if free[2][0] && free[2][1] && free[2][2] {
-                        return Position {
-                            x: self.x,
-                            y: self.y + 1,
-                        };
-                    }
-                }
-                2 => {
-                    // West
-                    if free[0][0] && free[1][0] && free[2][0] {
-                        return Position {
-                            x: self.x - 1,
-                            y: self.y,
-                        };
-                    }
-                }
-                3 => {
-                    // East
-                    if free[0][2] && free[1][2] && free[2][2] {
-                        return Position {
-                            x: self.x + 1,
-                            y: self.y,
-                        };
-                    }
-                }
-                _ => {}
-            }
-        }
-        *self
-    }
-}
+type Position = Vec2<i16>;
+
+/// The order the elves consider moving in on the first round; each round starts one later.
+const PROPOSALS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
 
-#[derive(Debug, Default)]
-struct Grove {
-    elves: BTreeSet<Position>,
-    cycle: u8,
+#[derive(Debug, Clone)]
+pub struct Grove {
+    elves: Automaton<Position>,
 }
 
 impl Grove {
     fn new(input: &str) -> Self {
-        let mut x = 0;
-        let mut y = 1;
-        let mut grove = Self::default();
-        for c in input.chars() {
-            x += 1;
-            match c {
-                '#' => {
-                    grove.elves.insert(Position { x, y });
-                }
-                '\n' => {
-                    y += 1;
-                    x = 0;
-                }
-                _ => {}
-            }
+        Self {
+            elves: Automaton::new(Sparse::from_text(input)),
+        }
+    }
+
+    fn proposed_move(elves: &Sparse<Position>, elf: Position, cycle: usize) -> Position {
+        let free = |p: Position| !elves.is_live(p);
+        if elf.neighbours_8().into_iter().all(free) {
+            return elf;
         }
-        grove
+        (0..4)
+            .map(|i| PROPOSALS[(cycle + i) % 4])
+            .find(|direction| {
+                let ahead = elf + direction.offset();
+                [
+                    ahead,
+                    ahead + direction.turn_left().offset(),
+                    ahead + direction.turn_right().offset(),
+                ]
+                .into_iter()
+                .all(free)
+            })
+            .map_or(elf, |direction| elf + direction.offset())
     }
 
     fn empty_tiles(&self) -> usize {
-        let min_x = self.elves.iter().map(|e| e.x).min().unwrap_or(0);
-        let min_y = self.elves.iter().map(|e| e.y).min().unwrap_or(0);
-        let max_x = self.elves.iter().map(|e| e.x).max().unwrap_or(0);
-        let max_y = self.elves.iter().map(|e| e.y).max().unwrap_or(0);
-        (min_x..=max_x).len() * (min_y..=max_y).len() - self.elves.len()
+        let elves = self.elves.board();
+        elves.bounds().map_or(0, |(min, max)| {
+            (min.x..=max.x).len() * (min.y..=max.y).len() - elves.population()
+        })
     }
 
+    /// Moves every elf that can, returning whether any did.
     fn follow_round(&mut self) -> bool {
-        let mut changes = false;
-        let mut proposed_position_counts = BTreeMap::<Position, usize>::new();
-        let mut proposed_moves = BTreeMap::<Position, Position>::new();
-        for &elf in self.elves.iter() {
-            let proposed_position = elf.proposed_move(self);
-            *proposed_position_counts
-                .entry(proposed_position)
-                .or_default() += 1;
-            proposed_moves.insert(elf, proposed_position);
+        let cycle = (self.elves.generation() % 4) as usize;
+        self.elves
+            .step_moves(|elf, elves| Self::proposed_move(elves, elf, cycle))
+    }
+}
+
+impl Solution for Grove {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Grove::new(input))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let mut grove = self.clone();
+        for _ in 0..10 {
+            grove.follow_round();
         }
-        self.elves = Default::default();
-        for (&original, &proposed) in proposed_moves.iter() {
-            self.elves.insert(
-                if proposed_position_counts.get(&proposed).cloned() == Some(1) {
-                    changes = changes || proposed != original;
-                    proposed
-                } else {
-                    original
-                },
-            );
+        Ok(grove.empty_tiles())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let mut rounds = 1;
+        let mut grove = self.clone();
+        while grove.follow_round() {
+            rounds += 1
         }
-        self.cycle = (self.cycle + 1) % 4;
-        changes
+        Ok(rounds)
     }
 }
 
 pub fn part_one(input: &str) -> usize {
-    let mut grove = Grove::new(input);
-    for _ in 0..10 {
-        grove.follow_round();
-    }
-    grove.empty_tiles()
+    aoc_core::part1::<Grove>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    let mut rounds = 1;
-    let mut grove = Grove::new(input);
-    while grove.follow_round() {
-        rounds += 1
-    }
-    rounds
+    aoc_core::part2::<Grove>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -159,8 +104,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 4068);
+        let input = aoc_input::challenge(2022, 23);
+        assert_eq!(part_one(&input), 4068);
     }
 
     #[test]
@@ -169,7 +116,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 968);
+        let input = aoc_input::challenge(2022, 23);
+        assert_eq!(part_two(&input), 968);
     }
 }
@@ -1,6 +1,11 @@
 use std::{collections::BTreeSet, mem::take};
 
-struct Valley {
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec2;
+
+type Position = Vec2<usize>;
+
+pub struct Valley {
     width: usize,
     height: usize,
     /// blizzards moving up. A vec of columns
@@ -14,9 +19,38 @@ struct Valley {
 }
 
 impl Valley {
-    fn new(input: &str) -> Self {
-        let width = input.lines().next().unwrap().chars().count();
-        let height = input.lines().filter(|line| !line.is_empty()).count();
+    fn new(input: &str) -> Result<Self, Error> {
+        let rows: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+        let width = rows
+            .first()
+            .ok_or_else(|| ParseError::at_end(input, "a row of the valley"))?
+            .chars()
+            .count();
+        let height = rows.len();
+        if width < 3 {
+            return Err(Error::parse(input, rows[0], "a row at least 3 wide"));
+        }
+        if height < 3 {
+            return Err(ParseError::at_end(input, "at least 3 rows").into());
+        }
+        for (y, &row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(Error::parse(input, row, format!("a row {width} wide")));
+            }
+            let wall_row = y == 0 || y + 1 == height;
+            for (x, (at, c)) in row.char_indices().enumerate() {
+                let (valid, expected) = if wall_row {
+                    (c == '#' || c == '.', "a wall or a gap in it")
+                } else if x == 0 || x + 1 == width {
+                    (c == '#', "a wall")
+                } else {
+                    (".^>v<".contains(c), "open ground or a blizzard")
+                };
+                if !valid {
+                    return Err(Error::parse(input, &row[at..], expected));
+                }
+            }
+        }
 
         let mut valley = Self {
             width,
@@ -26,7 +60,7 @@ impl Valley {
             down_blizzards: vec![vec![false; height]; width],
             left_blizzards: vec![vec![false; width]; height],
         };
-        for (y, row) in input.lines().enumerate() {
+        for (y, row) in rows.iter().enumerate() {
             for (x, c) in row.chars().enumerate() {
                 match c {
                     '^' => valley.up_blizzards[x][y] = true,
@@ -37,7 +71,7 @@ impl Valley {
                 }
             }
         }
-        valley
+        Ok(valley)
     }
 
     fn is_free(&self, x: usize, y: usize, time: usize) -> bool {
@@ -66,37 +100,37 @@ impl Valley {
 
     fn journey_time(
         &self,
-        start: (usize, usize),
-        goal: (usize, usize),
+        start: Position,
+        goal: Position,
         start_time: usize,
-    ) -> usize {
+    ) -> Result<usize, Error> {
         let mut time = start_time;
-        let mut possible_tiles = BTreeSet::<(usize, usize)>::new();
+        let mut possible_tiles = BTreeSet::<Position>::new();
         possible_tiles.insert(start);
         loop {
             time += 1;
             if possible_tiles.is_empty() {
-                panic!("Ran out of moves");
+                return Err(Error::no_answer("Ran out of moves"));
             }
             if time > 1000 {
-                panic!("over 1000 cycles");
+                return Err(Error::no_answer("over 1000 cycles"));
             }
 
-            for &(x, y) in take(&mut possible_tiles).iter() {
+            for &Position { x, y } in take(&mut possible_tiles).iter() {
                 if y > 0 && self.is_free(x, y - 1, time) {
-                    possible_tiles.insert((x, y - 1));
+                    possible_tiles.insert(Position::new(x, y - 1));
                 }
                 if self.is_free(x + 1, y, time) {
-                    possible_tiles.insert((x + 1, y));
+                    possible_tiles.insert(Position::new(x + 1, y));
                 }
                 if y + 1 < self.height && self.is_free(x, y + 1, time) {
-                    possible_tiles.insert((x, y + 1));
+                    possible_tiles.insert(Position::new(x, y + 1));
                 }
                 if x > 0 && self.is_free(x - 1, y, time) {
-                    possible_tiles.insert((x - 1, y));
+                    possible_tiles.insert(Position::new(x - 1, y));
                 }
                 if self.is_free(x, y, time) {
-                    possible_tiles.insert((x, y));
+                    possible_tiles.insert(Position::new(x, y));
                 }
             }
 
@@ -106,7 +140,10 @@ impl Valley {
                     for x in 0..self.width {
                         print!(
                             "{}",
-                            match (self.is_free(x, y, time), possible_tiles.contains(&(x, y))) {
+                            match (
+                                self.is_free(x, y, time),
+                                possible_tiles.contains(&Position::new(x, y))
+                            ) {
                                 (true, true) => 'E',
                                 (true, false) => ' ',
                                 (false, true) => panic!(),
@@ -119,26 +156,46 @@ impl Valley {
                 println!();
             }
             if possible_tiles.contains(&goal) {
-                return time;
+                return Ok(time);
             }
         }
     }
 }
 
+impl Solution for Valley {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Valley::new(input)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        self.journey_time(
+            Position::new(1, 0),
+            Position::new(self.width - 2, self.height - 1),
+            0,
+        )
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let start = Position::new(1, 0);
+        let goal = Position::new(self.width - 2, self.height - 1);
+
+        let there = self.journey_time(start, goal, 0)?;
+        let back = self.journey_time(goal, start, there)?;
+
+        self.journey_time(start, goal, back)
+    }
+}
+
 pub fn part_one(input: &str) -> usize {
-    let valley = Valley::new(input);
-    valley.journey_time((1, 0), (valley.width - 2, valley.height - 1), 0)
+    aoc_core::part1::<Valley>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> usize {
-    let valley = Valley::new(input);
-    let start = (1, 0);
-    let goal = (valley.width - 2, valley.height - 1);
-
-    let there = valley.journey_time(start, goal, 0);
-    let back = valley.journey_time(goal, start, there);
-    let again = valley.journey_time(start, goal, back);
-    again
+    aoc_core::part2::<Valley>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -151,8 +208,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 277);
+        let input = aoc_input::challenge(2022, 24);
+        assert_eq!(part_one(&input), 277);
     }
 
     #[test]
@@ -161,7 +220,24 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 877);
+        let input = aoc_input::challenge(2022, 24);
+        assert_eq!(part_two(&input), 877);
+    }
+
+    #[test]
+    fn rejects_valleys_without_walls() {
+        let error = |input| match Valley::parse(input) {
+            Err(Error::Parse(e)) => (e.line, e.column),
+            _ => panic!("{input:?} should not parse"),
+        };
+        assert_eq!(error(""), (1, 1));
+        assert_eq!(error("#.\n#.\n#.\n"), (1, 1));
+        assert_eq!(error("#.###\n###.#\n"), (3, 1));
+        assert_eq!(error("#.###\n#...#\n#..\n###.#\n"), (3, 1));
+        assert_eq!(error("#.###\n..>.#\n###.#\n"), (2, 1));
+        assert_eq!(error("#.###\n#.x.#\n###.#\n"), (2, 3));
+        assert_eq!(error("#>###\n#...#\n###.#\n"), (1, 2));
     }
 }
@@ -1,3 +1,7 @@
+use std::convert::Infallible;
+
+use aoc_core::{Error, Solution};
+
 fn snafu_to_num(snafu: &str) -> i64 {
     let mut num = 0;
     for s in snafu.chars() {
@@ -41,14 +45,32 @@ fn num_to_snafu(mut num: i64) -> String {
     }
 }
 
-fn parse_list(input: &str) -> Vec<i64> {
-    input.lines().map(snafu_to_num).collect()
+pub struct FuelRequirements {
+    numbers: Vec<i64>,
 }
 
-pub fn part_one(input: &str) -> String {
-    let sum = parse_list(input).iter().sum::<i64>();
+impl Solution for FuelRequirements {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = Infallible;
 
-    num_to_snafu(sum)
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            numbers: input.lines().map(snafu_to_num).collect(),
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        Ok(num_to_snafu(self.numbers.iter().sum()))
+    }
+
+    fn part2(&self, _: &()) -> Result<Infallible, Error> {
+        Err(Error::Unsolved)
+    }
+}
+
+pub fn part_one(input: &str) -> String {
+    aoc_core::part1::<FuelRequirements>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -61,10 +83,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_one() {
-        assert_eq!(
-            part_one(include_str!("../challenge.txt")),
-            "2-20=01--0=0=0=2-120"
-        );
+        let input = aoc_input::challenge(2022, 25);
+        assert_eq!(part_one(&input), "2-20=01--0=0=0=2-120");
     }
 }
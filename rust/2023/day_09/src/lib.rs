@@ -1,9 +1,7 @@
-fn parse(input: &str) -> impl Iterator<Item = Vec<i64>> + '_ {
-    input.trim().lines().map(|line| {
-        line.split_whitespace()
-            .filter_map(|v| v.parse().ok())
-            .collect()
-    })
+use aoc_core::{Error, Solution};
+
+pub struct Report {
+    histories: Vec<Vec<i64>>,
 }
 
 fn next_sequence_value(input: &[i64]) -> i64 {
@@ -32,12 +30,39 @@ fn prev_sequence_value(input: &[i64]) -> i64 {
     -prev_sequence_value(&differences) + input.first().unwrap()
 }
 
+impl Solution for Report {
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let histories = input
+            .trim()
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|v| v.parse().map_err(|_| Error::parse(input, v, "a number")))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { histories })
+    }
+
+    fn part1(&self, _: &()) -> Result<i64, Error> {
+        Ok(self.histories.iter().map(|l| next_sequence_value(l)).sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<i64, Error> {
+        Ok(self.histories.iter().map(|l| prev_sequence_value(l)).sum())
+    }
+}
+
 pub fn part_one(input: &str) -> i64 {
-    parse(input).map(|l| next_sequence_value(&l)).sum()
+    aoc_core::part1::<Report>(input).unwrap()
 }
 
 pub fn part_two(input: &str) -> i64 {
-    parse(input).map(|l| prev_sequence_value(&l)).sum()
+    aoc_core::part2::<Report>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -50,9 +75,11 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_one_challenge() {
-        assert_eq!(part_one(include_str!("../input.txt")), 1898776583)
+        let input = aoc_input::challenge(2023, 9);
+        assert_eq!(part_one(&input), 1898776583)
     }
 
     #[test]
@@ -61,8 +88,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_two_challenge() {
-        assert_eq!(part_two(include_str!("../input.txt")), 1100)
+        let input = aoc_input::challenge(2023, 9);
+        assert_eq!(part_two(&input), 1100)
     }
 }
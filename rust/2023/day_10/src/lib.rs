@@ -1,12 +1,7 @@
 use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+use aoc_core::{Error, ParseError, Solution};
+use aoc_grid::{Coord, Direction, Grid};
 
 #[derive(Clone, Copy, PartialEq)]
 pub struct Cell(u8);
@@ -25,56 +20,27 @@ impl Cell {
     }
 }
 
-pub struct Grid(Vec<Vec<Cell>>);
-
-impl Grid {
-    pub fn new(input: &str) -> Self {
-        Self(
-            input
-                .trim()
-                .lines()
-                .map(|l| l.trim().bytes().map(Cell).collect())
-                .collect(),
-        )
-    }
-
-    fn find_start(&self) -> (usize, usize) {
-        for (y, line) in self.0.iter().enumerate() {
-            for (x, cell) in line.iter().enumerate() {
-                if cell.0 == b'S' {
-                    return (x, y);
-                }
-            }
-        }
-        panic!("No start token");
-    }
+#[derive(Clone)]
+pub struct PipeMaze(Grid<Cell>);
 
-    fn normalise_start(&mut self) -> (usize, usize) {
-        let (start_x, start_y) = self.find_start();
-        let connects_north = start_y
-            .checked_sub(1)
-            .and_then(|y| self.0.get(y)?.get(start_x))
-            .map(|c| c.goes_direction(Direction::South))
-            .unwrap_or(false);
-        let connects_east = self
-            .0
-            .get(start_y)
-            .and_then(|line| line.get(start_x + 1))
-            .map(|c| c.goes_direction(Direction::West))
-            .unwrap_or(false);
-        let connects_south = self
+impl PipeMaze {
+    fn normalise_start(&mut self) -> Coord {
+        let start = self
             .0
-            .get(start_y + 1)
-            .and_then(|line| line.get(start_x))
-            .map(|c| c.goes_direction(Direction::North))
-            .unwrap_or(false);
-        let connects_west = start_x
-            .checked_sub(1)
-            .and_then(|x| self.0.get(start_y)?.get(x))
-            .map(|c| c.goes_direction(Direction::East))
-            .unwrap_or(false);
-        self.0[start_y][start_x] = Cell(
-            match (connects_north, connects_east, connects_south, connects_west) {
+            .find(|cell| cell.0 == b'S')
+            .expect("parse checks for a start tile");
+        let connects = |direction: Direction| {
+            self.0
+                .get(start + direction.offset())
+                .is_some_and(|c| c.goes_direction(direction.opposite()))
+        };
+        self.0[start] = Cell(
+            match (
+                connects(Direction::North),
+                connects(Direction::East),
+                connects(Direction::South),
+                connects(Direction::West),
+            ) {
                 (true, false, true, false) => b'|',
                 (false, true, false, true) => b'-',
                 (true, true, false, false) => b'L',
@@ -84,31 +50,25 @@ impl Grid {
                 _ => b'.',
             },
         );
-        (start_x, start_y)
+        start
     }
 
-    fn extract_loop(mut self) -> BTreeMap<(usize, usize), Cell> {
+    fn extract_loop(mut self) -> BTreeMap<Coord, Cell> {
         let start = self.normalise_start();
 
         let mut candidates = vec![start];
         let mut visited = candidates
             .iter()
-            .cloned()
-            .map(|(x, y)| ((x, y), self.0[y][x]))
-            .collect::<BTreeMap<(usize, usize), Cell>>();
+            .map(|&position| (position, self.0[position]))
+            .collect::<BTreeMap<Coord, Cell>>();
 
         while !candidates.is_empty() {
             let mut old_candidates = std::mem::take(&mut candidates);
             while let Some(old_candidate) = old_candidates.pop() {
-                for direction in [
-                    Direction::North,
-                    Direction::East,
-                    Direction::South,
-                    Direction::West,
-                ] {
+                for direction in Direction::ALL {
                     if let Some(new_candidate) = self.make_move(direction, old_candidate) {
                         if visited
-                            .insert(new_candidate, self.0[new_candidate.1][new_candidate.0])
+                            .insert(new_candidate, self.0[new_candidate])
                             .is_none()
                         {
                             candidates.push(new_candidate);
@@ -120,84 +80,85 @@ impl Grid {
         visited
     }
 
-    pub fn make_move(
-        &self,
-        direction: Direction,
-        (initial_x, initial_y): (usize, usize),
-    ) -> Option<(usize, usize)> {
-        let old_pipe = self.0.get(initial_y)?.get(initial_x)?;
-        let mut new_x = initial_x;
-        let mut new_y = initial_y;
-        match direction {
-            Direction::North => {
-                new_y = new_y.checked_sub(1)?;
-            }
-            Direction::East => {
-                new_x += 1;
-            }
-            Direction::South => {
-                new_y += 1;
-            }
-            Direction::West => {
-                new_x = new_x.checked_sub(1)?;
-            }
-        }
-
-        if old_pipe.goes_direction(direction) {
-            Some((new_x, new_y))
+    pub fn make_move(&self, direction: Direction, position: Coord) -> Option<Coord> {
+        let old_pipe = self.0.get(position)?;
+        let new_position = position + direction.offset();
+        if old_pipe.goes_direction(direction) && self.0.contains(new_position) {
+            Some(new_position)
         } else {
             None
         }
     }
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let grid = Grid::new(input);
+impl Solution for PipeMaze {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let grid = Grid::parse(input, |c| {
+            u8::try_from(c).map(Cell).map_err(|_| "an ASCII character")
+        })?;
+        if grid.find(|cell| cell.0 == b'S').is_none() {
+            return Err(ParseError::at_end(input, "a start tile S").into());
+        }
+        Ok(PipeMaze(grid))
+    }
 
-    let pipe_loop = grid.extract_loop();
-    (pipe_loop.len() / 2).try_into().unwrap()
-}
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        let pipe_loop = self.clone().extract_loop();
+        Ok((pipe_loop.len() / 2).try_into().unwrap())
+    }
 
-pub fn part_two(input: &str) -> u64 {
-    let grid = Grid::new(input);
-    let width = grid.0.get(0).map(|l| l.len()).unwrap_or(0);
-    let height = grid.0.len();
-
-    let pipe_loop = grid.extract_loop();
-    let mut count = 0;
-    for y in 0..height {
-        let mut is_inside = false;
-        let mut horizontal_start_from_north = false;
-        for x in 0..width {
-            let pipe = pipe_loop.get(&(x, y));
-            if pipe.is_none() && is_inside {
-                count += 1;
-                print!("x");
-            } else {
-                print!(" ");
-            }
-            match pipe {
-                Some(&Cell(b'|')) => {
-                    is_inside = !is_inside;
-                }
-                Some(&Cell(b'F')) => {
-                    horizontal_start_from_north = false;
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        let width = self.0.width() as isize;
+        let height = self.0.height() as isize;
+
+        let pipe_loop = self.clone().extract_loop();
+        let mut count = 0;
+        for y in 0..height {
+            let mut is_inside = false;
+            let mut horizontal_start_from_north = false;
+            for x in 0..width {
+                let pipe = pipe_loop.get(&Coord::new(x, y));
+                if pipe.is_none() && is_inside {
+                    count += 1;
+                    print!("x");
+                } else {
+                    print!(" ");
                 }
-                Some(&Cell(b'L')) => {
-                    horizontal_start_from_north = true;
-                }
-                Some(&Cell(b'7')) => {
-                    is_inside = is_inside != horizontal_start_from_north;
-                }
-                Some(&Cell(b'J')) => {
-                    is_inside = is_inside == horizontal_start_from_north;
+                match pipe {
+                    Some(&Cell(b'|')) => {
+                        is_inside = !is_inside;
+                    }
+                    Some(&Cell(b'F')) => {
+                        horizontal_start_from_north = false;
+                    }
+                    Some(&Cell(b'L')) => {
+                        horizontal_start_from_north = true;
+                    }
+                    Some(&Cell(b'7')) => {
+                        is_inside = is_inside != horizontal_start_from_north;
+                    }
+                    Some(&Cell(b'J')) => {
+                        is_inside = is_inside == horizontal_start_from_north;
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
+            println!();
         }
-        println!();
+        Ok(count)
     }
-    count
+}
+
+pub fn part_one(input: &str) -> u64 {
+    aoc_core::part1::<PipeMaze>(input).unwrap()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    aoc_core::part2::<PipeMaze>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -210,9 +171,11 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_one_challenge() {
-        assert_eq!(part_one(include_str!("../input.txt")), 6778)
+        let input = aoc_input::challenge(2023, 10);
+        assert_eq!(part_one(&input), 6778)
     }
 
     #[test]
@@ -221,8 +184,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_two_challenge() {
-        assert_eq!(part_two(include_str!("../input.txt")), 433)
+        let input = aoc_input::challenge(2023, 10);
+        assert_eq!(part_two(&input), 433)
     }
 }
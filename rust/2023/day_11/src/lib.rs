@@ -1,7 +1,10 @@
 use std::collections::HashSet;
 
-struct Image {
-    galaxies: HashSet<(i64, i64)>,
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
+
+pub struct Image {
+    galaxies: HashSet<Vec2<i64>>,
     non_empty_horizontal_space: HashSet<i64>,
     non_empty_vertical_space: HashSet<i64>,
 }
@@ -14,7 +17,7 @@ impl Image {
         for (line, y) in input.trim().lines().zip(0..) {
             for (c, x) in line.chars().zip(0..) {
                 if c == '#' {
-                    galaxies.insert((x, y));
+                    galaxies.insert(Vec2::new(x, y));
                     non_empty_horizontal_space.insert(y);
                     non_empty_vertical_space.insert(x);
                 }
@@ -27,31 +30,71 @@ impl Image {
         }
     }
 }
-pub fn solve(input: &str, empty_space_size: i64) -> i64 {
-    let image = Image::new(input);
-    let mut sum = 0;
-    for galaxy_a in image.galaxies.iter() {
-        for galaxy_b in image.galaxies.iter() {
-            if galaxy_a > galaxy_b {
-                for x in i64::min(galaxy_a.0, galaxy_b.0)..i64::max(galaxy_a.0, galaxy_b.0) {
-                    if image.non_empty_vertical_space.contains(&x) {
-                        sum += 1;
-                    } else {
-                        sum += empty_space_size;
+
+impl Image {
+    fn distance_sum(&self, empty_space_size: i64) -> i64 {
+        let image = self;
+        let mut sum = 0;
+        for galaxy_a in image.galaxies.iter() {
+            for galaxy_b in image.galaxies.iter() {
+                if galaxy_a > galaxy_b {
+                    for x in i64::min(galaxy_a.x, galaxy_b.x)..i64::max(galaxy_a.x, galaxy_b.x) {
+                        if image.non_empty_vertical_space.contains(&x) {
+                            sum += 1;
+                        } else {
+                            sum += empty_space_size;
+                        }
                     }
-                }
 
-                for y in i64::min(galaxy_a.1, galaxy_b.1)..i64::max(galaxy_a.1, galaxy_b.1) {
-                    if image.non_empty_horizontal_space.contains(&y) {
-                        sum += 1;
-                    } else {
-                        sum += empty_space_size;
+                    for y in i64::min(galaxy_a.y, galaxy_b.y)..i64::max(galaxy_a.y, galaxy_b.y) {
+                        if image.non_empty_horizontal_space.contains(&y) {
+                            sum += 1;
+                        } else {
+                            sum += empty_space_size;
+                        }
                     }
                 }
             }
         }
+        sum
+    }
+}
+
+/// How many rows or columns each empty one is really worth.
+pub struct EmptySpaceSize {
+    pub part_one: i64,
+    pub part_two: i64,
+}
+
+impl Default for EmptySpaceSize {
+    fn default() -> Self {
+        Self {
+            part_one: 2,
+            part_two: 1000000,
+        }
     }
-    sum
+}
+
+impl Solution for Image {
+    type Params = EmptySpaceSize;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Image::new(input))
+    }
+
+    fn part1(&self, params: &EmptySpaceSize) -> Result<i64, Error> {
+        Ok(self.distance_sum(params.part_one))
+    }
+
+    fn part2(&self, params: &EmptySpaceSize) -> Result<i64, Error> {
+        Ok(self.distance_sum(params.part_two))
+    }
+}
+
+pub fn solve(input: &str, empty_space_size: i64) -> i64 {
+    Image::new(input).distance_sum(empty_space_size)
 }
 
 #[cfg(test)]
@@ -64,9 +107,11 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_one_challenge() {
-        assert_eq!(solve(include_str!("../input.txt"), 2), 9214785)
+        let input = aoc_input::challenge(2023, 11);
+        assert_eq!(solve(&input, 2), 9214785)
     }
 
     #[test]
@@ -75,8 +120,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_two_challenge() {
-        assert_eq!(solve(include_str!("../input.txt"), 1000000), 613686987427)
+        let input = aoc_input::challenge(2023, 11);
+        assert_eq!(solve(&input, 1000000), 613686987427)
     }
 }
@@ -1,21 +1,24 @@
 use std::fmt::Write;
 use std::ops::Range;
 
+use aoc_core::{Error, Solution};
+use aoc_geometry::{Vec2, Vec3};
+
 #[derive(Debug)]
 struct Hailstone {
-    loc: [f64; 3],
-    velocity: [f64; 3],
+    loc: Vec3<f64>,
+    velocity: Vec3<f64>,
     two_d_m: f64,
     two_d_c: f64,
 }
 
 impl Hailstone {
-    fn new_from_loc_and_velocity(loc: [f64; 3], velocity: [f64; 3]) -> Self {
+    fn new_from_loc_and_velocity(loc: Vec3<f64>, velocity: Vec3<f64>) -> Self {
         Self {
             loc,
             velocity,
-            two_d_m: velocity[1] / velocity[0],
-            two_d_c: -loc[0] * velocity[1] / velocity[0] + loc[1],
+            two_d_m: velocity.y / velocity.x,
+            two_d_c: -loc.x * velocity.y / velocity.x + loc.y,
         }
     }
 
@@ -23,44 +26,87 @@ impl Hailstone {
         let mut split = input
             .split(|c: char| !c.is_ascii_digit() && c != '-')
             .filter_map(|n| n.parse().ok());
-        let loc = [split.next()?, split.next()?, split.next()?];
-        let velocity = [split.next()?, split.next()?, split.next()?];
+        let loc = Vec3::new(split.next()?, split.next()?, split.next()?);
+        let velocity = Vec3::new(split.next()?, split.next()?, split.next()?);
         Some(Self::new_from_loc_and_velocity(loc, velocity))
     }
 
-    fn two_d_paths_intersect(&self, other: &Self) -> Option<(f64, f64)> {
+    fn two_d_paths_intersect(&self, other: &Self) -> Option<Vec2<f64>> {
         if self.two_d_m == other.two_d_m {
             return None;
         }
         let x = (other.two_d_c - self.two_d_c) / (self.two_d_m - other.two_d_m);
         let y = self.two_d_m * x + self.two_d_c;
-        if (x >= self.loc[0]) != self.velocity[0].is_sign_positive() {
+        if (x >= self.loc.x) != self.velocity.x.is_sign_positive() {
             return None;
         }
-        if (x >= other.loc[0]) != other.velocity[0].is_sign_positive() {
+        if (x >= other.loc.x) != other.velocity.x.is_sign_positive() {
             return None;
         }
-        Some((x, y))
+        Some(Vec2::new(x, y))
     }
 }
 
-fn parse(input: &str) -> Vec<Hailstone> {
-    input
-        .trim()
-        .lines()
-        .map(|line| {
-            Hailstone::new(line).unwrap_or_else(|| panic!("failed to parse hailstorm {:?}", line))
-        })
-        .collect()
+pub struct Hailstorm {
+    hailstones: Vec<Hailstone>,
+}
+
+pub struct Params {
+    /// The test area both the x and y of an intersection need to be in.
+    pub boundary: Range<f64>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            boundary: 200000000000000.0..400000000000000.0,
+        }
+    }
+}
+
+impl Solution for Hailstorm {
+    type Params = Params;
+    type Part1 = u64;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let hailstones = input
+            .trim()
+            .lines()
+            .map(|line| {
+                Hailstone::new(line).ok_or_else(|| {
+                    Error::parse(input, line, "a hailstone like 19, 13, 30 @ -2, 1, -2")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { hailstones })
+    }
+
+    fn part1(&self, params: &Params) -> Result<u64, Error> {
+        Ok(intersections(&self.hailstones, &params.boundary))
+    }
+
+    fn part2(&self, _: &Params) -> Result<String, Error> {
+        Ok(equations(&self.hailstones))
+    }
 }
 
 pub fn part_one(input: &str, boundary: Range<f64>) -> u64 {
-    let hailstones = parse(input);
+    Hailstorm::parse(input)
+        .and_then(|hailstorm| hailstorm.part1(&Params { boundary }))
+        .unwrap()
+}
+
+pub fn part_two(input: &str) -> String {
+    aoc_core::part2::<Hailstorm>(input).unwrap()
+}
+
+fn intersections(hailstones: &[Hailstone], boundary: &Range<f64>) -> u64 {
     let mut count = 0;
     for (i, hailstone_a) in hailstones.iter().enumerate() {
         for hailstone_b in hailstones.iter().skip(i + 1) {
             if let Some(intersect) = hailstone_a.two_d_paths_intersect(hailstone_b) {
-                if boundary.contains(&intersect.0) && boundary.contains(&intersect.1) {
+                if boundary.contains(&intersect.x) && boundary.contains(&intersect.y) {
                     count += 1;
                 }
             }
@@ -69,21 +115,20 @@ pub fn part_one(input: &str, boundary: Range<f64>) -> u64 {
     count
 }
 
-pub fn part_two(input: &str) -> String {
+fn equations(hailstones: &[Hailstone]) -> String {
     let mut output = String::new();
-    let hailstones = parse(input);
     let time_variables = ['t', 'u', 'w'];
 
     for (hailstone, time_variable) in hailstones.iter().zip(time_variables.iter()) {
         writeln!(
             &mut output,
             "(x y z) + {time_variable}(a b c) - ({} {} {}) - {time_variable}({} {} {}) = (0 0 0)",
-            hailstone.loc[0],
-            hailstone.loc[1],
-            hailstone.loc[2],
-            hailstone.velocity[0],
-            hailstone.velocity[1],
-            hailstone.velocity[2]
+            hailstone.loc.x,
+            hailstone.loc.y,
+            hailstone.loc.z,
+            hailstone.velocity.x,
+            hailstone.velocity.y,
+            hailstone.velocity.z
         )
         .unwrap();
     }
@@ -93,12 +138,12 @@ pub fn part_two(input: &str) -> String {
         writeln!(
             &mut output,
             "(x y z) -  ({} {} {}) = {time_variable}({} {} {}) - {time_variable}(a b c)",
-            hailstone.loc[0],
-            hailstone.loc[1],
-            hailstone.loc[2],
-            hailstone.velocity[0],
-            hailstone.velocity[1],
-            hailstone.velocity[2]
+            hailstone.loc.x,
+            hailstone.loc.y,
+            hailstone.loc.z,
+            hailstone.velocity.x,
+            hailstone.velocity.y,
+            hailstone.velocity.z
         )
         .unwrap();
     }
@@ -108,12 +153,12 @@ pub fn part_two(input: &str) -> String {
         writeln!(
             &mut output,
             "(x - {loc_x}, y - {loc_y}, z - {loc_z}) = {time_variable}({vel_x} - a, {vel_y} - b, {vel_z} - c)",
-            loc_x= hailstone.loc[0],
-            loc_y= hailstone.loc[1],
-            loc_z = hailstone.loc[2],
-            vel_x = hailstone.velocity[0],
-            vel_y = hailstone.velocity[1],
-            vel_z = hailstone.velocity[2]
+            loc_x= hailstone.loc.x,
+            loc_y= hailstone.loc.y,
+            loc_z = hailstone.loc.z,
+            vel_x = hailstone.velocity.x,
+            vel_y = hailstone.velocity.y,
+            vel_z = hailstone.velocity.z
         )
         .unwrap();
     }
@@ -121,12 +166,12 @@ pub fn part_two(input: &str) -> String {
     writeln!(&mut output).unwrap();
 
     for hailstone in hailstones.iter().take(3) {
-        let loc_x = hailstone.loc[0];
-        let loc_y = hailstone.loc[1];
-        let loc_z = hailstone.loc[2];
-        let vel_x = hailstone.velocity[0];
-        let vel_y = hailstone.velocity[1];
-        let vel_z = hailstone.velocity[2];
+        let loc_x = hailstone.loc.x;
+        let loc_y = hailstone.loc.y;
+        let loc_z = hailstone.loc.z;
+        let vel_x = hailstone.velocity.x;
+        let vel_y = hailstone.velocity.y;
+        let vel_z = hailstone.velocity.z;
         writeln!(
             &mut output,
             "(y - {loc_y})*({vel_z} - c) - (z - {loc_z})*({vel_y} -b) = 0",
@@ -150,12 +195,12 @@ pub fn part_two(input: &str) -> String {
     writeln!(&mut output, "from sympy.abc import x, y, z, a, b, c").unwrap();
     writeln!(&mut output, "solve([").unwrap();
     for hailstone in hailstones.iter().take(3) {
-        let loc_x = hailstone.loc[0];
-        let loc_y = hailstone.loc[1];
-        let loc_z = hailstone.loc[2];
-        let vel_x = hailstone.velocity[0];
-        let vel_y = hailstone.velocity[1];
-        let vel_z = hailstone.velocity[2];
+        let loc_x = hailstone.loc.x;
+        let loc_y = hailstone.loc.y;
+        let loc_z = hailstone.loc.z;
+        let vel_x = hailstone.velocity.x;
+        let vel_y = hailstone.velocity.y;
+        let vel_z = hailstone.velocity.z;
         writeln!(
             &mut output,
             "{vel_z}*y - c*y {:+} {loc_y:+}*c {:+}*z +b*z {:+}*b,",
@@ -183,12 +228,12 @@ pub fn part_two(input: &str) -> String {
     }
     writeln!(&mut output, "], [x, y, z, a, b, c], set=True)").unwrap();
 
-    let loc_x_0 = hailstones[0].loc[0];
-    let loc_y_0 = hailstones[0].loc[1];
-    let loc_z_0 = hailstones[0].loc[2];
-    let vel_x_0 = hailstones[0].velocity[0];
-    let vel_y_0 = hailstones[0].velocity[1];
-    let vel_z_0 = hailstones[0].velocity[2];
+    let loc_x_0 = hailstones[0].loc.x;
+    let loc_y_0 = hailstones[0].loc.y;
+    let loc_z_0 = hailstones[0].loc.z;
+    let vel_x_0 = hailstones[0].velocity.x;
+    let vel_y_0 = hailstones[0].velocity.y;
+    let vel_z_0 = hailstones[0].velocity.z;
 
     writeln!(&mut output).unwrap();
 
@@ -196,12 +241,12 @@ pub fn part_two(input: &str) -> String {
     writeln!(&mut output, "from sympy.abc import x, y, z, a, b, c").unwrap();
     writeln!(&mut output, "solve([").unwrap();
     for hailstone in hailstones.iter().skip(1).take(2) {
-        let loc_x = hailstone.loc[0];
-        let loc_y = hailstone.loc[1];
-        let loc_z = hailstone.loc[2];
-        let vel_x = hailstone.velocity[0];
-        let vel_y = hailstone.velocity[1];
-        let vel_z = hailstone.velocity[2];
+        let loc_x = hailstone.loc.x;
+        let loc_y = hailstone.loc.y;
+        let loc_z = hailstone.loc.z;
+        let vel_x = hailstone.velocity.x;
+        let vel_y = hailstone.velocity.y;
+        let vel_z = hailstone.velocity.z;
         writeln!(
             &mut output,
             "{:+}*y {:+} {:+}*c {:+}*z {:+}*b {vel_z:+}*y {:+} {loc_y:+}*c {:+}*z {:+}*b,",
@@ -251,12 +296,12 @@ pub fn part_two(input: &str) -> String {
     writeln!(&mut output, "from sympy.abc import x, y, z, a, b, c").unwrap();
     writeln!(&mut output, "solve([").unwrap();
     for hailstone in hailstones.iter().skip(1).take(2) {
-        let loc_x = hailstone.loc[0];
-        let loc_y = hailstone.loc[1];
-        let loc_z = hailstone.loc[2];
-        let vel_x = hailstone.velocity[0];
-        let vel_y = hailstone.velocity[1];
-        let vel_z = hailstone.velocity[2];
+        let loc_x = hailstone.loc.x;
+        let loc_y = hailstone.loc.y;
+        let loc_z = hailstone.loc.z;
+        let vel_x = hailstone.velocity.x;
+        let vel_y = hailstone.velocity.y;
+        let vel_z = hailstone.velocity.z;
         writeln!(
             &mut output,
             "{:+}*y {:+} {:+}*c {:+}*z {:+}*b,",
@@ -291,12 +336,12 @@ pub fn part_two(input: &str) -> String {
 
     writeln!(&mut output).unwrap();
     for hailstone in hailstones.iter().skip(1).take(2) {
-        let loc_x = hailstone.loc[0];
-        let loc_y = hailstone.loc[1];
-        let loc_z = hailstone.loc[2];
-        let vel_x = hailstone.velocity[0];
-        let vel_y = hailstone.velocity[1];
-        let vel_z = hailstone.velocity[2];
+        let loc_x = hailstone.loc.x;
+        let loc_y = hailstone.loc.y;
+        let loc_z = hailstone.loc.z;
+        let vel_x = hailstone.velocity.x;
+        let vel_y = hailstone.velocity.y;
+        let vel_z = hailstone.velocity.z;
 
         let mut write = |a: f64, b: f64, c: f64, x: f64, y: f64, z: f64, constant: f64| {
             writeln!(
@@ -348,13 +393,12 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_one_challenge() {
+        let input = aoc_input::challenge(2023, 24);
         assert_eq!(
-            part_one(
-                include_str!("../input.txt"),
-                200000000000000.0..400000000000000.0
-            ),
+            part_one(&input, 200000000000000.0..400000000000000.0),
             15262
         )
     }
@@ -369,12 +413,18 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     #[cfg(feature = "challenge")]
     fn part_two_challenge() {
-        println!("{}", part_two(include_str!("../input.txt")));
+        let input = aoc_input::challenge(2023, 24);
+        println!("{}", part_two(&input));
         assert_eq!(
             part_two(include_str!("../example.txt")),
-            include_str!("../part-two-input-output.txt")
+            std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/part-two-input-output.txt"
+            ))
+            .unwrap()
         )
     }
 }
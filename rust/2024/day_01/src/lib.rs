@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     character::complete::{digit1, newline, space1},
     combinator::map_res,
@@ -11,40 +12,58 @@ type Num = u32;
 type Pair = (Num, Num);
 type List = Vec<Pair>;
 
-fn parse(input: &str) -> List {
-    let raw: IResult<&str, List> = separated_list0(
-        newline,
-        separated_pair(
-            map_res(digit1, str::parse),
-            space1,
-            map_res(digit1, str::parse),
-        ),
-    )(input);
+pub struct LocationLists(List);
 
-    raw.unwrap().1
+impl Solution for LocationLists {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<&str, List> = separated_list0(
+            newline,
+            separated_pair(
+                map_res(digit1, str::parse),
+                space1,
+                map_res(digit1, str::parse),
+            ),
+        )(input);
+
+        let list = aoc_core::finish(input, raw)?;
+        Ok(Self(list))
+    }
+
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        let list = &self.0;
+        let mut list_a: Vec<_> = list.iter().map(|p| p.0).collect();
+        let mut list_b: Vec<_> = list.iter().map(|p| p.1).collect();
+        list_a.sort();
+        list_b.sort();
+        Ok(Iterator::zip(list_a.iter(), list_b.iter())
+            .map(|(&a, &b)| Num::abs_diff(a, b))
+            .sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        let list = &self.0;
+        let mut right_count = HashMap::<Num, Num>::new();
+        for &(_, n) in list.iter() {
+            *right_count.entry(n).or_insert(0) += 1;
+        }
+
+        Ok(list
+            .iter()
+            .map(|&(l, _)| l * right_count.get(&l).copied().unwrap_or(0))
+            .sum())
+    }
 }
 
 pub fn part_1(input: &str) -> Num {
-    let list = parse(input);
-    let mut list_a: Vec<_> = list.iter().map(|p| p.0).collect();
-    let mut list_b: Vec<_> = list.iter().map(|p| p.1).collect();
-    list_a.sort();
-    list_b.sort();
-    Iterator::zip(list_a.iter(), list_b.iter())
-        .map(|(&a, &b)| Num::abs_diff(a, b))
-        .sum()
+    aoc_core::part1::<LocationLists>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> Num {
-    let list = parse(input);
-    let mut right_count = HashMap::<Num, Num>::new();
-    for &(_, n) in list.iter() {
-        *right_count.entry(n).or_insert(0) += 1;
-    }
-
-    list.iter()
-        .map(|&(l, _)| l * right_count.get(&l).copied().unwrap_or(0))
-        .sum()
+    aoc_core::part2::<LocationLists>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -57,8 +76,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 1603498);
+        let input = aoc_input::challenge(2024, 1);
+        assert_eq!(part_1(&input), 1603498);
     }
 
     #[test]
@@ -67,7 +88,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challengepart_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 25574739);
+        let input = aoc_input::challenge(2024, 1);
+        assert_eq!(part_2(&input), 25574739);
     }
 }
@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     character::complete::{digit1, newline, space1},
     combinator::map_res,
@@ -8,43 +9,60 @@ type Num = u64;
 type Report = Vec<Num>;
 type Reports = Vec<Report>;
 
-fn parse(input: &str) -> Reports {
-    let raw: IResult<&str, Reports> = separated_list0(
-        newline,
-        separated_list1(space1, map_res(digit1, str::parse)),
-    )(input);
-
-    raw.unwrap().1
-}
-
 fn safe_report(report: impl DoubleEndedIterator<Item = Num> + Clone) -> bool {
     (report.clone().is_sorted() || report.clone().rev().is_sorted())
         && Iterator::zip(report.clone(), report.clone().skip(1))
             .all(|(a, b)| (1..=3).contains(&Num::abs_diff(a, b)))
 }
 
+pub struct UnusualData(Reports);
+
+impl Solution for UnusualData {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<&str, Reports> = separated_list0(
+            newline,
+            separated_list1(space1, map_res(digit1, str::parse)),
+        )(input);
+
+        let reports = aoc_core::finish(input, raw)?;
+        Ok(Self(reports))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|r| safe_report(r.iter().copied()))
+            .count())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|r| {
+                (0..r.len()).any(|i| {
+                    safe_report(
+                        r.iter()
+                            .enumerate()
+                            .filter_map(|(j, &n)| (i != j).then_some(n)),
+                    )
+                })
+            })
+            .count())
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let reports = parse(input);
-    reports
-        .iter()
-        .filter(|r| safe_report(r.iter().copied()))
-        .count()
+    aoc_core::part1::<UnusualData>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let reports = parse(input);
-    reports
-        .iter()
-        .filter(|r| {
-            (0..r.len()).any(|i| {
-                safe_report(
-                    r.iter()
-                        .enumerate()
-                        .filter_map(|(j, &n)| (i != j).then_some(n)),
-                )
-            })
-        })
-        .count()
+    aoc_core::part2::<UnusualData>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -57,8 +75,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 326);
+        let input = aoc_input::challenge(2024, 2);
+        assert_eq!(part_1(&input), 326);
     }
 
     #[test]
@@ -67,7 +87,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 381);
+        let input = aoc_input::challenge(2024, 2);
+        assert_eq!(part_2(&input), 381);
     }
 }
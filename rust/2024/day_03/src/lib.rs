@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
@@ -15,57 +16,75 @@ enum Instruction {
     DoNot,
 }
 
-fn parse(input: &str) -> Vec<Instruction> {
-    let raw: IResult<_, _> = many0(alt((
-        map(
-            delimited(
-                tag("mul("),
-                separated_pair(
-                    map_res(digit1, str::parse),
-                    char(','),
-                    map_res(digit1, str::parse),
+pub struct Memory(Vec<Instruction>);
+
+impl Solution for Memory {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<_, _> = many0(alt((
+            map(
+                delimited(
+                    tag("mul("),
+                    separated_pair(
+                        map_res(digit1, str::parse),
+                        char(','),
+                        map_res(digit1, str::parse),
+                    ),
+                    char(')'),
                 ),
-                char(')'),
+                |(a, b)| Instruction::Mul(a, b),
             ),
-            |(a, b)| Instruction::Mul(a, b),
-        ),
-        map(tag("do()"), |_| Instruction::Do),
-        map(tag("don't()"), |_| Instruction::DoNot),
-        map(take(1usize), |_| Instruction::Junk),
-    )))(input);
+            map(tag("do()"), |_| Instruction::Do),
+            map(tag("don't()"), |_| Instruction::DoNot),
+            map(take(1usize), |_| Instruction::Junk),
+        )))(input);
 
-    raw.unwrap().1
-}
+        let instructions = aoc_core::finish(input, raw)?;
+        Ok(Self(instructions))
+    }
 
-pub fn part_1(input: &str) -> Num {
-    parse(input)
-        .iter()
-        .filter_map(|instruction| {
-            if let Instruction::Mul(a, b) = instruction {
-                Some(a * b)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self
+            .0
+            .iter()
+            .filter_map(|instruction| {
+                if let Instruction::Mul(a, b) = instruction {
+                    Some(a * b)
+                } else {
+                    None
+                }
+            })
+            .sum())
+    }
 
-pub fn part_2(input: &str) -> Num {
-    let mut sum = 0;
-    let mut enabled = true;
-    for instruction in parse(input).iter() {
-        match instruction {
-            Instruction::Do => enabled = true,
-            Instruction::DoNot => enabled = false,
-            Instruction::Mul(a, b) => {
-                if enabled {
-                    sum += a * b
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        let mut sum = 0;
+        let mut enabled = true;
+        for instruction in self.0.iter() {
+            match instruction {
+                Instruction::Do => enabled = true,
+                Instruction::DoNot => enabled = false,
+                Instruction::Mul(a, b) => {
+                    if enabled {
+                        sum += a * b
+                    }
                 }
+                Instruction::Junk => {}
             }
-            Instruction::Junk => {}
         }
+        Ok(sum)
     }
-    sum
+}
+
+pub fn part_1(input: &str) -> Num {
+    aoc_core::part1::<Memory>(input).unwrap()
+}
+
+pub fn part_2(input: &str) -> Num {
+    aoc_core::part2::<Memory>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -78,8 +97,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 180233229);
+        let input = aoc_input::challenge(2024, 3);
+        assert_eq!(part_1(&input), 180233229);
     }
 
     #[test]
@@ -88,7 +109,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 95411583);
+        let input = aoc_input::challenge(2024, 3);
+        assert_eq!(part_2(&input), 95411583);
     }
 }
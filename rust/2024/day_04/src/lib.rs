@@ -1,107 +1,67 @@
-struct Grid(Vec<Vec<char>>);
+use aoc_core::{Error, Solution};
+use aoc_grid::{Coord, Diagonal, Grid};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum Direction {
-    Forward,
-    Still,
-    Backward,
-}
-
-const DIRECTIONS: &[(Direction, Direction)] = &[
-    (Direction::Forward, Direction::Forward),
-    (Direction::Forward, Direction::Still),
-    (Direction::Forward, Direction::Backward),
-    (Direction::Still, Direction::Forward),
-    (Direction::Still, Direction::Backward),
-    (Direction::Backward, Direction::Forward),
-    (Direction::Backward, Direction::Still),
-    (Direction::Backward, Direction::Backward),
-];
+pub struct WordSearch(Grid<char>);
 
-impl Direction {
-    fn follow(&self, original: usize, offset: usize) -> Option<usize> {
-        match self {
-            Direction::Forward => original.checked_add(offset),
-            Direction::Still => Some(original),
-            Direction::Backward => original.checked_sub(offset),
-        }
+impl WordSearch {
+    fn spells(&self, start: Coord, direction: Coord, word: &str) -> bool {
+        let mut letters = word.chars();
+        self.0.get(start) == letters.next().as_ref()
+            && self
+                .0
+                .ray(start, direction)
+                .map(|(_, &letter)| letter)
+                .take(word.len() - 1)
+                .eq(letters)
     }
 }
 
-impl Grid {
-    fn new(input: &str) -> Self {
-        Grid(input.lines().map(|line| line.chars().collect()).collect())
+impl Solution for WordSearch {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(WordSearch(Grid::from_chars(input)?))
     }
 
-    fn get(
-        &self,
-        x: usize,
-        y: usize,
-        offset: usize,
-        horizontal_direction: Direction,
-        vertical_direction: Direction,
-    ) -> Option<char> {
-        let adjusted_x = horizontal_direction.follow(x, offset);
-        let adjusted_y = vertical_direction.follow(y, offset);
-        if let (Some(adjusted_x), Some(adjusted_y)) = (adjusted_x, adjusted_y) {
-            self.0
-                .get(adjusted_y)
-                .and_then(|line| line.get(adjusted_x))
-                .copied()
-        } else {
-            None
-        }
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .positions()
+            .map(|position| {
+                // The offsets to the neighbours of the origin are the eight directions.
+                Coord::ZERO
+                    .neighbours_8()
+                    .iter()
+                    .filter(|&&direction| self.spells(position, direction, "XMAS"))
+                    .count()
+            })
+            .sum())
     }
 
-    fn iterate_positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        self.0
-            .iter()
-            .enumerate()
-            .flat_map(|(y, line)| (0..line.len()).map(move |x| (x, y)))
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .positions()
+            .filter(|&position| {
+                // Each diagonal through the A has to read MAS one way or the other.
+                [Diagonal::NorthEast, Diagonal::SouthEast].iter().all(|d| {
+                    let step: Coord = d.offset();
+                    self.spells(position - step, step, "MAS")
+                        || self.spells(position + step, -step, "MAS")
+                })
+            })
+            .count())
     }
 }
 
 pub fn part_1(input: &str) -> usize {
-    let grid = Grid::new(input);
-    grid.iterate_positions()
-        .map(|(x, y)| {
-            DIRECTIONS
-                .iter()
-                .filter(|(horizontal_direction, vertical_direction)| {
-                    "XMAS".chars().enumerate().all(|(offset, letter)| {
-                        grid.get(x, y, offset, *horizontal_direction, *vertical_direction)
-                            == Some(letter)
-                    })
-                })
-                .count()
-        })
-        .sum()
+    aoc_core::part1::<WordSearch>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let grid = Grid::new(input);
-    grid.iterate_positions()
-        .filter(|&(x, y)| {
-            matches!(
-                (
-                    grid.get(x, y, 0, Direction::Still, Direction::Still),
-                    (
-                        grid.get(x, y, 1, Direction::Forward, Direction::Forward),
-                        grid.get(x, y, 1, Direction::Backward, Direction::Backward),
-                    ),
-                    (
-                        grid.get(x, y, 1, Direction::Forward, Direction::Backward),
-                        grid.get(x, y, 1, Direction::Backward, Direction::Forward),
-                    ),
-                ),
-                (
-                    Some('A'),
-                    (Some('M'), Some('S')) | (Some('S'), Some('M')),
-                    (Some('M'), Some('S')) | (Some('S'), Some('M')),
-                )
-            )
-        })
-        .count()
+    aoc_core::part2::<WordSearch>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -114,8 +74,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 2549);
+        let input = aoc_input::challenge(2024, 4);
+        assert_eq!(part_1(&input), 2549);
     }
 
     #[test]
@@ -124,7 +86,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 2003);
+        let input = aoc_input::challenge(2024, 4);
+        assert_eq!(part_2(&input), 2003);
     }
 }
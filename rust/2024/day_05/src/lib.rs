@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     character::complete::{char, digit1, newline},
     combinator::{map, map_res},
@@ -14,19 +15,59 @@ struct Rule(Num, Num);
 #[derive(Debug)]
 struct Rules(Vec<Rule>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Update(Vec<Num>);
 #[derive(Debug)]
 struct Updates(Vec<Update>);
 
 #[derive(Debug)]
-struct World {
+pub struct World {
     rules: Rules,
     updates: Updates,
 }
 
-impl World {
-    fn parse(input: &str) -> Self {
+impl Update {
+    fn middle_page_number(&self) -> Num {
+        self.0[self.0.len() / 2]
+    }
+
+    fn sorted_middle_page_number(&mut self, rules: &Rules) -> Num {
+        self.0.sort_by(|&a, &b| {
+            if rules.0.iter().any(|r| r.0 == a && r.1 == b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        });
+        self.middle_page_number()
+    }
+}
+
+impl Rule {
+    fn update_is_valid(&self, update: &Update) -> bool {
+        if let (Some(a), Some(b)) = (
+            update.0.iter().position(|&n| n == self.0),
+            update.0.iter().position(|&n| n == self.1),
+        ) {
+            a < b
+        } else {
+            true
+        }
+    }
+}
+
+impl Rules {
+    fn update_is_valid(&self, update: &Update) -> bool {
+        self.0.iter().all(|rule| rule.update_is_valid(update))
+    }
+}
+
+impl Solution for World {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
         let raw: IResult<_, _> = map(
             separated_pair(
                 map(
@@ -58,66 +99,36 @@ impl World {
             |(rules, updates)| World { rules, updates },
         )(input);
 
-        raw.unwrap().1
+        Ok(aoc_core::finish(input, raw)?)
     }
-}
 
-impl Update {
-    fn middle_page_number(&self) -> Num {
-        self.0[self.0.len() / 2]
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self
+            .updates
+            .0
+            .iter()
+            .filter(|update| self.rules.update_is_valid(update))
+            .map(|update| update.middle_page_number())
+            .sum())
     }
 
-    fn sorted_middle_page_number(&mut self, rules: &Rules) -> Num {
-        self.0.sort_by(|&a, &b| {
-            if rules.0.iter().any(|r| r.0 == a && r.1 == b) {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
-        });
-        self.middle_page_number()
-    }
-}
-
-impl Rule {
-    fn update_is_valid(&self, update: &Update) -> bool {
-        if let (Some(a), Some(b)) = (
-            update.0.iter().position(|&n| n == self.0),
-            update.0.iter().position(|&n| n == self.1),
-        ) {
-            a < b
-        } else {
-            true
-        }
-    }
-}
-
-impl Rules {
-    fn update_is_valid(&self, update: &Update) -> bool {
-        self.0.iter().all(|rule| rule.update_is_valid(update))
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        Ok(self
+            .updates
+            .0
+            .iter()
+            .filter(|update| !self.rules.update_is_valid(update))
+            .map(|update| update.clone().sorted_middle_page_number(&self.rules))
+            .sum())
     }
 }
 
 pub fn part_1(input: &str) -> Num {
-    let world = World::parse(input);
-    world
-        .updates
-        .0
-        .iter()
-        .filter(|update| world.rules.update_is_valid(update))
-        .map(|update| update.middle_page_number())
-        .sum()
+    aoc_core::part1::<World>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> Num {
-    let mut world = World::parse(input);
-    world
-        .updates
-        .0
-        .iter_mut()
-        .filter(|update| !world.rules.update_is_valid(update))
-        .map(|update| update.sorted_middle_page_number(&world.rules))
-        .sum()
+    aoc_core::part2::<World>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -130,8 +141,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 6242);
+        let input = aoc_input::challenge(2024, 5);
+        assert_eq!(part_1(&input), 6242);
     }
 
     #[test]
@@ -140,7 +153,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 123);
+        let input = aoc_input::challenge(2024, 5);
+        assert_eq!(part_2(&input), 123);
     }
 }
@@ -1,64 +1,28 @@
 use std::collections::BTreeSet;
 
+use aoc_core::{Error, ParseError, Solution};
+use aoc_grid::{Coord, Direction, Grid};
+
 #[derive(Debug, Clone)]
 struct Lab {
-    walls: Vec<bool>,
-    width: isize,
-    height: isize,
+    walls: Grid<bool>,
 }
 
 #[derive(Debug, Clone)]
 struct Guard {
-    direction: (isize, isize),
-    position: (isize, isize),
-    visited: BTreeSet<(isize, isize)>,
-    loop_record: BTreeSet<((isize, isize), (isize, isize))>,
+    direction: Direction,
+    position: Coord,
+    visited: BTreeSet<Coord>,
+    loop_record: BTreeSet<(Coord, Direction)>,
 }
 
 #[derive(Debug, Clone)]
-struct World {
+pub struct World {
     lab: Lab,
     guard: Guard,
 }
 
 impl World {
-    fn parse(input: &str) -> Self {
-        let width = input.lines().next().unwrap().chars().count() as isize;
-        let mut height = 0;
-        let mut position = (0, 0);
-        let mut walls = Vec::new();
-        let direction = (0, -1);
-        for (line, y) in input.lines().filter(|l| !l.is_empty()).zip(0..) {
-            height += 1;
-            for (c, x) in line.chars().zip(0..) {
-                match c {
-                    '#' => {
-                        walls.push(true);
-                    }
-                    '^' => {
-                        walls.push(false);
-                        position = (x, y)
-                    }
-                    _ => walls.push(false),
-                }
-            }
-        }
-
-        Self {
-            lab: Lab {
-                walls,
-                width,
-                height,
-            },
-            guard: Guard {
-                direction,
-                position,
-                visited: [position].iter().copied().collect(),
-                loop_record: [(position, direction)].iter().copied().collect(),
-            },
-        }
-    }
-
     fn guard_in_lab(&self) -> bool {
         self.guard.in_lab(&self.lab)
     }
@@ -69,35 +33,22 @@ impl World {
 }
 
 impl Lab {
-    fn is_wall(&self, x: isize, y: isize) -> bool {
-        if x < 0 || x >= self.width || y < 0 || y >= self.height {
-            return false;
-        }
-        let index = x + y * self.width;
-        match usize::try_from(index) {
-            Ok(i) => self.walls.get(i).copied().unwrap_or(false),
-            Err(_) => false,
-        }
+    fn is_wall(&self, position: Coord) -> bool {
+        self.walls.get(position) == Some(&true)
     }
 }
 
 impl Guard {
     fn rotate_right(&mut self) {
-        self.direction = (-self.direction.1, self.direction.0);
+        self.direction = self.direction.turn_right();
     }
 
-    fn position_in_front(&self) -> (isize, isize) {
-        (
-            self.position.0 + self.direction.0,
-            self.position.1 + self.direction.1,
-        )
+    fn position_in_front(&self) -> Coord {
+        self.position + self.direction.offset()
     }
 
     fn in_lab(&self, lab: &Lab) -> bool {
-        self.position.0 >= 0
-            && self.position.0 < lab.width
-            && self.position.1 >= 0
-            && self.position.1 < lab.height
+        lab.walls.contains(self.position)
     }
 
     fn record_visit(&mut self, lab: &Lab) -> bool {
@@ -109,45 +60,81 @@ impl Guard {
 
     fn advance(&mut self, lab: &Lab) -> bool {
         for _ in 0..2 {
-            let position_in_front = self.position_in_front();
-            if lab.is_wall(position_in_front.0, position_in_front.1) {
+            if lab.is_wall(self.position_in_front()) {
                 self.rotate_right();
             }
         }
 
-        self.position.0 += self.direction.0;
-        self.position.1 += self.direction.1;
+        self.position = self.position_in_front();
 
         self.record_visit(lab)
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let mut world = World::parse(input);
-    while world.guard_in_lab() {
-        world.advance_guard();
+impl Solution for World {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let map = Grid::parse(input, |c| match c {
+            '.' | '#' | '^' => Ok(c),
+            _ => Err("floor ., an obstruction # or the guard ^"),
+        })?;
+        let position = map
+            .find(|&c| c == '^')
+            .ok_or_else(|| ParseError::at_end(input, "the guard ^"))?;
+        let direction = Direction::North;
+        Ok(Self {
+            lab: Lab {
+                walls: map.map(|&c| c == '#'),
+            },
+            guard: Guard {
+                direction,
+                position,
+                visited: [position].iter().copied().collect(),
+                loop_record: [(position, direction)].iter().copied().collect(),
+            },
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let mut world = self.clone();
+        while world.guard_in_lab() {
+            world.advance_guard();
+        }
+        Ok(world.guard.visited.len())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let world = self;
+        Ok(world
+            .lab
+            .walls
+            .positions()
+            .filter(|&position| {
+                if world.guard.position == position {
+                    return false;
+                }
+                let mut world = world.clone();
+                world.lab.walls[position] = true;
+                while world.guard_in_lab() {
+                    if !world.advance_guard() {
+                        return true;
+                    }
+                }
+                false
+            })
+            .count())
     }
-    world.guard.visited.len()
+}
+
+pub fn part_1(input: &str) -> usize {
+    aoc_core::part1::<World>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let world = World::parse(input);
-    (0..world.lab.height)
-        .flat_map(|y| (0..world.lab.height).map(move |x| (x, y)))
-        .filter(|&(x, y)| {
-            if world.guard.position == (x, y) {
-                return false;
-            }
-            let mut world = world.clone();
-            world.lab.walls[(x + y * world.lab.height) as usize] = true;
-            while world.guard_in_lab() {
-                if !world.advance_guard() {
-                    return true;
-                }
-            }
-            false
-        })
-        .count()
+    aoc_core::part2::<World>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -160,8 +147,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 4580);
+        let input = aoc_input::challenge(2024, 6);
+        assert_eq!(part_1(&input), 4580);
     }
 
     #[test]
@@ -170,7 +159,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 1480);
+        let input = aoc_input::challenge(2024, 6);
+        assert_eq!(part_2(&input), 1480);
     }
 }
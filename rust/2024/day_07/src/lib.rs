@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, newline},
@@ -23,13 +24,9 @@ impl Equation {
             sum = match operator {
                 0 => sum.checked_add(num)?,
                 1 => sum.checked_mul(num)?,
-                2 => {
-                    if concatenation {
-                        let multiplier = Num::pow(10, num.ilog10() + 1);
-                        sum.checked_mul(multiplier)?.checked_add(num)?
-                    } else {
-                        return None;
-                    }
+                2 if concatenation => {
+                    let multiplier = Num::pow(10, num.ilog10() + 1);
+                    sum.checked_mul(multiplier)?.checked_add(num)?
                 }
                 _ => return None,
             }
@@ -43,41 +40,58 @@ impl Equation {
     }
 }
 
-fn parse(input: &str) -> Vec<Equation> {
-    let raw: IResult<_, _> = separated_list1(
-        newline,
-        map(
-            separated_pair(
-                map_res(digit1, str::parse),
-                tag(": "),
-                separated_list1(char(' '), map_res(digit1, str::parse)),
+pub struct Equations(Vec<Equation>);
+
+impl Equations {
+    fn calibration_result(&self, concatenation: bool) -> Num {
+        self.0
+            .iter()
+            .filter(|e| e.is_valid(concatenation))
+            .map(|e| e.test_value)
+            .sum()
+    }
+}
+
+impl Solution for Equations {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<_, _> = separated_list1(
+            newline,
+            map(
+                separated_pair(
+                    map_res(digit1, str::parse),
+                    tag(": "),
+                    separated_list1(char(' '), map_res(digit1, str::parse)),
+                ),
+                |(test_value, remaining_numbers)| Equation {
+                    test_value,
+                    remaining_numbers,
+                },
             ),
-            |(test_value, remaining_numbers)| Equation {
-                test_value,
-                remaining_numbers,
-            },
-        ),
-    )(input);
-
-    raw.unwrap().1
+        )(input);
+
+        let equations = aoc_core::finish(input, raw)?;
+        Ok(Self(equations))
+    }
+
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self.calibration_result(false))
+    }
+
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        Ok(self.calibration_result(true))
+    }
 }
 
 pub fn part_1(input: &str) -> Num {
-    let equations = parse(input);
-    equations
-        .iter()
-        .filter(|e| e.is_valid(false))
-        .map(|e| e.test_value)
-        .sum()
+    aoc_core::part1::<Equations>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> Num {
-    let equations = parse(input);
-    equations
-        .iter()
-        .filter(|e| e.is_valid(true))
-        .map(|e| e.test_value)
-        .sum()
+    aoc_core::part2::<Equations>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -90,8 +104,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 7885693428401);
+        let input = aoc_input::challenge(2024, 7);
+        assert_eq!(part_1(&input), 7885693428401);
     }
 
     #[test]
@@ -100,7 +116,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 348360680516005);
+        let input = aoc_input::challenge(2024, 7);
+        assert_eq!(part_2(&input), 348360680516005);
     }
 }
@@ -1,34 +1,17 @@
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
+
 use std::collections::{BTreeMap, BTreeSet};
 
-type Coord = (isize, isize);
+type Coord = Vec2<isize>;
 
-struct Map {
+pub struct Map {
     width: isize,
     height: isize,
     frequencies: BTreeMap<char, Vec<Coord>>,
 }
 
 impl Map {
-    fn parse(input: &str) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut frequencies = BTreeMap::<char, Vec<Coord>>::new();
-        for (line, y) in input.lines().zip(0..) {
-            height = y + 1;
-            for (c, x) in line.chars().zip(0..) {
-                width = x + 1;
-                if c.is_ascii_alphanumeric() {
-                    frequencies.entry(c).or_default().push((x, y));
-                }
-            }
-        }
-        Self {
-            width,
-            height,
-            frequencies,
-        }
-    }
-
     fn frequency_pairs(&self) -> impl Iterator<Item = (Coord, Coord)> + '_ {
         self.frequencies.values().flat_map(|antennas| {
             antennas
@@ -38,52 +21,84 @@ impl Map {
         })
     }
 
-    fn coord_inside(&self, (x, y): Coord) -> bool {
-        x >= 0 && x < self.width && y >= 0 && y < self.height
+    fn coord_inside(&self, c: Coord) -> bool {
+        c.x >= 0 && c.x < self.width && c.y >= 0 && c.y < self.height
     }
 
     fn antinodes_of_antenna_pairs_with_distance(
         &self,
-        (ax, ay): Coord,
-        (bx, by): Coord,
+        a: Coord,
+        b: Coord,
     ) -> impl Iterator<Item = Coord> + '_ {
-        [(2 * ax - bx, 2 * ay - by), (2 * bx - ax, 2 * by - ay)]
+        [a * 2 - b, b * 2 - a]
             .into_iter()
             .filter(|&c| self.coord_inside(c))
     }
 
     fn antinodes_of_antenna_pairs_in_line(
         &self,
-        (ax, ay): Coord,
-        (bx, by): Coord,
+        a: Coord,
+        b: Coord,
     ) -> impl Iterator<Item = Coord> + '_ {
         Iterator::chain(
             (0..)
-                .map(move |m| (ax + m * (ax - bx), ay + m * (ay - by)))
+                .map(move |m| a + (a - b) * m)
                 .take_while(|&c| self.coord_inside(c)),
             (1..)
-                .map(move |m| (ax - m * (ax - bx), ay - m * (ay - by)))
+                .map(move |m| a - (a - b) * m)
                 .take_while(|&c| self.coord_inside(c)),
         )
     }
 }
 
+impl Solution for Map {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut width = 0;
+        let mut height = 0;
+        let mut frequencies = BTreeMap::<char, Vec<Coord>>::new();
+        for (line, y) in input.lines().zip(0..) {
+            height = y + 1;
+            for (c, x) in line.chars().zip(0..) {
+                width = x + 1;
+                if c.is_ascii_alphanumeric() {
+                    frequencies.entry(c).or_default().push(Coord::new(x, y));
+                }
+            }
+        }
+        Ok(Self {
+            width,
+            height,
+            frequencies,
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let antinodes = BTreeSet::from_iter(
+            self.frequency_pairs()
+                .flat_map(|(a, b)| self.antinodes_of_antenna_pairs_with_distance(a, b)),
+        );
+        Ok(antinodes.len())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let antinodes = BTreeSet::from_iter(
+            self.frequency_pairs()
+                .flat_map(|(a, b)| self.antinodes_of_antenna_pairs_in_line(a, b)),
+        );
+        Ok(antinodes.len())
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let map = Map::parse(input);
-    let antinodes = BTreeSet::from_iter(
-        map.frequency_pairs()
-            .flat_map(|(a, b)| map.antinodes_of_antenna_pairs_with_distance(a, b)),
-    );
-    antinodes.len()
+    aoc_core::part1::<Map>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let map = Map::parse(input);
-    let antinodes = BTreeSet::from_iter(
-        map.frequency_pairs()
-            .flat_map(|(a, b)| map.antinodes_of_antenna_pairs_in_line(a, b)),
-    );
-    antinodes.len()
+    aoc_core::part2::<Map>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -98,8 +113,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 214);
+        let input = aoc_input::challenge(2024, 8);
+        assert_eq!(part_1(&input), 214);
     }
 
     #[test]
@@ -109,7 +126,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 809);
+        let input = aoc_input::challenge(2024, 8);
+        assert_eq!(part_2(&input), 809);
     }
 }
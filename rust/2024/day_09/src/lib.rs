@@ -1,25 +1,18 @@
+use aoc_core::{Error, Solution};
+
 use std::iter::repeat_n;
 
-#[derive(Debug)]
-struct Disk {
+#[derive(Debug, Clone)]
+pub struct Disk {
     blocks: Vec<Option<usize>>,
 }
 
 impl Disk {
-    fn parse(input: &str) -> Self {
-        let blocks = input
-            .bytes()
-            .enumerate()
-            .filter_map(|(i, d)| Some((i, d.checked_sub(b'0')?)))
-            .flat_map(|(i, n)| repeat_n((i % 2 == 0).then_some(i / 2), n.into()))
-            .collect();
-
-        Self { blocks }
-    }
-
     fn compact_files(&mut self) {
         let mut free_space_search_index = 0;
-        let mut block_search_index = self.blocks.len() - 1;
+        let Some(mut block_search_index) = self.blocks.len().checked_sub(1) else {
+            return;
+        };
         while free_space_search_index < block_search_index {
             if self.blocks[free_space_search_index].is_some() {
                 free_space_search_index += 1;
@@ -36,7 +29,9 @@ impl Disk {
 
     fn compact_whole_files(&mut self) {
         let mut largest_remaining_gap = self.blocks.len();
-        let mut file_end = self.blocks.len() - 1;
+        let Some(mut file_end) = self.blocks.len().checked_sub(1) else {
+            return;
+        };
         let mut gaps_start = 0;
 
         for (i, j) in (0..self.blocks.len()).zip(1..self.blocks.len()).rev() {
@@ -107,16 +102,48 @@ impl Disk {
     }
 }
 
+impl Solution for Disk {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let disk_map = input.trim_end();
+        if disk_map.is_empty() {
+            return Err(Error::parse(input, disk_map, "a digit"));
+        }
+        if let Some(at) = disk_map.find(|c: char| !c.is_ascii_digit()) {
+            return Err(Error::parse(input, &disk_map[at..], "a digit"));
+        }
+        let blocks = disk_map
+            .bytes()
+            .map(|d| d - b'0')
+            .enumerate()
+            .flat_map(|(i, n)| repeat_n((i % 2 == 0).then_some(i / 2), n.into()))
+            .collect();
+
+        Ok(Self { blocks })
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let mut disk = self.clone();
+        disk.compact_files();
+        Ok(disk.filesystem_checksum())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let mut disk = self.clone();
+        disk.compact_whole_files();
+        Ok(disk.filesystem_checksum())
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let mut disk = Disk::parse(input);
-    disk.compact_files();
-    disk.filesystem_checksum()
+    aoc_core::part1::<Disk>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let mut disk = Disk::parse(input);
-    disk.compact_whole_files();
-    disk.filesystem_checksum()
+    aoc_core::part2::<Disk>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -129,8 +156,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 6241633730082);
+        let input = aoc_input::challenge(2024, 9);
+        assert_eq!(part_1(&input), 6241633730082);
     }
 
     #[test]
@@ -139,7 +168,17 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 6265268809555);
+        let input = aoc_input::challenge(2024, 9);
+        assert_eq!(part_2(&input), 6265268809555);
+    }
+
+    #[test]
+    fn rejects_maps_without_digits() {
+        assert!(matches!(Disk::parse(""), Err(Error::Parse(_))));
+        assert!(matches!(Disk::parse("12x4\n"), Err(Error::Parse(e)) if e.column == 3));
+        assert_eq!(part_1("0"), 0);
+        assert_eq!(part_2("0"), 0);
     }
 }
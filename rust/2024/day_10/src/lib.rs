@@ -1,59 +1,26 @@
 use std::collections::BTreeSet;
 
-#[derive(Debug)]
-struct Map {
-    width: isize,
-    height: isize,
-    topology: Vec<i8>,
-}
-
-type Coord = (isize, isize);
+use aoc_core::{Error, Solution};
+use aoc_grid::{Coord, Grid};
 
-const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+#[derive(Debug)]
+pub struct Map(Grid<i8>);
 
 impl Map {
-    fn parse(input: &str) -> Self {
-        let mut width = 0;
-        let mut height = 0;
-        let mut topology = Vec::new();
-        for (line, y) in input.lines().zip(0..) {
-            height = y + 1;
-            for (c, x) in line.bytes().zip(0..) {
-                width = x + 1;
-                topology.push((c - b'0') as i8);
-            }
-        }
-        Self {
-            width,
-            height,
-            topology,
-        }
-    }
-
-    fn get(&self, (x, y): Coord) -> Option<i8> {
-        if x >= 0 && x < self.width && y >= 0 && y < self.height {
-            self.topology.get((y * self.width + x) as usize).copied()
-        } else {
-            None
-        }
-    }
-
     fn find_trailheads(&self) -> impl Iterator<Item = Coord> + '_ {
-        self.topology
+        self.0
             .iter()
-            .zip(0..)
-            .filter(|&(&t, _i)| (t == 0))
-            .map(|(_t, i)| (i % self.width, i / self.width))
+            .filter(|&(_, &t)| t == 0)
+            .map(|(position, _)| position)
     }
 
     fn peaks_count(&self, trailhead: Coord) -> usize {
         let mut peaks = BTreeSet::new();
         let mut stack = vec![trailhead];
         while let Some(v) = stack.pop() {
-            let height = self.get(v).unwrap();
-            for &d in DIRECTIONS.iter() {
-                let neighbour = (v.0 + d.0, v.1 + d.1);
-                if self.get(neighbour) == Some(height + 1) {
+            let height = self.0[v];
+            for neighbour in self.0.neighbours_4(v) {
+                if self.0[neighbour] == height + 1 {
                     stack.push(neighbour)
                 }
             }
@@ -68,10 +35,9 @@ impl Map {
         let mut count = 0;
         let mut stack = vec![trailhead];
         while let Some(v) = stack.pop() {
-            let height = self.get(v).unwrap();
-            for &d in DIRECTIONS.iter() {
-                let neighbour = (v.0 + d.0, v.1 + d.1);
-                if self.get(neighbour) == Some(height + 1) {
+            let height = self.0[v];
+            for neighbour in self.0.neighbours_4(v) {
+                if self.0[neighbour] == height + 1 {
                     stack.push(neighbour)
                 }
             }
@@ -83,18 +49,38 @@ impl Map {
     }
 }
 
+impl Solution for Map {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(Grid::parse(input, |c| {
+            c.to_digit(10).map(|h| h as i8).ok_or("a height")
+        })?))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .find_trailheads()
+            .map(|trailhead| self.peaks_count(trailhead))
+            .sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .find_trailheads()
+            .map(|trailhead| self.distinct_trails(trailhead))
+            .sum())
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let map = Map::parse(input);
-    map.find_trailheads()
-        .map(|trailhead| map.peaks_count(trailhead))
-        .sum()
+    aoc_core::part1::<Map>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let map = Map::parse(input);
-    map.find_trailheads()
-        .map(|trailhead| map.distinct_trails(trailhead))
-        .sum()
+    aoc_core::part2::<Map>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -108,8 +94,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 472);
+        let input = aoc_input::challenge(2024, 10);
+        assert_eq!(part_1(&input), 472);
     }
 
     #[test]
@@ -118,7 +106,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 969);
+        let input = aoc_input::challenge(2024, 10);
+        assert_eq!(part_2(&input), 969);
     }
 }
@@ -1,18 +1,11 @@
 use std::collections::BTreeMap;
 
+use aoc_core::{Error, Solution};
+
 type Num = u64;
-struct Pebbles(Vec<Num>);
+pub struct Pebbles(Vec<Num>);
 
 impl Pebbles {
-    fn parse(input: &str) -> Self {
-        Self(
-            input
-                .split_ascii_whitespace()
-                .map(|c| c.parse().unwrap())
-                .collect(),
-        )
-    }
-
     fn sequence_length_calculator(&self, iterations: usize) -> usize {
         let mut memo = BTreeMap::<(Num, usize), usize>::new();
         let mut count = 0;
@@ -54,14 +47,34 @@ fn sequence_length_calculator_internal(
     count
 }
 
+impl Solution for Pebbles {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        input
+            .split_ascii_whitespace()
+            .map(|c| c.parse().map_err(|_| Error::parse(input, c, "a number")))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.sequence_length_calculator(25))
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.sequence_length_calculator(75))
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let pebbles = Pebbles::parse(input);
-    pebbles.sequence_length_calculator(25)
+    aoc_core::part1::<Pebbles>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let pebbles = Pebbles::parse(input);
-    pebbles.sequence_length_calculator(75)
+    aoc_core::part2::<Pebbles>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -74,12 +87,16 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 183620);
+        let input = aoc_input::challenge(2024, 11);
+        assert_eq!(part_1(&input), 183620);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 220377651399268);
+        let input = aoc_input::challenge(2024, 11);
+        assert_eq!(part_2(&input), 220377651399268);
     }
 }
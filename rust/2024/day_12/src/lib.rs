@@ -1,49 +1,42 @@
 use std::collections::BTreeSet;
 
-#[derive(Debug)]
-struct GardenMap(Vec<Vec<char>>);
-
-type Coord = (isize, isize);
+use aoc_core::{Error, Solution};
+use aoc_graph::UnionFind;
+use aoc_grid::{Coord, Direction, Grid};
 
-const DIRECTIONS: [Coord; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+#[derive(Debug)]
+pub struct GardenMap(Grid<char>);
 
 #[derive(Debug)]
 struct Plot(BTreeSet<Coord>);
 
 impl GardenMap {
-    fn parse(input: &str) -> Self {
-        Self(input.lines().map(|line| line.chars().collect()).collect())
-    }
-
-    fn get(&self, c: Coord) -> Option<char> {
-        self.0
-            .get(c.1 as usize)
-            .and_then(|l| l.get(c.0 as usize).copied())
-    }
-
+    /// The regions of matching plants, by joining each plot to the matching plots to its
+    /// right and below.
     fn plots(&self) -> Vec<Plot> {
-        let mut allocated = BTreeSet::<Coord>::new();
-        let mut plots = Vec::new();
-        for (line, y) in self.0.iter().zip(0..) {
-            for (&t, x) in line.iter().zip(0..) {
-                if allocated.insert((x, y)) {
-                    let mut plot = BTreeSet::new();
-                    let mut stack = vec![(x, y)];
-                    while let Some(c) = stack.pop() {
-                        plot.insert(c);
-                        for &d in DIRECTIONS.iter() {
-                            let new_location = (c.0 + d.0, c.1 + d.1);
-                            if self.get(new_location) == Some(t) && allocated.insert(new_location) {
-                                stack.push(new_location);
-                            }
-                        }
-                    }
-
-                    plots.push(Plot(plot));
+        let width = self.0.width();
+        let index = |c: Coord| c.y as usize * width + c.x as usize;
+        let mut regions = UnionFind::new(width * self.0.height());
+        for (position, &t) in self.0.iter() {
+            for step in [Direction::East, Direction::South] {
+                let neighbour = position + step.offset();
+                if self.0.get(neighbour) == Some(&t) {
+                    regions.union(index(position), index(neighbour));
                 }
             }
         }
-        plots
+        regions
+            .components()
+            .into_iter()
+            .map(|region| {
+                Plot(
+                    region
+                        .into_iter()
+                        .map(|i| Coord::new((i % width) as isize, (i / width) as isize))
+                        .collect(),
+                )
+            })
+            .collect()
     }
 }
 
@@ -53,12 +46,12 @@ impl Plot {
     }
 
     fn perimeter(&self) -> usize {
-        DIRECTIONS
+        Direction::ALL
             .iter()
-            .map(|&d| {
+            .map(|d| {
                 self.0
                     .iter()
-                    .filter(|&&c| !self.0.contains(&(c.0 + d.0, c.1 + d.1)))
+                    .filter(|&&c| !self.0.contains(&(c + d.offset())))
                     .count()
             })
             .sum()
@@ -66,23 +59,19 @@ impl Plot {
 
     fn number_of_sides(&self) -> usize {
         let mut count = 0;
-        for &direction in DIRECTIONS.iter() {
+        for direction in Direction::ALL.map(Direction::offset::<isize>) {
             let mut considered = BTreeSet::new();
-            let turn_a = (-direction.1, direction.0);
-            let turn_b = (direction.1, -direction.0);
+            let turn_a = direction.rotate_right();
+            let turn_b = direction.rotate_left();
             for &c in self.0.iter() {
-                if considered.insert(c) && !self.0.contains(&(c.0 + direction.0, c.1 + direction.1))
-                {
+                if considered.insert(c) && !self.0.contains(&(c + direction)) {
                     for &turn in &[turn_a, turn_b] {
                         for i in 0.. {
-                            let neighbour = (c.0 + turn.0 * i, c.1 + turn.1 * i);
+                            let neighbour = c + turn * i;
                             if !self.0.contains(&neighbour) {
                                 break;
                             }
-                            if self
-                                .0
-                                .contains(&(neighbour.0 + direction.0, neighbour.1 + direction.1))
-                            {
+                            if self.0.contains(&(neighbour + direction)) {
                                 break;
                             }
                             considered.insert(neighbour);
@@ -96,16 +85,32 @@ impl Plot {
     }
 }
 
+impl Solution for GardenMap {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(Grid::from_chars(input)?))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let plots = self.plots();
+        Ok(plots.iter().map(|p| p.area() * p.perimeter()).sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let plots = self.plots();
+        Ok(plots.iter().map(|p| p.area() * p.number_of_sides()).sum())
+    }
+}
+
 pub fn part_1(input: &str) -> usize {
-    let garden = GardenMap::parse(input);
-    let plots = garden.plots();
-    plots.iter().map(|p| p.area() * p.perimeter()).sum()
+    aoc_core::part1::<GardenMap>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let garden = GardenMap::parse(input);
-    let plots = garden.plots();
-    plots.iter().map(|p| p.area() * p.number_of_sides()).sum()
+    aoc_core::part2::<GardenMap>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -120,8 +125,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 1485656);
+        let input = aoc_input::challenge(2024, 12);
+        assert_eq!(part_1(&input), 1485656);
     }
 
     #[test]
@@ -134,7 +141,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 899196);
+        let input = aoc_input::challenge(2024, 12);
+        assert_eq!(part_2(&input), 899196);
     }
 }
@@ -1,3 +1,5 @@
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
 use nom::{
     bytes::complete::tag,
     character::complete::digit1,
@@ -9,10 +11,9 @@ use nom::{
 
 type Num = i64;
 
-#[derive(Debug)]
-struct Coord([Num; 2]);
+type Coord = Vec2<Num>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct ClawMachine {
     button_a: Coord,
     button_b: Coord,
@@ -21,19 +22,12 @@ struct ClawMachine {
 
 impl ClawMachine {
     fn tokens_for_prize(&self) -> Option<Num> {
-        let b_presses = Num::checked_div(
-            self.button_a.0[0] * self.prize.0[1] - self.button_a.0[1] * self.prize.0[0],
-            self.button_a.0[0] * self.button_b.0[1] - self.button_a.0[1] * self.button_b.0[0],
-        )?;
-
-        let a_presses = Num::checked_div(
-            self.prize.0[0] - b_presses * self.button_b.0[0],
-            self.button_a.0[0],
-        )?;
-
-        if a_presses * self.button_a.0[0] + b_presses * self.button_b.0[0] != self.prize.0[0]
-            || a_presses * self.button_a.0[1] + b_presses * self.button_b.0[1] != self.prize.0[1]
-        {
+        let (a, b, prize) = (self.button_a, self.button_b, self.prize);
+        let b_presses = Num::checked_div(a.x * prize.y - a.y * prize.x, a.x * b.y - a.y * b.x)?;
+
+        let a_presses = Num::checked_div(prize.x - b_presses * b.x, a.x)?;
+
+        if a * a_presses + b * b_presses != prize {
             return None;
         }
 
@@ -41,53 +35,65 @@ impl ClawMachine {
     }
 }
 
-fn parse_claw_machines(input: &str) -> Vec<ClawMachine> {
-    let raw: IResult<&str, Vec<ClawMachine>> = separated_list1(
-        tag("\n\n"),
-        map(
-            tuple((
-                tag("Button A: X+"),
-                map_res(digit1, str::parse),
-                tag(", Y+"),
-                map_res(digit1, str::parse),
-                tag("\nButton B: X+"),
-                map_res(digit1, str::parse),
-                tag(", Y+"),
-                map_res(digit1, str::parse),
-                tag("\nPrize: X="),
-                map_res(digit1, str::parse),
-                tag(", Y="),
-                map_res(digit1, str::parse),
-            )),
-            |(_, ax, _, ay, _, bx, _, by, _, px, _, py)| ClawMachine {
-                button_a: Coord([ax, ay]),
-                button_b: Coord([bx, by]),
-                prize: Coord([px, py]),
-            },
-        ),
-    )(input);
-
-    raw.unwrap().1
+pub struct ClawMachines(Vec<ClawMachine>);
+
+impl Solution for ClawMachines {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<&str, Vec<ClawMachine>> = separated_list1(
+            tag("\n\n"),
+            map(
+                tuple((
+                    tag("Button A: X+"),
+                    map_res(digit1, str::parse),
+                    tag(", Y+"),
+                    map_res(digit1, str::parse),
+                    tag("\nButton B: X+"),
+                    map_res(digit1, str::parse),
+                    tag(", Y+"),
+                    map_res(digit1, str::parse),
+                    tag("\nPrize: X="),
+                    map_res(digit1, str::parse),
+                    tag(", Y="),
+                    map_res(digit1, str::parse),
+                )),
+                |(_, ax, _, ay, _, bx, _, by, _, px, _, py)| ClawMachine {
+                    button_a: Coord::new(ax, ay),
+                    button_b: Coord::new(bx, by),
+                    prize: Coord::new(px, py),
+                },
+            ),
+        )(input);
+
+        let claw_machines = aoc_core::finish(input, raw)?;
+        Ok(Self(claw_machines))
+    }
+
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self.0.iter().filter_map(|m| m.tokens_for_prize()).sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        let mut claw_machines = self.0.clone();
+        for claw_machine in claw_machines.iter_mut() {
+            claw_machine.prize += Coord::new(10000000000000, 10000000000000);
+        }
+        Ok(claw_machines
+            .iter()
+            .filter_map(|m| m.tokens_for_prize())
+            .sum())
+    }
 }
 
 pub fn part_1(input: &str) -> Num {
-    let claw_machines = parse_claw_machines(input);
-    claw_machines
-        .iter()
-        .filter_map(|m| m.tokens_for_prize())
-        .sum()
+    aoc_core::part1::<ClawMachines>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> Num {
-    let mut claw_machines = parse_claw_machines(input);
-    for claw_machine in claw_machines.iter_mut() {
-        claw_machine.prize.0[0] += 10000000000000;
-        claw_machine.prize.0[1] += 10000000000000;
-    }
-    claw_machines
-        .iter()
-        .filter_map(|m| m.tokens_for_prize())
-        .sum()
+    aoc_core::part2::<ClawMachines>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -100,12 +106,16 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 28753);
+        let input = aoc_input::challenge(2024, 13);
+        assert_eq!(part_1(&input), 28753);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 102718967795500);
+        let input = aoc_input::challenge(2024, 13);
+        assert_eq!(part_2(&input), 102718967795500);
     }
 }
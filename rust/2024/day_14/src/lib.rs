@@ -1,5 +1,9 @@
 use std::iter::repeat_n;
 
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
+use aoc_grid::Grid;
+use aoc_render::{Animate, Backend, Cell, Colour, Raster, Render};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, newline},
@@ -10,9 +14,9 @@ use nom::{
 };
 
 type Num = i64;
-type Coord = [Num; 2];
+pub type Coord = Vec2<Num>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Robot {
     position: Coord,
     velocity: Coord,
@@ -34,10 +38,11 @@ impl Robot {
     }
 
     fn step(&mut self, world_size: Coord) {
-        self.position = [
-            Num::rem_euclid(self.position[0] + self.velocity[0], world_size[0]),
-            Num::rem_euclid(self.position[1] + self.velocity[1], world_size[1]),
-        ];
+        let Coord { x, y } = self.position + self.velocity;
+        self.position = Coord::new(
+            Num::rem_euclid(x, world_size.x),
+            Num::rem_euclid(y, world_size.y),
+        );
     }
 }
 
@@ -53,20 +58,11 @@ fn parse_coord<'a>(
 ) -> impl FnMut(&'a str) -> Result<(&'a str, Coord), nom::Err<nom::error::Error<&'a str>>> {
     map(
         separated_pair(parse_num(), char(','), parse_num()),
-        |(a, b)| [a, b],
+        |(x, y)| Coord::new(x, y),
     )
 }
 
 impl World {
-    fn parse(size: Coord, input: &str) -> Self {
-        let raw: IResult<_, _> = separated_list1(newline, Robot::parse())(input);
-
-        Self {
-            size,
-            robots: raw.unwrap().1,
-        }
-    }
-
     fn step(&mut self) {
         for robot in self.robots.iter_mut() {
             robot.step(self.size);
@@ -75,13 +71,12 @@ impl World {
 
     fn safety_factor(&self) -> usize {
         let mut product = 1;
-        for x_range in [0..self.size[0] / 2, self.size[0] / 2 + 1..self.size[0]] {
-            for y_range in [0..self.size[1] / 2, self.size[1] / 2 + 1..self.size[1]] {
+        for x_range in [0..self.size.x / 2, self.size.x / 2 + 1..self.size.x] {
+            for y_range in [0..self.size.y / 2, self.size.y / 2 + 1..self.size.y] {
                 let mut count = 0;
 
                 for robot in self.robots.iter() {
-                    if x_range.contains(&robot.position[0]) && y_range.contains(&robot.position[1])
-                    {
+                    if x_range.contains(&robot.position.x) && y_range.contains(&robot.position.y) {
                         count += 1;
                     }
                 }
@@ -93,33 +88,14 @@ impl World {
         product
     }
 
-    fn draw(&self) {
-        let width = self.size[0];
-        let height = self.size[1];
-        let mut bitmap: Vec<usize> = repeat_n(0, (width * height) as usize).collect();
-        for robot in self.robots.iter() {
-            bitmap[(robot.position[0] + robot.position[1] * width) as usize] += 1
-        }
-
-        for (&b, i) in bitmap.iter().zip(0..) {
-            if b == 0 {
-                print!(" ");
-            } else {
-                print!("{}", b);
-            }
-
-            if i % width == width - 1 {
-                println!();
-            }
-        }
-    }
-
     fn has_line_of_robots(&self) -> bool {
-        let width = self.size[0];
-        let height = self.size[1];
+        let Coord {
+            x: width,
+            y: height,
+        } = self.size;
         let mut bitmap: Vec<usize> = repeat_n(0, (width * height) as usize).collect();
         for robot in self.robots.iter() {
-            bitmap[(robot.position[0] + robot.position[1] * width) as usize] += 1
+            bitmap[(robot.position.x + robot.position.y * width) as usize] += 1
         }
 
         let mut uninterupted_line = 0;
@@ -138,27 +114,103 @@ impl World {
     }
 }
 
-pub fn part_1(size: Coord, input: &str) -> usize {
-    let mut world = World::parse(size, input);
+/// How many robots are on each tile, as the puzzle draws them.
+impl Render for World {
+    fn render(&self) -> Raster {
+        let mut counts = Grid::filled(self.size.x as usize, self.size.y as usize, 0);
+        for robot in self.robots.iter() {
+            counts[Vec2::new(robot.position.x as isize, robot.position.y as isize)] += 1;
+        }
+        counts.map(|&count| match char::from_digit(count, 10) {
+            Some('0') => Cell::new('.', Colour::BLACK),
+            Some(digit) => Cell::new(digit, Colour::new(0, 200, 0)),
+            None => Cell::new('*', Colour::new(0, 200, 0)),
+        })
+    }
+}
 
-    for _ in 0..100 {
-        world.step();
+pub struct Robots(Vec<Robot>);
+
+impl Robots {
+    fn world(&self, params: &Params) -> World {
+        World {
+            size: params.size,
+            robots: self.0.clone(),
+        }
     }
+}
 
-    world.safety_factor()
+pub struct Params {
+    /// The width and height of the space the robots move around.
+    pub size: Coord,
 }
 
-pub fn part_2(size: Coord, input: &str) -> usize {
-    let mut world = World::parse(size, input);
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            size: Coord::new(101, 103),
+        }
+    }
+}
+
+impl Solution for Robots {
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let raw: IResult<_, _> = separated_list1(newline, Robot::parse())(input);
+
+        let robots = aoc_core::finish(input, raw)?;
+        Ok(Self(robots))
+    }
+
+    fn part1(&self, params: &Params) -> Result<usize, Error> {
+        let mut world = self.world(params);
+
+        for _ in 0..100 {
+            world.step();
+        }
+
+        Ok(world.safety_factor())
+    }
+
+    fn part2(&self, params: &Params) -> Result<usize, Error> {
+        let mut world = self.world(params);
 
-    for i in 0.. {
-        if world.has_line_of_robots() {
-            world.draw();
-            return i;
+        for i in 0.. {
+            if world.has_line_of_robots() {
+                return Ok(i);
+            }
+            world.step();
         }
-        world.step();
+        Err(Error::no_answer("the robots never line up"))
     }
-    0
+}
+
+/// The robots of part two, a frame per second until they draw the tree.
+impl Animate for Robots {
+    fn animate(&self, backend: &mut dyn Backend) -> std::io::Result<()> {
+        let mut world = self.world(&Params::default());
+        backend.frame(&world.render())?;
+        while !world.has_line_of_robots() {
+            world.step();
+            backend.frame(&world.render())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn part_1(size: Coord, input: &str) -> usize {
+    Robots::parse(input)
+        .and_then(|robots| robots.part1(&Params { size }))
+        .unwrap()
+}
+
+pub fn part_2(size: Coord, input: &str) -> usize {
+    Robots::parse(input)
+        .and_then(|robots| robots.part2(&Params { size }))
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -167,16 +219,42 @@ mod tests {
 
     #[test]
     fn example_part_1() {
-        assert_eq!(part_1([11, 7], include_str!("../example_1.txt")), 12);
+        assert_eq!(
+            part_1(Coord::new(11, 7), include_str!("../example_1.txt")),
+            12
+        );
+    }
+
+    #[test]
+    fn example_drawing() {
+        let world = Robots::parse(include_str!("../example_1.txt"))
+            .unwrap()
+            .world(&Params {
+                size: Coord::new(11, 7),
+            });
+        assert_eq!(
+            world.render().to_string(),
+            "1.12.......\n\
+             ...........\n\
+             ...........\n\
+             ......11.11\n\
+             1.1........\n\
+             .........1.\n\
+             .......1...\n"
+        );
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1([101, 103], include_str!("../input.txt")), 215476074);
+        let input = aoc_input::challenge(2024, 14);
+        assert_eq!(part_1(Coord::new(101, 103), &input), 215476074);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2([101, 103], include_str!("../input.txt")), 6285);
+        let input = aoc_input::challenge(2024, 14);
+        assert_eq!(part_2(Coord::new(101, 103), &input), 6285);
     }
 }
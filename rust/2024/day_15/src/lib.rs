@@ -1,8 +1,54 @@
+use aoc_core::{Error, Solution};
+use aoc_render::{Animate, Backend};
+
 mod part_1;
 mod part_2;
 
-pub use part_1::part_1;
-pub use part_2::part_2;
+type Num = isize;
+
+/// The same warehouse read twice: once as written and once with everything
+/// but the robot twice as wide.
+#[derive(Debug)]
+pub struct Warehouses {
+    narrow: part_1::Puzzle,
+    wide: part_2::Puzzle,
+}
+
+impl Solution for Warehouses {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            narrow: part_1::Puzzle::new_from_input(input)?,
+            wide: part_2::Puzzle::new_from_input(input)?,
+        })
+    }
+
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self.narrow.clone().gps_sum_after_moves())
+    }
+
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        Ok(self.wide.clone().gps_sum_after_moves())
+    }
+}
+
+/// The robot of part two pushing the wide boxes about, a frame per move.
+impl Animate for Warehouses {
+    fn animate(&self, backend: &mut dyn Backend) -> std::io::Result<()> {
+        self.wide.clone().animate(backend)
+    }
+}
+
+pub fn part_1(input: &str) -> Num {
+    aoc_core::part1::<Warehouses>(input).unwrap()
+}
+
+pub fn part_2(input: &str) -> Num {
+    aoc_core::part2::<Warehouses>(input).unwrap()
+}
 
 #[cfg(test)]
 mod tests {
@@ -15,8 +61,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 1412971);
+        let input = aoc_input::challenge(2024, 15);
+        assert_eq!(part_1(&input), 1412971);
     }
 
     #[test]
@@ -25,7 +73,39 @@ mod tests {
     }
 
     #[test]
+    fn example_animation() {
+        let mut frames = Vec::new();
+        Warehouses::parse(include_str!("../example_3.txt"))
+            .unwrap()
+            .animate(&mut frames)
+            .unwrap();
+        assert_eq!(frames.len(), 12);
+        assert_eq!(
+            frames[0].to_string(),
+            "##############\n\
+             ##......##..##\n\
+             ##..........##\n\
+             ##....[][]@.##\n\
+             ##....[]....##\n\
+             ##..........##\n\
+             ##############\n"
+        );
+        assert_eq!(
+            frames[11].to_string(),
+            "##############\n\
+             ##...[].##..##\n\
+             ##...@.[]...##\n\
+             ##....[]....##\n\
+             ##..........##\n\
+             ##..........##\n\
+             ##############\n"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 1429299);
+        let input = aoc_input::challenge(2024, 15);
+        assert_eq!(part_2(&input), 1429299);
     }
 }
@@ -1,8 +1,7 @@
-use std::{
-    collections::BTreeSet,
-    ops::{Add, AddAssign, Mul},
-};
+use std::collections::BTreeSet;
 
+use aoc_core::ParseError;
+use aoc_geometry::{Direction, Vec2};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until1},
@@ -14,32 +13,9 @@ use nom::{
 
 type Num = isize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Coord([Num; 2]);
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
-    }
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
-    }
-}
-
-impl Mul<Num> for Coord {
-    type Output = Self;
+type Coord = Vec2<Num>;
 
-    fn mul(self, other: Num) -> Self {
-        Self([self.0[0] * other, self.0[1] * other])
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Warehouse {
     robot: Coord,
     walls: BTreeSet<Coord>,
@@ -51,14 +27,14 @@ impl Warehouse {
     ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
         map(take_until1("\n\n"), |input: &'a str| {
             let mut warehouse = Warehouse {
-                robot: Coord([0, 0]),
+                robot: Coord::ZERO,
                 walls: Default::default(),
                 boxes: Default::default(),
             };
 
             for (line, y) in input.lines().zip(0..) {
                 for (c, x) in line.chars().zip(0..) {
-                    let coord = Coord([x, y]);
+                    let coord = Coord::new(x, y);
                     match c {
                         '#' => {
                             warehouse.walls.insert(coord);
@@ -98,11 +74,11 @@ impl Warehouse {
     }
 
     fn gps_sum(&self) -> Num {
-        self.boxes.iter().map(|b| b.0[0] + 100 * b.0[1]).sum()
+        self.boxes.iter().map(|b| b.x + 100 * b.y).sum()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Moves(Vec<Coord>);
 
 impl Moves {
@@ -111,10 +87,10 @@ impl Moves {
         map(
             many1(terminated(
                 alt((
-                    map(char('<'), |_| Coord([-1, 0])),
-                    map(char('>'), |_| Coord([1, 0])),
-                    map(char('^'), |_| Coord([0, -1])),
-                    map(char('v'), |_| Coord([0, 1])),
+                    map(char('<'), |_| Direction::West.offset()),
+                    map(char('>'), |_| Direction::East.offset()),
+                    map(char('^'), |_| Direction::North.offset()),
+                    map(char('v'), |_| Direction::South.offset()),
                 )),
                 opt(newline),
             )),
@@ -123,8 +99,8 @@ impl Moves {
     }
 }
 
-#[derive(Debug)]
-struct Puzzle {
+#[derive(Debug, Clone)]
+pub(crate) struct Puzzle {
     moves: Moves,
     warehouse: Warehouse,
 }
@@ -138,15 +114,14 @@ impl Puzzle {
         )
     }
 
-    fn new_from_input(input: &str) -> Self {
-        Self::parse()(input).unwrap().1
+    pub(crate) fn new_from_input(input: &str) -> Result<Self, ParseError> {
+        aoc_core::finish(input, Self::parse()(input))
     }
-}
 
-pub fn part_1(input: &str) -> Num {
-    let mut puzzle = Puzzle::new_from_input(input);
-    for &m in puzzle.moves.0.iter() {
-        puzzle.warehouse.move_robot(m);
+    pub(crate) fn gps_sum_after_moves(mut self) -> Num {
+        for &m in self.moves.0.iter() {
+            self.warehouse.move_robot(m);
+        }
+        self.warehouse.gps_sum()
     }
-    puzzle.warehouse.gps_sum()
 }
@@ -1,8 +1,9 @@
-use std::{
-    collections::BTreeSet,
-    ops::{Add, AddAssign, Mul},
-};
+use std::collections::BTreeSet;
+use std::io;
 
+use aoc_core::ParseError;
+use aoc_geometry::{Direction, Vec2};
+use aoc_render::{Backend, Cell, Colour, Raster, Render};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until1},
@@ -14,32 +15,9 @@ use nom::{
 
 type Num = isize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Coord([Num; 2]);
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
-    }
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
-    }
-}
-
-impl Mul<Num> for Coord {
-    type Output = Self;
-
-    fn mul(self, other: Num) -> Self {
-        Self([self.0[0] * other, self.0[1] * other])
-    }
-}
+type Coord = Vec2<Num>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Warehouse {
     robot: Coord,
     walls: BTreeSet<Coord>,
@@ -51,18 +29,18 @@ impl Warehouse {
     ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
         map(take_until1("\n\n"), |input: &'a str| {
             let mut warehouse = Warehouse {
-                robot: Coord([0, 0]),
+                robot: Coord::ZERO,
                 walls: Default::default(),
                 boxes: Default::default(),
             };
 
             for (line, y) in input.lines().zip(0..) {
                 for (c, x) in line.chars().zip(0..) {
-                    let coord = Coord([x * 2, y]);
+                    let coord = Coord::new(x * 2, y);
                     match c {
                         '#' => {
                             warehouse.walls.insert(coord);
-                            warehouse.walls.insert(coord + Coord([1, 0]));
+                            warehouse.walls.insert(coord + Direction::East.offset());
                         }
                         'O' => {
                             warehouse.boxes.insert(coord);
@@ -99,10 +77,10 @@ impl Warehouse {
                 return MoveInto::Wall;
             }
 
-            let next_c_left_offset = next_c + Coord([-1, 0]);
-            let next_c_right_offset = next_c + Coord([1, 0]);
+            let next_c_left_offset = next_c + Direction::West.offset();
+            let next_c_right_offset = next_c + Direction::East.offset();
 
-            if direction == Coord([1, 0]) && warehouse.boxes.contains(&next_c) {
+            if direction == Direction::East.offset() && warehouse.boxes.contains(&next_c) {
                 boxes_to_move.insert(next_c);
                 return move_robot_internal(
                     warehouse,
@@ -111,7 +89,9 @@ impl Warehouse {
                     boxes_to_move,
                 );
             }
-            if direction == Coord([-1, 0]) && warehouse.boxes.contains(&next_c_left_offset) {
+            if direction == Direction::West.offset()
+                && warehouse.boxes.contains(&next_c_left_offset)
+            {
                 boxes_to_move.insert(next_c_left_offset);
                 return move_robot_internal(
                     warehouse,
@@ -164,11 +144,32 @@ impl Warehouse {
     }
 
     fn gps_sum(&self) -> Num {
-        self.boxes.iter().map(|b| b.0[0] + 100 * b.0[1]).sum()
+        self.boxes.iter().map(|b| b.x + 100 * b.y).sum()
+    }
+}
+
+impl Render for Warehouse {
+    fn render(&self) -> Raster {
+        let size = self.walls.iter().fold(Coord::ZERO, |size, wall| {
+            Coord::new(size.x.max(wall.x + 1), size.y.max(wall.y + 1))
+        });
+        Raster::from_fn(size.x as usize, size.y as usize, |p| {
+            if p == self.robot {
+                Cell::new('@', Colour::new(255, 0, 0))
+            } else if self.walls.contains(&p) {
+                Cell::new('#', Colour::new(128, 128, 128))
+            } else if self.boxes.contains(&p) {
+                Cell::new('[', Colour::new(160, 110, 50))
+            } else if self.boxes.contains(&(p + Direction::West.offset())) {
+                Cell::new(']', Colour::new(160, 110, 50))
+            } else {
+                Cell::new('.', Colour::BLACK)
+            }
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Moves(Vec<Coord>);
 
 impl Moves {
@@ -177,10 +178,10 @@ impl Moves {
         map(
             many1(terminated(
                 alt((
-                    map(char('<'), |_| Coord([-1, 0])),
-                    map(char('>'), |_| Coord([1, 0])),
-                    map(char('^'), |_| Coord([0, -1])),
-                    map(char('v'), |_| Coord([0, 1])),
+                    map(char('<'), |_| Direction::West.offset()),
+                    map(char('>'), |_| Direction::East.offset()),
+                    map(char('^'), |_| Direction::North.offset()),
+                    map(char('v'), |_| Direction::South.offset()),
                 )),
                 opt(newline),
             )),
@@ -189,8 +190,8 @@ impl Moves {
     }
 }
 
-#[derive(Debug)]
-struct Puzzle {
+#[derive(Debug, Clone)]
+pub(crate) struct Puzzle {
     moves: Moves,
     warehouse: Warehouse,
 }
@@ -204,15 +205,24 @@ impl Puzzle {
         )
     }
 
-    fn new_from_input(input: &str) -> Self {
-        Self::parse()(input).unwrap().1
+    pub(crate) fn new_from_input(input: &str) -> Result<Self, ParseError> {
+        aoc_core::finish(input, Self::parse()(input))
     }
-}
 
-pub fn part_2(input: &str) -> Num {
-    let mut puzzle = Puzzle::new_from_input(input);
-    for &m in puzzle.moves.0.iter() {
-        puzzle.warehouse.move_robot(m);
+    /// A frame before the first move and after each one.
+    pub(crate) fn animate(mut self, backend: &mut dyn Backend) -> io::Result<()> {
+        backend.frame(&self.warehouse.render())?;
+        for &m in self.moves.0.iter() {
+            self.warehouse.move_robot(m);
+            backend.frame(&self.warehouse.render())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn gps_sum_after_moves(mut self) -> Num {
+        for &m in self.moves.0.iter() {
+            self.warehouse.move_robot(m);
+        }
+        self.warehouse.gps_sum()
     }
-    puzzle.warehouse.gps_sum()
 }
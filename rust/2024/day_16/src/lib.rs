@@ -1,76 +1,63 @@
-use std::{
-    cmp::{Ordering, Reverse},
-    collections::{btree_map::Entry, BTreeMap, BTreeSet, BinaryHeap},
-    ops::Add,
-};
+use std::collections::{BTreeSet, HashSet};
 
-#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-struct Coord {
-    x: isize,
-    y: isize,
-}
+use aoc_core::{Error, Solution};
+use aoc_geometry::{Direction, Vec2};
+use aoc_search::Shortest;
 
-impl Coord {
-    fn rotate_right(&self) -> Self {
-        Self {
-            x: -self.y,
-            y: self.x,
-        }
-    }
+type Coord = Vec2<isize>;
 
-    fn rotate_left(&self) -> Self {
-        Self {
-            x: self.y,
-            y: -self.x,
-        }
-    }
-}
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-#[derive(Debug, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Ord, PartialOrd, PartialEq, Eq, Hash, Clone, Copy)]
 struct Node {
     position: Coord,
-    direction: Coord,
+    direction: Direction,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct HeapData {
-    score: isize,
-    node: Node,
+pub struct Maze {
+    start: Coord,
+    end: Coord,
+    walls: BTreeSet<Coord>,
 }
 
-impl PartialOrd for HeapData {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Maze {
+    /// Turning costs 1000 and stepping forward 1.
+    fn moves(&self, node: &Node) -> impl Iterator<Item = (Node, isize)> {
+        let turns = [node.direction.turn_right(), node.direction.turn_left()].map(|direction| {
+            (
+                Node {
+                    position: node.position,
+                    direction,
+                },
+                1000,
+            )
+        });
+        let forward = node.position + node.direction.offset();
+        let step = (!self.walls.contains(&forward)).then_some((
+            Node {
+                position: forward,
+                ..*node
+            },
+            1,
+        ));
+        turns.into_iter().chain(step)
     }
-}
 
-impl Ord for HeapData {
-    fn cmp(&self, other: &Self) -> Ordering {
-        Reverse(self.score)
-            .cmp(&Reverse(other.score))
-            .then(self.node.cmp(&other.node))
+    fn best_paths(&self) -> Result<Shortest<Node, isize>, Error> {
+        aoc_search::dijkstra(|node| self.moves(node))
+            .start(Node {
+                position: self.start,
+                direction: Direction::East,
+            })
+            .shortest(|node| node.position == self.end)
+            .ok_or_else(|| Error::no_answer("the end can't be reached"))
     }
 }
 
-struct Maze {
-    start: Coord,
-    end: Coord,
-    walls: BTreeSet<Coord>,
-}
+impl Solution for Maze {
+    type Params = ();
+    type Part1 = isize;
+    type Part2 = usize;
 
-impl Maze {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, Error> {
         let mut start = Coord::default();
         let mut end = Coord::default();
         let mut walls = BTreeSet::new();
@@ -91,159 +78,31 @@ impl Maze {
                 }
             }
         }
-        Self { start, end, walls }
-    }
-
-    fn lowest_score(&self) -> isize {
-        let mut visited = BTreeSet::new();
-
-        let mut unvisited = BinaryHeap::<HeapData>::new();
-        unvisited.push(HeapData {
-            score: 0,
-            node: Node {
-                position: self.start,
-                direction: Coord { x: 1, y: 0 },
-            },
-        });
-
-        while let Some(candidate) = unvisited.pop() {
-            if candidate.node.position == self.end {
-                return candidate.score;
-            }
-
-            if !visited.insert(candidate.node) {
-                continue;
-            }
-
-            for n in candidate.edges(self) {
-                unvisited.push(n);
-            }
-        }
-
-        0
+        Ok(Self { start, end, walls })
     }
 
-    fn seats(&self) -> usize {
-        let mut shortest_path_from_start = BTreeMap::<Node, isize>::new();
-
-        let mut unvisited = BinaryHeap::<HeapData>::new();
-        unvisited.push(HeapData {
-            score: 0,
-            node: Node {
-                position: self.start,
-                direction: Coord { x: 1, y: 0 },
-            },
-        });
-
-        while let Some(candidate) = unvisited.pop() {
-            match shortest_path_from_start.entry(candidate.node) {
-                Entry::Vacant(vacant_entry) => vacant_entry.insert(candidate.score),
-                Entry::Occupied(_) => {
-                    continue;
-                }
-            };
-            if candidate.node.position == self.end {
-                break;
-            }
-
-            for n in candidate.edges(self) {
-                unvisited.push(n);
-            }
-        }
-        let mut shortest_path_from_end = BTreeMap::<Node, isize>::new();
-        {
-            let mut direction = Coord { x: 1, y: 0 };
-            for _ in 0..4 {
-                unvisited.push(HeapData {
-                    score: 0,
-                    node: Node {
-                        position: self.end,
-                        direction,
-                    },
-                });
-                direction = direction.rotate_right();
-            }
-        }
-
-        while let Some(candidate) = unvisited.pop() {
-            match shortest_path_from_end.entry(candidate.node) {
-                Entry::Vacant(vacant_entry) => vacant_entry.insert(candidate.score),
-                Entry::Occupied(_) => {
-                    continue;
-                }
-            };
-            if candidate.node.position == self.start {
-                break;
-            }
-
-            for n in candidate.edges(self) {
-                unvisited.push(n);
-            }
-        }
-
-        let mut on_shortest_path = BTreeSet::new();
-
-        let &shortest_path = shortest_path_from_start
-            .iter()
-            .find_map(|(node, d)| (node.position == self.end).then_some(d))
-            .unwrap();
-        for (node, &d) in shortest_path_from_start.iter() {
-            match shortest_path_from_end.get(&Node {
-                position: node.position,
-                direction: node.direction.rotate_right().rotate_right(),
-            }) {
-                None => {}
-                Some(od) => {
-                    if od + d == shortest_path {
-                        on_shortest_path.insert(node.position);
-                    }
-                }
-            }
-        }
-        on_shortest_path.len()
+    fn part1(&self, _: &()) -> Result<isize, Error> {
+        Ok(self.best_paths()?.cost)
     }
-}
 
-impl HeapData {
-    fn edges<'a>(&'a self, maze: &'a Maze) -> impl Iterator<Item = HeapData> + 'a {
-        (0..3).filter_map(|i| match i {
-            0 => Some(HeapData {
-                score: 1000 + self.score,
-                node: Node {
-                    position: self.node.position,
-                    direction: self.node.direction.rotate_right(),
-                },
-            }),
-            1 => Some(HeapData {
-                score: 1000 + self.score,
-                node: Node {
-                    position: self.node.position,
-                    direction: self.node.direction.rotate_left(),
-                },
-            }),
-            2 => {
-                let forward = self.node.position + self.node.direction;
-                (!maze.walls.contains(&forward)).then(|| HeapData {
-                    score: 1 + self.score,
-                    node: Node {
-                        position: forward,
-                        direction: self.node.direction,
-                    },
-                })
-            }
-            _ => None,
-        })
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        let best_paths = self.best_paths()?;
+        let seats: HashSet<Coord> = best_paths
+            .paths
+            .on_paths_to(best_paths.ends)
+            .into_iter()
+            .map(|node| node.position)
+            .collect();
+        Ok(seats.len())
     }
 }
 
 pub fn part_1(input: &str) -> isize {
-    let maze = Maze::parse(input);
-    maze.lowest_score()
+    aoc_core::part1::<Maze>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> usize {
-    let maze = Maze::parse(input);
-    maze.seats()
+    aoc_core::part2::<Maze>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -257,8 +116,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 114476);
+        let input = aoc_input::challenge(2024, 16);
+        assert_eq!(part_1(&input), 114476);
     }
 
     #[test]
@@ -268,7 +129,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 508);
+        let input = aoc_input::challenge(2024, 16);
+        assert_eq!(part_2(&input), 508);
     }
 }
@@ -0,0 +1,132 @@
+use std::fmt;
+
+use crate::Num;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    fn from_num(num: Num) -> Option<Self> {
+        Some(match num {
+            0 => Self::Adv,
+            1 => Self::Bxl,
+            2 => Self::Bst,
+            3 => Self::Jnz,
+            4 => Self::Bxc,
+            5 => Self::Out,
+            6 => Self::Bdv,
+            7 => Self::Cdv,
+            _ => return None,
+        })
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Adv => "adv",
+            Self::Bxl => "bxl",
+            Self::Bst => "bst",
+            Self::Jnz => "jnz",
+            Self::Bxc => "bxc",
+            Self::Out => "out",
+            Self::Bdv => "bdv",
+            Self::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand names a register above 3, rather than being the number itself.
+    fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Self::Adv | Self::Bst | Self::Out | Self::Bdv | Self::Cdv
+        )
+    }
+}
+
+/// An opcode and its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: Num,
+}
+
+impl Instruction {
+    /// The instruction at the start of `code`, if there's an opcode and operand there.
+    pub fn decode(code: &[Num]) -> Option<Self> {
+        match *code {
+            [opcode, operand, ..] => Some(Self {
+                opcode: Opcode::from_num(opcode)?,
+                operand,
+            }),
+            _ => None,
+        }
+    }
+
+    fn combo(&self) -> Combo {
+        Combo(self.operand)
+    }
+
+    /// What the instruction does, written out.
+    pub fn meaning(&self) -> String {
+        match self.opcode {
+            Opcode::Adv => format!("A = A >> {}", self.combo()),
+            Opcode::Bxl => format!("B = B ^ {}", self.operand),
+            Opcode::Bst => format!("B = {} % 8", self.combo()),
+            Opcode::Jnz => format!("if A != 0 jump to {}", self.operand),
+            Opcode::Bxc => "B = B ^ C".to_string(),
+            Opcode::Out => format!("output {} % 8", self.combo()),
+            Opcode::Bdv => format!("B = A >> {}", self.combo()),
+            Opcode::Cdv => format!("C = A >> {}", self.combo()),
+        }
+    }
+}
+
+/// A combo operand, shown as the register it reads if it reads one.
+struct Combo(Num);
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            4 => write!(f, "A"),
+            5 => write!(f, "B"),
+            6 => write!(f, "C"),
+            n => write!(f, "{n}"),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.opcode {
+            Opcode::Bxc => write!(f, "{}", self.opcode.mnemonic()),
+            opcode if opcode.takes_combo() => {
+                write!(f, "{} {}", opcode.mnemonic(), self.combo())
+            }
+            opcode => write!(f, "{} {}", opcode.mnemonic(), self.operand),
+        }
+    }
+}
+
+/// The program as assembly, one instruction a line, each with its address and what it does.
+pub fn disassemble(code: &[Num]) -> String {
+    code.chunks(2)
+        .enumerate()
+        .map(|(i, pair)| match Instruction::decode(pair) {
+            Some(instruction) => format!(
+                "{:>3}: {:<8}; {}\n",
+                i * 2,
+                instruction.to_string(),
+                instruction.meaning()
+            ),
+            None => format!("{:>3}: data {pair:?}\n", i * 2),
+        })
+        .collect()
+}
@@ -1,5 +1,9 @@
 type Num = i64;
 
+use std::fmt;
+
+use aoc_core::{Error, Solution};
+use instruction::{Instruction, Opcode};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1},
@@ -8,8 +12,13 @@ use nom::{
     sequence::tuple,
 };
 
+mod instruction;
+
+/// How many instructions a program may run before it's taken to never halt.
+const MAX_STEPS: usize = 1_000_000;
+
 #[derive(Debug, Clone)]
-struct Computer {
+pub struct Computer {
     registers: [Num; 3],
     code: Vec<Num>,
     instruction_pointer: usize,
@@ -44,116 +53,201 @@ impl Computer {
         )
     }
 
-    fn parse(input: &str) -> Self {
-        Self::parser()(input).unwrap().1
-    }
-
-    fn combo_operand(&self, operand: Num) -> Option<Num> {
+    fn combo_operand(&self, operand: Num) -> Result<Num, Error> {
         match operand {
-            0..=3 => Some(operand),
-            4..=6 => self
-                .registers
-                .get(usize::try_from(operand - 4).unwrap())
-                .copied(),
-            7 => None,
-            _ => None,
+            0..=3 => Ok(operand),
+            4..=6 => Ok(self.registers[(operand - 4) as usize]),
+            _ => Err(Error::no_answer(format!(
+                "combo operand {operand} at {} is reserved",
+                self.instruction_pointer + 1
+            ))),
         }
     }
 
-    fn compute(&mut self) -> Option<()> {
-        let &opcode = self.code.get(self.instruction_pointer)?;
-        let &operand = self.code.get(self.instruction_pointer + 1)?;
+    /// Register A divided by two to the power of the combo operand, rounding down.
+    fn divide_a(&self, operand: Num) -> Result<Num, Error> {
+        Ok(u32::try_from(self.combo_operand(operand)?)
+            .ok()
+            .and_then(|shift| self.registers[0].checked_shr(shift))
+            .unwrap_or(0))
+    }
+
+    /// The instruction that will run next, or `None` once the program has halted.
+    pub fn next_instruction(&self) -> Option<Instruction> {
+        Instruction::decode(self.code.get(self.instruction_pointer..)?)
+    }
 
+    fn compute(&mut self, Instruction { opcode, operand }: Instruction) -> Result<(), Error> {
         match opcode {
-            0 => {
-                self.registers[0] /= Num::pow(2, self.combo_operand(operand).unwrap() as u32);
+            Opcode::Adv => {
+                self.registers[0] = self.divide_a(operand)?;
             }
-            1 => {
+            Opcode::Bxl => {
                 self.registers[1] ^= operand;
             }
-            2 => {
-                self.registers[1] = self.combo_operand(operand).unwrap() % 8;
+            Opcode::Bst => {
+                self.registers[1] = self.combo_operand(operand)? % 8;
             }
-            3 => {
+            Opcode::Jnz => {
                 if self.registers[0] != 0 {
                     self.instruction_pointer = usize::try_from(operand).unwrap();
-                    return Some(());
+                    return Ok(());
                 }
             }
-            4 => {
+            Opcode::Bxc => {
                 self.registers[1] ^= self.registers[2];
             }
-            5 => {
-                self.output.push(self.combo_operand(operand).unwrap() % 8);
+            Opcode::Out => {
+                self.output.push(self.combo_operand(operand)? % 8);
             }
-            6 => {
-                self.registers[1] =
-                    self.registers[0] / Num::pow(2, self.combo_operand(operand).unwrap() as u32);
+            Opcode::Bdv => {
+                self.registers[1] = self.divide_a(operand)?;
             }
-            7 => {
-                self.registers[2] =
-                    self.registers[0] / Num::pow(2, self.combo_operand(operand).unwrap() as u32);
-            }
-            _ => {
-                panic!("unexected opcode");
+            Opcode::Cdv => {
+                self.registers[2] = self.divide_a(operand)?;
             }
         }
         self.instruction_pointer += 2;
-        Some(())
+        Ok(())
     }
-}
 
-pub fn part_1(input: &str) -> String {
-    let mut computer = Computer::parse(input);
-    while let Some(()) = computer.compute() {}
-
-    computer
-        .output
-        .iter()
-        .enumerate()
-        .map(|(i, &o)| {
-            if i == 0 {
-                format!("{}", o)
-            } else {
-                format!(",{}", o)
+    /// Runs the program until it halts or `done` says to stop, calling `watch` before each
+    /// instruction. Fails on a reserved combo operand, or once it's run [`MAX_STEPS`]
+    /// instructions.
+    fn run(
+        &mut self,
+        mut done: impl FnMut(&Self) -> bool,
+        mut watch: impl FnMut(&Step),
+    ) -> Result<(), Error> {
+        for _ in 0..MAX_STEPS {
+            let Some(instruction) = self.next_instruction() else {
+                return Ok(());
+            };
+            if done(self) {
+                return Ok(());
             }
-        })
-        .collect()
+            watch(&Step {
+                instruction_pointer: self.instruction_pointer,
+                registers: self.registers,
+                instruction,
+            });
+            self.compute(instruction)?;
+        }
+        Err(Error::no_answer(format!(
+            "the program didn't halt within {MAX_STEPS} instructions"
+        )))
+    }
+
+    /// Runs the program to the end, calling `watch` before each instruction.
+    pub fn trace(&mut self, watch: impl FnMut(&Step)) -> Result<(), Error> {
+        self.run(|_| false, watch)
+    }
+
+    /// The program as assembly.
+    pub fn disassemble(&self) -> String {
+        instruction::disassemble(&self.code)
+    }
+
+    /// What the program outputs when register A starts as `a`. Stops early once there's
+    /// more output than the program is long.
+    fn output_for(&self, a: Num) -> Result<Vec<Num>, Error> {
+        let mut computer = Self {
+            registers: [a, self.registers[1], self.registers[2]],
+            ..self.clone()
+        };
+        computer.run(|c| c.output.len() > c.code.len(), |_| {})?;
+        Ok(computer.output)
+    }
+
+    /// The lowest value of register A that makes the program output itself.
+    ///
+    /// Programs like this loop once per output, shifting three bits off A each time, so the
+    /// last output depends only on A's top three bits. A is built up three bits at a time
+    /// from the top, keeping values whose output matches the end of the program and
+    /// trying each next three bits in turn, lowest first.
+    pub fn quine(&self) -> Result<Option<Num>, Error> {
+        self.quine_from(0, self.code.len())
+    }
+
+    fn quine_from(&self, a: Num, matched: usize) -> Result<Option<Num>, Error> {
+        if matched == 0 {
+            return Ok(Some(a));
+        }
+        let Some(a) = a.checked_mul(8) else {
+            return Ok(None);
+        };
+        for a in a..a + 8 {
+            if a != 0 && self.output_for(a)? == self.code[matched - 1..] {
+                if let Some(quine) = self.quine_from(a, matched - 1)? {
+                    return Ok(Some(quine));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The state of the computer before an instruction runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub instruction_pointer: usize,
+    pub registers: [Num; 3],
+    pub instruction: Instruction,
 }
 
-fn computer(mut a: u64, buffer: &mut Vec<u64>) {
-    buffer.clear();
-    while a != 0 {
-        let b = (a % 8) ^ 1;
-        let c = a >> (b as u32);
-        a /= 8;
-        buffer.push((b ^ 4 ^ c) % 8);
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c] = self.registers;
+        write!(
+            f,
+            "{:>3}: {:<8}A={a:#o} B={b:#o} C={c:#o}",
+            self.instruction_pointer,
+            self.instruction.to_string()
+        )
     }
 }
 
-// Not a generalizable solution, but a scratchpad that got me my answer.
-//
-// observation that the partial solutions all ended in 0o25052
-// and used that to generate further digits quicker.
-// Next observation that the partial solutions ended in 0o37262025052.
-pub fn part_2() -> u64 {
-    let target = &[2, 4, 1, 1, 7, 5, 1, 4, 0, 3, 4, 5, 5, 5, 3, 0];
-    let mut buffer = Vec::with_capacity(16);
-
-    let mut buffer_len = 0;
-    for a in 0.. {
-        let a = a * 0o100000000000 + 0o37262025052;
-        computer(a, &mut buffer);
-        if buffer == target {
-            return a;
-        }
+impl Solution for Computer {
+    type Params = ();
+    type Part1 = String;
+    type Part2 = u64;
 
-        if buffer.len() > buffer_len && Some(buffer.as_slice()) == target.get(0..buffer.len()) {
-            buffer_len = buffer.len();
-            dbg!((a, format!("{a:o}"), format!("{a:b}"), &buffer));
-        }
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Self::parser()(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<String, Error> {
+        let mut computer = self.clone();
+        computer.run(|_| false, |_| {})?;
+
+        Ok(computer
+            .output
+            .iter()
+            .enumerate()
+            .map(|(i, &o)| {
+                if i == 0 {
+                    format!("{}", o)
+                } else {
+                    format!(",{}", o)
+                }
+            })
+            .collect())
     }
-    0
+
+    fn part2(&self, _: &()) -> Result<u64, Error> {
+        let a = self.quine()?.ok_or_else(|| {
+            Error::no_answer("no value of register A makes the program output itself")
+        })?;
+        Ok(u64::try_from(a).unwrap())
+    }
+}
+
+pub fn part_1(input: &str) -> String {
+    aoc_core::part1::<Computer>(input).unwrap()
+}
+
+pub fn part_2(input: &str) -> u64 {
+    aoc_core::part2::<Computer>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -169,17 +263,63 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), "5,1,4,0,5,1,0,2,6");
+        let input = aoc_input::challenge(2024, 17);
+        assert_eq!(part_1(&input), "5,1,4,0,5,1,0,2,6");
     }
 
-    // #[test]
-    // fn example_part_2() {
-    //     assert_eq!(part_2(include_str!("../example_2.txt")), 117440);
-    // }
+    #[test]
+    fn example_part_2() {
+        assert_eq!(part_2(include_str!("../example_2.txt")), 117440);
+    }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(), 202322936867370);
+        let input = aoc_input::challenge(2024, 17);
+        assert_eq!(part_2(&input), 202322936867370);
+    }
+
+    #[test]
+    fn disassembles() {
+        let computer = Computer::parse(include_str!("../example_2.txt")).unwrap();
+        assert_eq!(
+            computer.disassemble(),
+            "  0: adv 3   ; A = A >> 3\n  2: out A   ; output A % 8\n  4: jnz 0   ; if A != 0 jump to 0\n"
+        );
+    }
+
+    #[test]
+    fn traces() {
+        let mut computer = Computer::parse(include_str!("../example_2.txt")).unwrap();
+        computer.registers[0] = 0o12;
+        let mut steps = Vec::new();
+        computer.trace(|step| steps.push(step.to_string())).unwrap();
+        assert_eq!(
+            steps,
+            [
+                "  0: adv 3   A=0o12 B=0o0 C=0o0",
+                "  2: out A   A=0o1 B=0o0 C=0o0",
+                "  4: jnz 0   A=0o1 B=0o0 C=0o0",
+                "  0: adv 3   A=0o1 B=0o0 C=0o0",
+                "  2: out A   A=0o0 B=0o0 C=0o0",
+                "  4: jnz 0   A=0o0 B=0o0 C=0o0",
+            ]
+        );
+        assert_eq!(computer.output, [1, 0]);
+    }
+
+    #[test]
+    fn fails_on_programs_it_cannot_run() {
+        let reserved =
+            Computer::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7").unwrap();
+        assert!(matches!(reserved.part1(&()), Err(Error::NoAnswer(_))));
+        assert!(matches!(reserved.part2(&()), Err(Error::NoAnswer(_))));
+        let endless =
+            Computer::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,0,3,0")
+                .unwrap();
+        assert!(matches!(endless.part1(&()), Err(Error::NoAnswer(_))));
+        assert!(matches!(endless.part2(&()), Err(Error::NoAnswer(_))));
     }
 }
@@ -1,5 +1,7 @@
-use std::{collections::BTreeSet, ops::Add};
+use std::collections::BTreeSet;
 
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
 use nom::{
     character::complete::{char, digit1, newline},
     combinator::{map, map_res},
@@ -14,64 +16,39 @@ fn parser_num<'a>(
     map_res(digit1, str::parse)
 }
 
-#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub struct Coord {
-    x: Num,
-    y: Num,
-}
-impl Add<[Num; 2]> for &Coord {
-    type Output = Coord;
+pub type Coord = Vec2<Num>;
 
-    fn add(self, other: [Num; 2]) -> Coord {
-        Coord {
-            x: self.x + other[0],
-            y: self.y + other[1],
-        }
-    }
-}
-
-impl Coord {
-    fn parser<'a>(
-    ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
-        map(
-            separated_pair(parser_num(), char(','), parser_num()),
-            |(x, y)| Self { x, y },
-        )
-    }
-
-    fn neighbours(&self) -> [Self; 4] {
-        [self + [0, 1], self + [-1, 0], self + [1, 0], self + [0, -1]]
-    }
+fn parser_coord<'a>(
+) -> impl FnMut(&'a str) -> Result<(&'a str, Coord), nom::Err<nom::error::Error<&'a str>>> {
+    map(
+        separated_pair(parser_num(), char(','), parser_num()),
+        |(x, y)| Coord { x, y },
+    )
 }
 
 #[derive(Debug)]
-struct MazeInput {
+pub struct MazeInput {
     walls: Vec<Coord>,
 }
 
 impl MazeInput {
     fn parser<'a>(
     ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
-        map(separated_list1(newline, Coord::parser()), |walls| Self {
+        map(separated_list1(newline, parser_coord()), |walls| Self {
             walls,
         })
     }
-
-    fn parse(input: &str) -> Self {
-        Self::parser()(input).unwrap().1
-    }
 }
 
-struct Maze {
-    maze_input: MazeInput,
+struct Maze<'a> {
+    maze_input: &'a MazeInput,
     time: usize,
     walls: BTreeSet<Coord>,
     size: Coord,
 }
 
-impl Maze {
-    fn new(input: &str, size: Coord, time: usize) -> Self {
-        let maze_input = MazeInput::parse(input);
+impl<'a> Maze<'a> {
+    fn new(maze_input: &'a MazeInput, size: Coord, time: usize) -> Self {
         Self {
             walls: maze_input.walls.iter().copied().take(time).collect(),
             maze_input,
@@ -99,43 +76,68 @@ impl Maze {
     }
 
     fn time_to_end(&self) -> Option<usize> {
-        let mut visited = BTreeSet::new();
-        let mut to_visit_next = BTreeSet::new();
-        to_visit_next.insert(Coord { x: 0, y: 0 });
+        let exit = self.size - Coord::new(1, 1);
+        aoc_search::bfs(|c: &Coord| c.neighbours_4().into_iter().filter(|n| self.is_passable(n)))
+            .start(Coord::ZERO)
+            .shortest(|&c| c == exit)
+            .map(|shortest| shortest.cost)
+    }
+}
+
+pub struct Params {
+    pub size: Coord,
+    pub time: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            size: Coord { x: 71, y: 71 },
+            time: 1024,
+        }
+    }
+}
 
-        let mut step_count = 0;
+impl Solution for MazeInput {
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = Coord;
 
-        loop {
-            if to_visit_next.is_empty() {
-                return None;
-            }
-            let to_visit = std::mem::take(&mut to_visit_next);
-            for &c in to_visit.iter() {
-                if c.x == self.size.x - 1 && c.y == self.size.y - 1 {
-                    return Some(step_count);
-                }
-                if visited.insert(c) {
-                    to_visit_next.extend(c.neighbours().iter().filter(|&n| self.is_passable(n)));
-                }
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Self::parser()(input))?)
+    }
+
+    fn part1(&self, params: &Params) -> Result<usize, Error> {
+        let maze = Maze::new(self, params.size, params.time);
+        maze.time_to_end()
+            .ok_or_else(|| Error::no_answer("the exit can't be reached"))
+    }
+
+    fn part2(&self, params: &Params) -> Result<Coord, Error> {
+        let mut maze = Maze::new(self, params.size, params.time);
+        while maze.time_to_end().is_some() {
+            if !maze.tick() {
+                return Err(Error::no_answer("the exit is never cut off"));
             }
-            step_count += 1;
         }
+        maze.maze_input
+            .walls
+            .get(maze.time - 1)
+            .copied()
+            .ok_or_else(|| Error::no_answer("the exit is cut off from the start"))
     }
 }
 
 pub fn part_1(input: &str, size: Coord, time: usize) -> Option<usize> {
-    let maze = Maze::new(input, size, time);
-    maze.time_to_end()
+    MazeInput::parse(input)
+        .and_then(|maze_input| maze_input.part1(&Params { size, time }))
+        .ok()
 }
 
 pub fn part_2(input: &str, size: Coord, time: usize) -> Option<Coord> {
-    let mut maze = Maze::new(input, size, time);
-    while maze.time_to_end().is_some() {
-        if !maze.tick() {
-            return None;
-        }
-    }
-    maze.maze_input.walls.get(maze.time - 1).copied()
+    MazeInput::parse(input)
+        .and_then(|maze_input| maze_input.part2(&Params { size, time }))
+        .ok()
 }
 
 #[cfg(test)]
@@ -151,11 +153,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(
-            part_1(include_str!("../input.txt"), Coord { x: 71, y: 71 }, 1024),
-            Some(380)
-        );
+        let input = aoc_input::challenge(2024, 18);
+        assert_eq!(part_1(&input, Coord { x: 71, y: 71 }, 1024), Some(380));
     }
 
     #[test]
@@ -167,9 +168,11 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
+        let input = aoc_input::challenge(2024, 18);
         assert_eq!(
-            part_2(include_str!("../input.txt"), Coord { x: 71, y: 71 }, 0),
+            part_2(&input, Coord { x: 71, y: 71 }, 0),
             Some(Coord { x: 26, y: 50 })
         );
     }
@@ -1,3 +1,4 @@
+use aoc_core::{Error, Solution};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline},
@@ -22,26 +23,8 @@ fn count_permutations(design: &[char], patterns: &[Vec<char>]) -> usize {
     perms[design.len()]
 }
 
-pub fn part_1(input: &str) -> usize {
-    let onsen = Onsen::parse(input);
-    onsen
-        .desired_designs
-        .par_iter()
-        .filter(|d| count_permutations(d, &onsen.towel_patterns) > 0)
-        .count()
-}
-
-pub fn part_2(input: &str) -> usize {
-    let onsen = Onsen::parse(input);
-    onsen
-        .desired_designs
-        .par_iter()
-        .map(|d| count_permutations(d, &onsen.towel_patterns))
-        .sum()
-}
-
 #[derive(Debug)]
-struct Onsen {
+pub struct Onsen {
     towel_patterns: Vec<Vec<char>>,
     desired_designs: Vec<Vec<char>>,
 }
@@ -61,12 +44,42 @@ impl Onsen {
             },
         )
     }
+}
+
+impl Solution for Onsen {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Self::parser()(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .desired_designs
+            .par_iter()
+            .filter(|d| count_permutations(d, &self.towel_patterns) > 0)
+            .count())
+    }
 
-    fn parse(input: &str) -> Self {
-        Self::parser()(input).unwrap().1
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .desired_designs
+            .par_iter()
+            .map(|d| count_permutations(d, &self.towel_patterns))
+            .sum())
     }
 }
 
+pub fn part_1(input: &str) -> usize {
+    aoc_core::part1::<Onsen>(input).unwrap()
+}
+
+pub fn part_2(input: &str) -> usize {
+    aoc_core::part2::<Onsen>(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,8 +90,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 340);
+        let input = aoc_input::challenge(2024, 19);
+        assert_eq!(part_1(&input), 340);
     }
 
     #[test]
@@ -87,7 +102,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 717561822679428);
+        let input = aoc_input::challenge(2024, 19);
+        assert_eq!(part_2(&input), 717561822679428);
     }
 }
@@ -1,71 +1,81 @@
+use aoc_core::{Error, ParseError, Solution};
+use aoc_geometry::Vec2;
 use rayon::prelude::*;
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::HashMap;
 
 type Num = i32;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Coord {
-    x: Num,
-    y: Num,
-}
+type Coord = Vec2<Num>;
 
-impl Coord {
-    fn neighbours_within_distance(self, distance: Num) -> impl Iterator<Item = (Num, Self)> {
-        (-distance..=distance).flat_map(move |dy| {
-            (-distance + dy.abs()..=distance - dy.abs()).map(move |dx| {
-                (
-                    dy.abs() + dx.abs(),
-                    Self {
-                        x: self.x + dx,
-                        y: self.y + dy,
-                    },
-                )
-            })
+/// Every point within `distance` steps of `coord`, with how many steps away it is.
+fn neighbours_within_distance(coord: Coord, distance: Num) -> impl Iterator<Item = (Num, Coord)> {
+    (-distance..=distance).flat_map(move |dy| {
+        (-distance + dy.abs()..=distance - dy.abs()).map(move |dx| {
+            let n = coord + Coord::new(dx, dy);
+            (coord.manhattan(n), n)
         })
-    }
+    })
 }
 
 #[derive(Debug)]
-struct Maze {
+pub struct Maze {
     size: Coord,
     walls: Vec<bool>,
     start: Coord,
     end: Coord,
 }
 
-impl Maze {
-    fn parse(input: &str) -> Self {
+impl Solution for Maze {
+    type Params = Params;
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
         let mut width = 0;
         let mut height = 0;
-        let mut start = Coord { x: 0, y: 0 };
-        let mut end = Coord { x: 0, y: 0 };
+        let mut start = None;
+        let mut end = None;
         let mut walls = Vec::new();
 
         for (line, y) in input.lines().filter(|l| !l.is_empty()).zip(0..) {
             height = y + 1;
+            if y == 0 {
+                width = line.chars().count() as Num;
+            } else if line.chars().count() as Num != width {
+                return Err(Error::parse(input, line, format!("a row {width} wide")));
+            }
             for (c, x) in line.chars().zip(0..) {
-                width = Num::max(width, x + 1);
                 walls.push(c == '#');
                 if c == 'S' {
-                    start = Coord { x, y };
+                    start = Some(Coord { x, y });
                 }
                 if c == 'E' {
-                    end = Coord { x, y };
+                    end = Some(Coord { x, y });
                 }
             }
         }
 
-        Self {
-            start,
-            end,
+        Ok(Self {
+            start: start.ok_or_else(|| ParseError::at_end(input, "a start S"))?,
+            end: end.ok_or_else(|| ParseError::at_end(input, "an end E"))?,
             walls,
             size: Coord {
                 x: width,
                 y: height,
             },
-        }
+        })
     }
 
+    fn part1(&self, params: &Params) -> Result<Num, Error> {
+        self.cheats(params.required_saving, 2)
+    }
+
+    fn part2(&self, params: &Params) -> Result<Num, Error> {
+        self.cheats(params.required_saving, 20)
+    }
+}
+
+impl Maze {
     fn is_wall(&self, coord: &Coord) -> bool {
         coord.x < 0
             || coord.y < 0
@@ -75,47 +85,61 @@ impl Maze {
     }
 
     fn distances_from_point(&self, point: Coord) -> HashMap<Coord, Num> {
-        let mut distances: HashMap<Coord, Num> = HashMap::new();
-        let mut explore_next = vec![point];
-        for distance in 0.. {
-            for &c in std::mem::take(&mut explore_next).iter() {
-                if let Entry::Vacant(v) = distances.entry(c) {
-                    v.insert(distance);
-                    for (_, n) in c.neighbours_within_distance(1) {
-                        if !self.is_wall(&n) {
-                            explore_next.push(n);
+        aoc_search::bfs(|&c: &Coord| {
+            neighbours_within_distance(c, 1)
+                .map(|(_, n)| n)
+                .filter(|n| !self.is_wall(n))
+        })
+        .start(point)
+        .explore()
+        .costs()
+        .iter()
+        .map(|(&c, &distance)| (c, distance as Num))
+        .collect()
+    }
+
+    fn cheats(&self, required_saving: Num, skip_distance: Num) -> Result<Num, Error> {
+        let distances_from_start = self.distances_from_point(self.start);
+        let distances_from_end = self.distances_from_point(self.end);
+        let regular_distance = *distances_from_start
+            .get(&self.end)
+            .ok_or_else(|| Error::no_answer("the end can't be reached from the start"))?;
+
+        Ok(distances_from_end
+            .par_iter()
+            .map(|(c, &d)| {
+                let mut cheat_counts = 0;
+                for (skipped_distance, n) in neighbours_within_distance(*c, skip_distance) {
+                    if let Some(&sd) = distances_from_start.get(&n) {
+                        if regular_distance - d - sd - skipped_distance - required_saving >= 0 {
+                            cheat_counts += 1;
                         }
                     }
                 }
-            }
-            if explore_next.is_empty() {
-                break;
-            }
+                cheat_counts
+            })
+            .sum())
+    }
+}
+
+/// How many picoseconds a cheat has to save to be counted.
+pub struct Params {
+    pub required_saving: Num,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            required_saving: 100,
         }
-        distances
     }
 }
 
 pub fn puzzle(input: &str, required_saving: Num, skip_distance: Num) -> Num {
-    let maze = Maze::parse(input);
-    let distances_from_start = maze.distances_from_point(maze.start);
-    let distances_from_end = maze.distances_from_point(maze.end);
-    let regular_distance = distances_from_start[&maze.end];
-
-    distances_from_end
-        .par_iter()
-        .map(|(c, &d)| {
-            let mut cheat_counts = 0;
-            for (skipped_distance, n) in c.neighbours_within_distance(skip_distance) {
-                if let Some(&sd) = distances_from_start.get(&n) {
-                    if regular_distance - d - sd - skipped_distance - required_saving >= 0 {
-                        cheat_counts += 1;
-                    }
-                }
-            }
-            cheat_counts
-        })
-        .sum()
+    Maze::parse(input)
+        .unwrap()
+        .cheats(required_saving, skip_distance)
+        .unwrap()
 }
 
 #[cfg(test)]
@@ -128,8 +152,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(puzzle(include_str!("../input.txt"), 100, 2), 1395);
+        let input = aoc_input::challenge(2024, 20);
+        assert_eq!(puzzle(&input, 100, 2), 1395);
     }
 
     #[test]
@@ -138,7 +164,18 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(puzzle(include_str!("../input.txt"), 100, 20), 993178);
+        let input = aoc_input::challenge(2024, 20);
+        assert_eq!(puzzle(&input, 100, 20), 993178);
+    }
+
+    #[test]
+    fn needs_a_way_from_start_to_end() {
+        assert!(matches!(Maze::parse("#.E#\n"), Err(Error::Parse(_))));
+        assert!(matches!(Maze::parse("#S.#\n"), Err(Error::Parse(_))));
+        assert!(matches!(Maze::parse("#S.#\n#.\n"), Err(Error::Parse(_))));
+        let maze = Maze::parse("S#E\n").unwrap();
+        assert!(matches!(maze.cheats(0, 2), Err(Error::NoAnswer(_))));
     }
 }
@@ -1,27 +1,14 @@
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashMap, HashSet},
-    ops::Add,
 };
 
-type Num = i64;
-
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Coord {
-    x: Num,
-    y: Num,
-}
+use aoc_core::{Error, Solution};
+use aoc_geometry::Vec2;
 
-impl Add for &Coord {
-    type Output = Coord;
+type Num = i64;
 
-    fn add(self, other: &Coord) -> Coord {
-        Coord {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
+type Coord = Vec2<Num>;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 enum DirectionalKeypadButton {
@@ -124,7 +111,7 @@ fn populate_move_times(levels: usize) -> HashMap<MoveTime, usize> {
                     start,
                     end,
                 },
-                (start_pos.x.abs_diff(end_pos.x) + start_pos.y.abs_diff(end_pos.y) + 1) as usize,
+                (start_pos.manhattan(end_pos) + 1) as usize,
             );
         }
     }
@@ -167,7 +154,7 @@ fn populate_move_times(levels: usize) -> HashMap<MoveTime, usize> {
 
                     for direction in DIRECTIONAL_KEYPAD_BUTTONS {
                         if let Some(resulting_button) = DirectionalKeypadButton::at_coord(
-                            &(&button.as_coord() + &direction.direction()),
+                            &(button.as_coord() + direction.direction()),
                         ) {
                             unvisited.push((
                                 Reverse(
@@ -235,7 +222,7 @@ fn input_code(
                     ),
                     inputted,
                     direction,
-                    &arm + &direction.direction(),
+                    arm + direction.direction(),
                 ));
             }
         }
@@ -243,27 +230,62 @@ fn input_code(
     panic!();
 }
 
+/// Each code to type on the door's keypad, with the numeric part of the code.
+pub struct DoorCodes(Vec<(usize, Vec<NumberPadButton>)>);
+
+impl DoorCodes {
+    fn complexity(&self, depth: usize) -> usize {
+        let move_times = populate_move_times(depth);
+        self.0
+            .iter()
+            .map(|(numeric_part, encoded)| {
+                let shortest_sequence = input_code(encoded, depth, &move_times);
+                numeric_part * shortest_sequence
+            })
+            .sum()
+    }
+}
+
+impl Solution for DoorCodes {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|code| {
+                let encoded = code
+                    .bytes()
+                    .enumerate()
+                    .map(|(i, b)| match b {
+                        b'A' => Ok(NumberPadButton::A),
+                        b'0'..=b'9' => Ok(NumberPadButton::Digit(b - b'0')),
+                        _ => Err(Error::parse(input, &code[i..], "a button from 0 to 9 or A")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let numeric_part = code
+                    .get(0..3)
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(|| Error::parse(input, code, "a three digit number"))?;
+                Ok((numeric_part, encoded))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.complexity(2))
+    }
+
+    fn part2(&self, _: &()) -> Result<usize, Error> {
+        Ok(self.complexity(25))
+    }
+}
+
 pub fn complexity(input: &str, depth: usize) -> usize {
-    let move_times = populate_move_times(depth);
-    input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|code| {
-            let encoded: Vec<NumberPadButton> = code
-                .bytes()
-                .map(|b| {
-                    if b == b'A' {
-                        NumberPadButton::A
-                    } else {
-                        NumberPadButton::Digit(b - b'0')
-                    }
-                })
-                .collect();
-            let shortest_sequence = input_code(&encoded, depth, &move_times);
-            let numeric_part: usize = code[0..3].parse().unwrap();
-            numeric_part * shortest_sequence
-        })
-        .sum()
+    DoorCodes::parse(input).unwrap().complexity(depth)
 }
 
 #[cfg(test)]
@@ -276,15 +298,16 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(complexity(include_str!("../input.txt"), 2), 138764);
+        let input = aoc_input::challenge(2024, 21);
+        assert_eq!(complexity(&input, 2), 138764);
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(
-            complexity(include_str!("../input.txt"), 25),
-            169137886514152
-        );
+        let input = aoc_input::challenge(2024, 21);
+        assert_eq!(complexity(&input, 25), 169137886514152);
     }
 }
@@ -1,10 +1,11 @@
 use std::{collections::BTreeMap, iter::once};
 
+use aoc_core::{Error, Solution};
+
 type Num = i64;
 
-fn parse(input: &str) -> impl Iterator<Item = Num> + '_ {
-    input.lines().filter_map(|l| l.parse().ok())
-}
+/// Each buyer's initial secret number.
+pub struct Buyers(Vec<Num>);
 
 fn evolve(secret: Num) -> Num {
     let secret = ((secret * 64) ^ secret) % 16777216;
@@ -19,34 +20,58 @@ fn secret_evolution(mut secret: Num) -> impl Iterator<Item = Num> {
     }))
 }
 
+impl Solution for Buyers {
+    type Params = ();
+    type Part1 = Num;
+    type Part2 = Num;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(input.lines().filter_map(|l| l.parse().ok()).collect()))
+    }
+
+    fn part1(&self, _: &()) -> Result<Num, Error> {
+        Ok(self
+            .0
+            .iter()
+            .map(|&secret| secret_evolution(secret).last().unwrap())
+            .sum())
+    }
+
+    fn part2(&self, _: &()) -> Result<Num, Error> {
+        let mut overall_totals = BTreeMap::new();
+        for &hiding_spot in self.0.iter() {
+            let prices: Vec<_> = secret_evolution(hiding_spot).map(|i| i % 10).collect();
+            let local_totals: BTreeMap<_, _> = prices
+                .windows(5)
+                .rev()
+                .map(|window| {
+                    (
+                        ((window[1] - window[0] + 9) << 15)
+                            + ((window[2] - window[1] + 9) << 10)
+                            + ((window[3] - window[2] + 9) << 5)
+                            + (window[4] - window[3] + 9),
+                        window[4],
+                    )
+                })
+                .collect();
+            for (&seq, &bananas) in local_totals.iter() {
+                *overall_totals.entry(seq).or_default() += bananas;
+            }
+        }
+        overall_totals
+            .values()
+            .copied()
+            .max()
+            .ok_or_else(|| Error::no_answer("there are no buyers"))
+    }
+}
+
 pub fn part_1(input: &str) -> Num {
-    parse(input)
-        .map(|secret| secret_evolution(secret).last().unwrap())
-        .sum()
+    aoc_core::part1::<Buyers>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> Num {
-    let mut overall_totals = BTreeMap::new();
-    for hiding_spot in parse(input) {
-        let prices: Vec<_> = secret_evolution(hiding_spot).map(|i| i % 10).collect();
-        let local_totals: BTreeMap<_, _> = prices
-            .windows(5)
-            .rev()
-            .map(|window| {
-                (
-                    ((window[1] - window[0] + 9) << 15)
-                        + ((window[2] - window[1] + 9) << 10)
-                        + ((window[3] - window[2] + 9) << 5)
-                        + ((window[4] - window[3] + 9) << 0),
-                    window[4],
-                )
-            })
-            .collect();
-        for (&seq, &bananas) in local_totals.iter() {
-            *overall_totals.entry(seq).or_default() += bananas;
-        }
-    }
-    overall_totals.values().copied().max().unwrap()
+    aoc_core::part2::<Buyers>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -59,8 +84,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 19847565303);
+        let input = aoc_input::challenge(2024, 22);
+        assert_eq!(part_1(&input), 19847565303);
     }
 
     #[test]
@@ -69,7 +96,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(part_2(include_str!("../input.txt")), 2250);
+        let input = aoc_input::challenge(2024, 22);
+        assert_eq!(part_2(&input), 2250);
     }
 }
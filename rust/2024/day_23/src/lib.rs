@@ -1,62 +1,56 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    iter::once,
-};
+use aoc_core::{Error, Solution};
+use aoc_graph::Graph;
 
-fn parse_connections(input: &str) -> BTreeMap<&str, BTreeSet<&str>> {
-    let mut connections: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
-    for line in input.lines() {
-        if line.is_empty() {
-            continue;
+/// Which computers are directly connected to each other.
+pub struct Network(Graph<String>);
+
+impl Solution for Network {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut connections = Graph::new();
+        for line in input.lines() {
+            if line.is_empty() {
+                continue;
+            }
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| Error::parse(input, line, "a connection like ab-cd"))?;
+            connections.add_edge(a.to_string(), b.to_string());
         }
-        let a = &line[0..2];
-        let b = &line[3..5];
-        connections.entry(a).or_default().insert(b);
-        connections.entry(b).or_default().insert(a);
+        Ok(Self(connections))
     }
-    connections
-}
 
-pub fn part_1(input: &str) -> usize {
-    let connections = parse_connections(input);
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        Ok(self
+            .0
+            .triangles()
+            .iter()
+            .filter(|cluster| cluster.iter().any(|computer| computer.starts_with('t')))
+            .count())
+    }
 
-    let mut clusters: BTreeSet<BTreeSet<&str>> = BTreeSet::new();
-    for (&a, a_connections) in connections.iter() {
-        for &b in a_connections.iter() {
-            for &c in a_connections.intersection(&connections[b]) {
-                clusters.insert([a, b, c].iter().copied().collect());
-            }
+    fn part2(&self, _: &()) -> Result<String, Error> {
+        if self.0.is_empty() {
+            return Err(Error::no_answer("there are no computers"));
         }
+        Ok(self
+            .0
+            .maximum_clique()
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(","))
     }
-    clusters
-        .iter()
-        .filter(|cluster| cluster.iter().any(|computer| computer.starts_with('t')))
-        .count()
+}
+
+pub fn part_1(input: &str) -> usize {
+    aoc_core::part1::<Network>(input).unwrap()
 }
 
 pub fn part_2(input: &str) -> String {
-    let connections = parse_connections(input);
-    let mut clusters: BTreeSet<BTreeSet<&str>> = BTreeSet::new();
-    clusters.insert(BTreeSet::new());
-    loop {
-        let prev_clusters = std::mem::take(&mut clusters);
-        for prev_cluster in prev_clusters.iter() {
-            for (&other, other_connections) in connections.iter() {
-                if prev_cluster.iter().all(|p| other_connections.contains(p)) {
-                    clusters.insert(prev_cluster.iter().copied().chain(once(other)).collect());
-                }
-            }
-        }
-        if clusters.is_empty() {
-            return prev_clusters
-                .first()
-                .unwrap()
-                .iter()
-                .copied()
-                .collect::<Vec<_>>()
-                .join(",");
-        }
-    }
+    aoc_core::part2::<Network>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -69,8 +63,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 1110);
+        let input = aoc_input::challenge(2024, 23);
+        assert_eq!(part_1(&input), 1110);
     }
 
     #[test]
@@ -79,10 +75,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_2() {
-        assert_eq!(
-            part_2(include_str!("../input.txt")),
-            "ej,hm,ks,ms,ns,rb,rq,sc,so,un,vb,vd,wd"
-        );
+        let input = aoc_input::challenge(2024, 23);
+        assert_eq!(part_2(&input), "ej,hm,ks,ms,ns,rb,rq,sc,so,un,vb,vd,wd");
     }
 }
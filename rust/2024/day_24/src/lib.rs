@@ -1,5 +1,6 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::Infallible};
 
+use aoc_core::{Error, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -16,14 +17,14 @@ enum GateInput {
     Xor,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Gate<'a> {
-    inputs: [&'a str; 2],
+#[derive(Debug, Clone)]
+struct Gate {
+    inputs: [String; 2],
     gate: GateInput,
 }
 
-impl<'a> Gate<'a> {
-    fn parser(
+impl Gate {
+    fn parser<'a>(
     ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
         map(
             tuple((
@@ -35,8 +36,8 @@ impl<'a> Gate<'a> {
                 )),
                 alphanumeric1,
             )),
-            |(a, g, b)| Self {
-                inputs: [a, b],
+            |(a, g, b): (&str, _, &str)| Self {
+                inputs: [a.to_string(), b.to_string()],
                 gate: g,
             },
         )
@@ -44,13 +45,13 @@ impl<'a> Gate<'a> {
 }
 
 #[derive(Debug, Clone)]
-struct PuzzleInput<'a> {
-    inputs: BTreeMap<&'a str, bool>,
-    gates: BTreeMap<&'a str, Gate<'a>>,
+pub struct PuzzleInput {
+    inputs: BTreeMap<String, bool>,
+    gates: BTreeMap<String, Gate>,
 }
 
-impl<'a> PuzzleInput<'a> {
-    fn parser(
+impl PuzzleInput {
+    fn parser<'a>(
     ) -> impl FnMut(&'a str) -> Result<(&'a str, Self), nom::Err<nom::error::Error<&'a str>>> {
         map(
             separated_pair(
@@ -69,26 +70,28 @@ impl<'a> PuzzleInput<'a> {
                 ),
             ),
             |(inputs, gates)| Self {
-                inputs: inputs.iter().copied().collect(),
-                gates: gates.iter().copied().map(|(a, b)| (b, a)).collect(),
+                inputs: inputs
+                    .into_iter()
+                    .map(|(wire, value): (&str, _)| (wire.to_string(), value))
+                    .collect(),
+                gates: gates
+                    .into_iter()
+                    .map(|(gate, wire): (_, &str)| (wire.to_string(), gate))
+                    .collect(),
             },
         )
     }
 
-    fn parse(input: &'a str) -> Self {
-        Self::parser()(input).unwrap().1
-    }
-
-    fn value_of_wire<'b>(&self, wire: &'a str, memo: &'b mut BTreeMap<&'a str, bool>) -> bool {
+    fn value_of_wire<'a>(&'a self, wire: &'a str, memo: &mut BTreeMap<&'a str, bool>) -> bool {
         if let Some(&v) = memo.get(wire) {
             return v;
         }
         if let Some(&v) = self.inputs.get(wire) {
             return v;
         }
-        let gate_input = self.gates[wire];
-        let input_a = self.value_of_wire(gate_input.inputs[0], memo);
-        let input_b = self.value_of_wire(gate_input.inputs[1], memo);
+        let gate_input = &self.gates[wire];
+        let input_a = self.value_of_wire(&gate_input.inputs[0], memo);
+        let input_b = self.value_of_wire(&gate_input.inputs[1], memo);
         let v = match gate_input.gate {
             GateInput::And => input_a && input_b,
             GateInput::Or => input_a || input_b,
@@ -98,8 +101,12 @@ impl<'a> PuzzleInput<'a> {
         v
     }
 
-    fn equation_of_wire_with_carry(&self, mut wire: &'a str, swaps: &[[&'a str; 2]]) -> String {
-        if let Some(_) = self.inputs.get(wire) {
+    fn equation_of_wire_with_carry<'a>(
+        &'a self,
+        mut wire: &'a str,
+        swaps: &[[&'a str; 2]],
+    ) -> String {
+        if self.inputs.contains_key(wire) {
             return wire.to_string();
         }
 
@@ -108,33 +115,51 @@ impl<'a> PuzzleInput<'a> {
         } else if let Some(s) = swaps.iter().find(|s| s[1] == wire) {
             wire = s[0];
         }
-        let gate_input = self.gates[wire];
-        let input_a = self.equation_of_wire_with_carry(gate_input.inputs[0], swaps);
-        let input_b = self.equation_of_wire_with_carry(gate_input.inputs[1], swaps);
+        let gate_input = &self.gates[wire];
+        let input_a = self.equation_of_wire_with_carry(&gate_input.inputs[0], swaps);
+        let input_b = self.equation_of_wire_with_carry(&gate_input.inputs[1], swaps);
         match gate_input.gate {
             GateInput::And => format!("({} & {})", input_a, input_b),
-            GateInput::Or => format!("carry"),
+            GateInput::Or => "carry".to_string(),
             // GateInput::Or => format!("({} | {})", input_a, input_b),
             GateInput::Xor => format!("({} ^ {})", input_a, input_b),
         }
     }
 }
 
-pub fn part_1(input: &str) -> u64 {
-    let puzzle = PuzzleInput::parse(input);
-    let mut result = 0;
-    let mut memo = BTreeMap::new();
-    for &wire in puzzle.gates.keys().rev() {
-        if wire.starts_with('z') {
-            let value = puzzle.value_of_wire(wire, &mut memo);
-            result = result << 1 | if value { 1 } else { 0 };
+impl Solution for PuzzleInput {
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(aoc_core::finish(input, Self::parser()(input))?)
+    }
+
+    fn part1(&self, _: &()) -> Result<u64, Error> {
+        let mut result = 0;
+        let mut memo = BTreeMap::new();
+        for wire in self.gates.keys().rev() {
+            if wire.starts_with('z') {
+                let value = self.value_of_wire(wire, &mut memo);
+                result = result << 1 | if value { 1 } else { 0 };
+            }
         }
+        Ok(result)
+    }
+
+    /// Part 2 was solved by inspecting the adder's wiring by hand, see `part_2`.
+    fn part2(&self, _: &()) -> Result<Infallible, Error> {
+        Err(Error::Unsolved)
     }
-    result
+}
+
+pub fn part_1(input: &str) -> u64 {
+    aoc_core::part1::<PuzzleInput>(input).unwrap()
 }
 
 pub fn part_2(input: &str) {
-    let puzzle = PuzzleInput::parse(input);
+    let puzzle = PuzzleInput::parse(input).unwrap();
     for i in 0..45 {
         let start = format!("z{:02}", i);
         dbg!((
@@ -167,8 +192,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 51837135476040);
+        let input = aoc_input::challenge(2024, 24);
+        assert_eq!(part_1(&input), 51837135476040);
     }
 
     #[test]
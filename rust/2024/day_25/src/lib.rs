@@ -1,3 +1,7 @@
+use std::convert::Infallible;
+
+use aoc_core::{Error, ParseError, Solution};
+
 #[derive(Debug)]
 struct LockKey {
     switch_points: [u8; 5],
@@ -5,28 +9,33 @@ struct LockKey {
 }
 
 impl LockKey {
-    fn parse(input: &[u8]) -> Self {
-        let lock = &input[0..5] == b"#####";
+    fn parse(input: &str, schematic: &str) -> Result<Self, ParseError> {
+        let bytes = schematic.as_bytes();
+        let lock = &bytes[0..5] == b"#####";
         let switch_point = |x: usize| {
             for y in 0..=5 {
-                let i = input[6 + x + y * 6];
+                let i = bytes[6 + x + y * 6];
                 if (i == b'.') == lock {
-                    return y as u8;
+                    return Ok(y as u8);
                 }
             }
-            panic!();
+            Err(ParseError::new(
+                input,
+                schematic.get(6 + x..).unwrap_or(schematic),
+                "a column of pins",
+            ))
         };
 
-        Self {
+        Ok(Self {
             lock,
             switch_points: [
-                switch_point(0),
-                switch_point(1),
-                switch_point(2),
-                switch_point(3),
-                switch_point(4),
+                switch_point(0)?,
+                switch_point(1)?,
+                switch_point(2)?,
+                switch_point(3)?,
+                switch_point(4)?,
             ],
-        }
+        })
     }
 
     fn fit(&self, other: &Self) -> bool {
@@ -39,33 +48,53 @@ impl LockKey {
         (0..5).all(|x| self.switch_points[x] <= other.switch_points[x])
     }
 
-    fn parse_all(inputs: &[u8]) -> Vec<Self> {
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
         let mut lock_keys = vec![];
         for i in 0.. {
-            if let Some(input) = inputs.get(i * 43..i * 43 + 41) {
-                lock_keys.push(Self::parse(input));
+            if let Some(schematic) = input.get(i * 43..i * 43 + 41) {
+                lock_keys.push(Self::parse(input, schematic)?);
             } else {
                 break;
             }
         }
-        lock_keys
+        Ok(lock_keys)
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    let lock_keys = LockKey::parse_all(input.as_bytes());
-    let locks: Vec<&LockKey> = lock_keys.iter().filter(|lk| lk.lock).collect();
-    let keys: Vec<&LockKey> = lock_keys.iter().filter(|lk| !lk.lock).collect();
-    let mut count = 0;
-    for lock in locks.iter() {
-        for key in keys.iter() {
-            if lock.fit(key) {
-                count += 1;
+/// Every schematic, whether it's a lock or a key.
+pub struct Schematics(Vec<LockKey>);
+
+impl Solution for Schematics {
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self(LockKey::parse_all(input)?))
+    }
+
+    fn part1(&self, _: &()) -> Result<usize, Error> {
+        let locks: Vec<&LockKey> = self.0.iter().filter(|lk| lk.lock).collect();
+        let keys: Vec<&LockKey> = self.0.iter().filter(|lk| !lk.lock).collect();
+        let mut count = 0;
+        for lock in locks.iter() {
+            for key in keys.iter() {
+                if lock.fit(key) {
+                    count += 1;
+                }
             }
         }
+
+        Ok(count)
     }
 
-    count
+    fn part2(&self, _: &()) -> Result<Infallible, Error> {
+        Err(Error::Unsolved)
+    }
+}
+
+pub fn part_1(input: &str) -> usize {
+    aoc_core::part1::<Schematics>(input).unwrap()
 }
 
 #[cfg(test)]
@@ -78,7 +107,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(challenge_input), ignore = "input not cached")]
     fn challenge_part_1() {
-        assert_eq!(part_1(include_str!("../input.txt")), 3136);
+        let input = aoc_input::challenge(2024, 25);
+        assert_eq!(part_1(&input), 3136);
     }
 }
@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_render::{AnimatedGif, Backend, PngSequence, Terminal};
+use clap::ValueEnum;
+
+use crate::registry;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// Redraw each frame in the terminal
+    Terminal,
+    /// One numbered PNG per frame, in the output directory
+    Png,
+    /// One looping GIF, at the output path
+    Gif,
+}
+
+pub struct Options {
+    pub year: u16,
+    pub day: u8,
+    pub input: Option<PathBuf>,
+    pub format: Format,
+    /// Where the PNGs or GIF go. The terminal animation always goes to stdout.
+    pub output: Option<PathBuf>,
+    /// How many pixels wide and high each cell of a PNG or GIF is.
+    pub scale: usize,
+    /// How long each frame is shown for.
+    pub delay: Duration,
+}
+
+/// Runs a day's animation through the chosen backend.
+pub fn run(options: Options) -> Result<(), String> {
+    let entry = registry::find(options.year, options.day)
+        .ok_or_else(|| format!("{} day {} is not registered", options.year, options.day))?;
+    let animate = entry
+        .animate
+        .ok_or_else(|| format!("{} day {} has no animation", options.year, options.day))?;
+    let input = crate::read_input(options.year, options.day, options.input)?;
+    let output = || {
+        options
+            .output
+            .clone()
+            .ok_or_else(|| "--output is needed for PNGs and GIFs".to_string())
+    };
+    let mut backend: Box<dyn Backend> = match options.format {
+        Format::Terminal => Box::new(Terminal::new(io::stdout().lock()).delay(options.delay)),
+        Format::Png => Box::new(PngSequence::new(output()?).scale(options.scale)),
+        Format::Gif => {
+            let path = output()?;
+            let file = File::create(&path)
+                .map_err(|e| format!("could not create {}: {e}", path.display()))?;
+            Box::new(
+                AnimatedGif::new(BufWriter::new(file))
+                    .scale(options.scale)
+                    .delay(options.delay),
+            )
+        }
+    };
+    animate(&input, backend.as_mut())?;
+    backend
+        .finish()
+        .map_err(|e| format!("could not finish the animation: {e}"))
+}
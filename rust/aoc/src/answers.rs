@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Where the `<year>.toml` answer files are kept in the repository.
+pub const DEFAULT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers");
+
+/// The known answers for one day, as `aoc run` prints them.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl DayAnswers {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// A year's answers, keyed by day.
+#[derive(Debug, Default)]
+pub struct YearAnswers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+impl YearAnswers {
+    fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    /// Reads `<dir>/<year>.toml`. A year without a file has no known answers.
+    pub fn load(dir: &Path, year: u16) -> Result<Self, String> {
+        let path = Self::path(dir, year);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tables: BTreeMap<String, DayAnswers> =
+            toml::from_str(text).map_err(|e| e.to_string())?;
+        let days = tables
+            .into_iter()
+            .map(|(day, answers)| match day.parse() {
+                Ok(day @ 1..=25) => Ok((day, answers)),
+                _ => Err(format!("[{day}] is not a day between 1 and 25")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { days })
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayAnswers> {
+        self.days.get(&day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_days_and_parts() {
+        let answers = YearAnswers::parse(
+            "[17]\npart1 = \"5,1,4,0,5,1,0,2,6\"\npart2 = \"202322936867370\"\n\n[25]\npart1 = \"3136\"\n",
+        )
+        .unwrap();
+        assert_eq!(answers.day(17).unwrap().part(1), Some("5,1,4,0,5,1,0,2,6"));
+        assert_eq!(answers.day(17).unwrap().part(2), Some("202322936867370"));
+        assert_eq!(answers.day(25).unwrap().part(2), None);
+        assert!(answers.day(1).is_none());
+
+        assert!(YearAnswers::parse("[26]\npart1 = \"1\"\n").is_err());
+        assert!(YearAnswers::parse("[1]\npart3 = \"1\"\n").is_err());
+    }
+
+    #[test]
+    fn answer_files_only_name_registered_days() {
+        for year in [2018, 2022, 2023, 2024] {
+            let answers = YearAnswers::load(Path::new(DEFAULT_DIR), year).unwrap();
+            for &day in answers.days.keys() {
+                assert!(
+                    crate::registry::find(year, day).is_some(),
+                    "{year} day {day} has answers but isn't registered"
+                );
+            }
+        }
+    }
+}
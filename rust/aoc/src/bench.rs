@@ -0,0 +1,132 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_core::Timings;
+
+use crate::history::{self, DayTimings, History, Run, Step};
+use crate::registry::{self, Day};
+
+pub struct Options {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    /// How many times to solve each day. The fastest time of each step is kept.
+    pub runs: u32,
+    /// How many percent slower than the previous run a step can get before it's flagged.
+    pub threshold: f64,
+    /// Defaults to [`history::FILE_NAME`] in the input cache's directory.
+    pub history: Option<PathBuf>,
+    pub save: bool,
+}
+
+fn fastest(a: Timings, b: Timings) -> Timings {
+    let min = |a: Option<Duration>, b: Option<Duration>| a.zip(b).map(|(a, b)| a.min(b));
+    Timings {
+        parse: a.parse.min(b.parse),
+        part1: min(a.part1, b.part1),
+        part2: min(a.part2, b.part2),
+    }
+}
+
+fn time_day(day: &Day, input: &str, runs: u32) -> Result<Timings, aoc_core::Error> {
+    let mut best = (day.time)(input)?;
+    for _ in 1..runs {
+        best = fastest(best, (day.time)(input)?);
+    }
+    Ok(best)
+}
+
+/// Times every registered day that matches the filters, compares each step with the
+/// previous run that included the day, and adds this run to the history.
+pub fn run(options: Options) -> Result<(), String> {
+    let days: Vec<&Day> = registry::DAYS
+        .iter()
+        .filter(|d| {
+            options.year.is_none_or(|y| d.year == y) && options.day.is_none_or(|n| d.day == n)
+        })
+        .collect();
+    if days.is_empty() {
+        return Err("no registered days match".to_string());
+    }
+    let history_path = options
+        .history
+        .unwrap_or_else(|| aoc_input::Cache::from_env().root().join(history::FILE_NAME));
+    let mut history = History::load(&history_path)?;
+
+    let mut timed = Vec::new();
+    let mut problems = Vec::new();
+    let mut regressions = Vec::new();
+    println!(
+        "{:<4}  {:>3}  {:>17}  {:>17}  {:>17}",
+        "year", "day", "parse", "part 1", "part 2"
+    );
+    for day in days {
+        let input = match aoc_input::load(day.year, day.day) {
+            Ok(input) => input,
+            Err(aoc_input::Error::Missing { .. }) => {
+                println!("{:<4}  {:>3}  no input", day.year, day.day);
+                continue;
+            }
+            Err(e) => {
+                println!("{:<4}  {:>3}  error", day.year, day.day);
+                problems.push(e.to_string());
+                continue;
+            }
+        };
+        let timings = match time_day(day, &input, options.runs) {
+            Ok(timings) => DayTimings::new(day.year, day.day, &timings),
+            Err(e) => {
+                println!("{:<4}  {:>3}  error", day.year, day.day);
+                problems.push(format!("{} day {}: {e}", day.year, day.day));
+                continue;
+            }
+        };
+
+        let previous = history.previous(day.year, day.day);
+        let cells = Step::ALL.map(|step| {
+            let Some(current) = timings.step(step) else {
+                return "-".to_string();
+            };
+            let Some(before) = previous.and_then(|p| p.step(step)) else {
+                return format!("{current:.2?}");
+            };
+            let change = history::change(before, current);
+            if history::regressed(before, current, options.threshold) {
+                regressions.push(format!(
+                    "{} day {} {step}: {before:.2?} -> {current:.2?} ({change:+.0}%)",
+                    day.year, day.day
+                ));
+                format!("{current:.2?} {change:+.0}% !")
+            } else {
+                format!("{current:.2?} {change:+.0}%")
+            }
+        });
+        println!(
+            "{:<4}  {:>3}  {:>17}  {:>17}  {:>17}",
+            day.year, day.day, cells[0], cells[1], cells[2]
+        );
+        // Slow days take a while, so show each row as soon as it's ready.
+        let _ = std::io::stdout().flush();
+        timed.push(timings);
+    }
+
+    println!();
+    for problem in problems.iter().chain(&regressions) {
+        println!("{problem}");
+    }
+    if options.save && !timed.is_empty() {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        history.append(Run { time, days: timed })?;
+        println!("saved to {}", history_path.display());
+    }
+    match (problems.len(), regressions.len()) {
+        (0, 0) => Ok(()),
+        (0, n) => Err(format!(
+            "{n} steps got more than {}% slower than the previous run",
+            options.threshold
+        )),
+        (n, _) => Err(format!("{n} days could not be timed")),
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_core::Timings;
+use serde::{Deserialize, Serialize};
+
+/// Kept in the input cache's directory, as the timings are only meaningful on this machine.
+pub const FILE_NAME: &str = "bench-history.jsonl";
+
+/// Slowdowns smaller than this are put down to noise, whatever the threshold.
+const MIN_SLOWDOWN: Duration = Duration::from_micros(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::Part1, Step::Part2];
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part 1",
+            Step::Part2 => "part 2",
+        })
+    }
+}
+
+/// One day's timings from one run, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub year: u16,
+    pub day: u8,
+    parse_ns: u64,
+    part1_ns: Option<u64>,
+    part2_ns: Option<u64>,
+}
+
+impl DayTimings {
+    pub fn new(year: u16, day: u8, timings: &Timings) -> Self {
+        let nanos = |d: Duration| d.as_nanos().try_into().unwrap_or(u64::MAX);
+        Self {
+            year,
+            day,
+            parse_ns: nanos(timings.parse),
+            part1_ns: timings.part1.map(nanos),
+            part2_ns: timings.part2.map(nanos),
+        }
+    }
+
+    pub fn step(&self, step: Step) -> Option<Duration> {
+        match step {
+            Step::Parse => Some(self.parse_ns),
+            Step::Part1 => self.part1_ns,
+            Step::Part2 => self.part2_ns,
+        }
+        .map(Duration::from_nanos)
+    }
+}
+
+/// Every day timed by one `aoc bench`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub days: Vec<DayTimings>,
+}
+
+/// The earlier runs, oldest first, one JSON object per line.
+pub struct History {
+    path: PathBuf,
+    runs: Vec<Run>,
+}
+
+impl History {
+    /// A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let runs = match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("could not read {}: {e}", path.display())),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            runs,
+        })
+    }
+
+    fn parse(text: &str) -> Result<Vec<Run>, String> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {e}", n + 1)))
+            .collect()
+    }
+
+    /// The day's timings from the latest run that included it.
+    pub fn previous(&self, year: u16, day: u8) -> Option<&DayTimings> {
+        self.runs
+            .iter()
+            .rev()
+            .find_map(|run| run.days.iter().find(|d| d.year == year && d.day == day))
+    }
+
+    pub fn append(&mut self, run: Run) -> Result<(), String> {
+        let write_error =
+            |e: std::io::Error| format!("could not write {}: {e}", self.path.display());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        let mut line = serde_json::to_string(&run).map_err(|e| e.to_string())?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(write_error)?;
+        self.runs.push(run);
+        Ok(())
+    }
+}
+
+/// How much slower `current` is than `previous`, as a percentage.
+pub fn change(previous: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() / previous.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+}
+
+/// Whether `current` is more than `threshold` percent slower than `previous`.
+pub fn regressed(previous: Duration, current: Duration, threshold: f64) -> bool {
+    current > previous + MIN_SLOWDOWN && change(previous, current) > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_latest_timings_for_a_day() {
+        let runs = History::parse(concat!(
+            r#"{"time":1,"days":[{"year":2024,"day":17,"parse_ns":10,"part1_ns":20,"part2_ns":null}]}"#,
+            "\n",
+            r#"{"time":2,"days":[{"year":2024,"day":1,"parse_ns":1,"part1_ns":2,"part2_ns":3}]}"#,
+            "\n",
+        ))
+        .unwrap();
+        let history = History {
+            path: PathBuf::new(),
+            runs,
+        };
+        let previous = history.previous(2024, 17).unwrap();
+        assert_eq!(previous.step(Step::Part1), Some(Duration::from_nanos(20)));
+        assert_eq!(previous.step(Step::Part2), None);
+        assert!(history.previous(2024, 2).is_none());
+    }
+
+    #[test]
+    fn flags_slowdowns_over_the_threshold() {
+        let ms = Duration::from_millis;
+        assert!(regressed(ms(100), ms(120), 10.0));
+        assert!(!regressed(ms(100), ms(105), 10.0));
+        assert!(!regressed(ms(100), ms(50), 10.0));
+        // Doubling a few microseconds is noise.
+        assert!(!regressed(
+            Duration::from_micros(5),
+            Duration::from_micros(10),
+            10.0
+        ));
+    }
+}
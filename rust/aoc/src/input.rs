@@ -0,0 +1,110 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use aoc_input::{Cache, Fetcher, FixtureServer};
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum InputCommand {
+    /// Download a day's puzzle page and input into the cache
+    Fetch {
+        year: u16,
+        day: u8,
+        /// Download again even if the input is already cached
+        #[arg(long)]
+        force: bool,
+    },
+    /// Store an input saved by hand in the cache
+    Import {
+        year: u16,
+        day: u8,
+        /// Input file, read from stdin when omitted
+        file: Option<PathBuf>,
+    },
+    /// Print where a day's input is cached
+    Path { year: u16, day: u8 },
+    /// List the cached inputs
+    List,
+    /// Serve the cache at the site's URLs, for pointing AOC_BASE_URL at
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+    },
+}
+
+fn fetch(cache: &Cache, year: u16, day: u8, force: bool) -> Result<(), aoc_input::Error> {
+    if !force && cache.has_input(year, day) {
+        println!("{year} day {day} is already cached");
+        return Ok(());
+    }
+    let fetcher = Fetcher::from_env();
+    let input = fetcher.input(year, day)?;
+    let path = cache.store_input(year, day, &input)?;
+    // The page is only kept for its title, so carry on without it.
+    let page = fetcher.page(year, day).ok();
+    if let Some(page) = &page {
+        cache.store_page(year, day, page)?;
+    }
+    match page.as_deref().and_then(aoc_input::puzzle_title) {
+        Some(title) => println!("{year} day {day}: {title} -> {}", path.display()),
+        None => println!("{year} day {day} -> {}", path.display()),
+    }
+    Ok(())
+}
+
+fn import(cache: &Cache, year: u16, day: u8, file: Option<PathBuf>) -> Result<(), String> {
+    let input = match file {
+        Some(file) => std::fs::read_to_string(&file)
+            .map_err(|e| format!("could not read {}: {e}", file.display()))?,
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("could not read stdin: {e}"))?;
+            input
+        }
+    };
+    let path = cache
+        .store_input(year, day, &input)
+        .map_err(|e| e.to_string())?;
+    println!("{year} day {day} -> {}", path.display());
+    Ok(())
+}
+
+fn list(cache: &Cache) -> Result<(), aoc_input::Error> {
+    for (year, day) in cache.days()? {
+        let page = cache.page(year, day)?;
+        match page.as_deref().and_then(aoc_input::puzzle_title) {
+            Some(title) => println!("{year} day {day:>2}  {title}"),
+            None => println!("{year} day {day:>2}"),
+        }
+    }
+    Ok(())
+}
+
+fn serve(cache: &Cache, addr: &str) -> Result<(), aoc_input::Error> {
+    let server = FixtureServer::from_cache(cache)?.start(addr)?;
+    println!(
+        "serving {} on {}",
+        cache.root().display(),
+        server.base_url()
+    );
+    server.wait();
+    Ok(())
+}
+
+pub fn run(command: InputCommand) -> Result<(), String> {
+    let cache = Cache::from_env();
+    match command {
+        InputCommand::Fetch { year, day, force } => {
+            fetch(&cache, year, day, force).map_err(|e| e.to_string())
+        }
+        InputCommand::Import { year, day, file } => import(&cache, year, day, file),
+        InputCommand::Path { year, day } => {
+            println!("{}", cache.input_path(year, day).display());
+            Ok(())
+        }
+        InputCommand::List => list(&cache).map_err(|e| e.to_string()),
+        InputCommand::Serve { addr } => serve(&cache, &addr).map_err(|e| e.to_string()),
+    }
+}
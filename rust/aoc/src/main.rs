@@ -0,0 +1,218 @@
+mod animate;
+mod answers;
+mod bench;
+mod history;
+mod input;
+mod registry;
+mod scaffold;
+mod verify;
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_core::Error;
+use clap::{Parser, Subcommand};
+use input::InputCommand;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle
+    Run {
+        year: u16,
+        day: u8,
+        /// Only solve this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file, or `-` for stdin. Read from the input cache when omitted
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Manage the cached puzzle inputs
+    #[command(subcommand)]
+    Input(InputCommand),
+    /// List the registered days
+    List,
+    /// Start a new day's crate from the template, and add it to the workspace and runner
+    New {
+        year: u16,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Workspace to add the day to
+        #[arg(long, default_value = scaffold::DEFAULT_ROOT)]
+        root: PathBuf,
+    },
+    /// Watch a day's solution run, in the terminal or written out as images
+    Animate {
+        year: u16,
+        day: u8,
+        /// Puzzle input file, or `-` for stdin. Read from the input cache when omitted
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = animate::Format::Terminal)]
+        format: animate::Format,
+        /// Directory for the PNGs, or file for the GIF
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Pixels per cell in PNGs and GIFs
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+        /// Milliseconds each frame is shown for
+        #[arg(long, default_value_t = 50)]
+        delay: u64,
+    },
+    /// Check every registered day's answers against the known ones
+    Verify {
+        /// Only check this year
+        year: Option<u16>,
+        /// Only check this day
+        #[arg(long, requires = "year")]
+        day: Option<u8>,
+        /// Directory of `<year>.toml` answer files
+        #[arg(long, default_value = answers::DEFAULT_DIR)]
+        answers: PathBuf,
+    },
+    /// Time each registered day's parse and parts, comparing with the previous run
+    Bench {
+        /// Only time this year
+        year: Option<u16>,
+        /// Only time this day
+        #[arg(long, requires = "year")]
+        day: Option<u8>,
+        /// Solve each day this many times and keep the fastest
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// Flag steps that got this many percent slower than the previous run
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// History file. Defaults to bench-history.jsonl in the input cache's directory
+        #[arg(long)]
+        history: Option<PathBuf>,
+        /// Compare with the history without adding this run to it
+        #[arg(long)]
+        no_save: bool,
+    },
+}
+
+fn read_input(year: u16, day: u8, path: Option<PathBuf>) -> Result<String, String> {
+    match path {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("could not read stdin: {e}"))?;
+            Ok(input)
+        }
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {e}", path.display())),
+        None => aoc_input::load(year, day).map_err(|e| e.to_string()),
+    }
+}
+
+fn run(year: u16, day: u8, part: Option<u8>, input: Option<PathBuf>) -> Result<(), String> {
+    let entry =
+        registry::find(year, day).ok_or_else(|| format!("{year} day {day} is not registered"))?;
+    let input = read_input(year, day, input)?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut failed = false;
+    for part in parts {
+        let start = Instant::now();
+        let answer = (entry.solve)(&input, part);
+        let elapsed = start.elapsed();
+        match answer {
+            Ok(answer) => println!("{year} day {day} part {part}: {answer} ({elapsed:.2?})"),
+            Err(Error::Unsolved) => println!("{year} day {day} part {part}: not available"),
+            Err(e) => {
+                failed = true;
+                println!("{year} day {day} part {part}: error: {e} ({elapsed:.2?})");
+            }
+        }
+    }
+    if failed {
+        Err("not every part could be solved".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn list() {
+    for day in registry::DAYS {
+        let animated = if day.animate.is_some() {
+            " (animated)"
+        } else {
+            ""
+        };
+        println!("{} day {:>2}{animated}", day.year, day.day);
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+        } => run(year, day, part, input),
+        Command::Input(command) => input::run(command),
+        Command::List => {
+            list();
+            Ok(())
+        }
+        Command::New { year, day, root } => {
+            scaffold::run(&root, &aoc_input::Cache::from_env(), year, day)
+        }
+        Command::Animate {
+            year,
+            day,
+            input,
+            format,
+            output,
+            scale,
+            delay,
+        } => animate::run(animate::Options {
+            year,
+            day,
+            input,
+            format,
+            output,
+            scale: scale as usize,
+            delay: Duration::from_millis(delay),
+        }),
+        Command::Verify { year, day, answers } => verify::run(&answers, year, day),
+        Command::Bench {
+            year,
+            day,
+            runs,
+            threshold,
+            history,
+            no_save,
+        } => bench::run(bench::Options {
+            year,
+            day,
+            runs,
+            threshold,
+            history,
+            save: !no_save,
+        }),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
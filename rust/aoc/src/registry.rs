@@ -0,0 +1,119 @@
+use aoc_core::{Error, Solution, Timings};
+use aoc_render::{Animate, Backend};
+
+pub type Solver = fn(&str, u8) -> Result<String, Error>;
+pub type Timer = fn(&str) -> Result<Timings, Error>;
+pub type Animator = fn(&str, &mut dyn Backend) -> Result<(), String>;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: Solver,
+    pub time: Timer,
+    /// For the days that can show their solution running.
+    pub animate: Option<Animator>,
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+fn animate<S: Solution + Animate>(input: &str, backend: &mut dyn Backend) -> Result<(), String> {
+    let solution = S::parse(input).map_err(|e| e.to_string())?;
+    solution
+        .animate(backend)
+        .map_err(|e| format!("could not write a frame: {e}"))
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $solution:ty) => {
+        Day {
+            year: $year,
+            day: $day,
+            solve: aoc_core::solve::<$solution>,
+            time: aoc_core::time::<$solution>,
+            animate: None,
+        }
+    };
+    ($year:literal, $day:literal, $solution:ty, animated) => {
+        Day {
+            animate: Some(animate::<$solution>),
+            ..day!($year, $day, $solution)
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(2018, 1, day_1_frequency::FrequencyChanges),
+    day!(2018, 2, day_2_inventory_management_system::BoxIds),
+    day!(2018, 3, day_3_overlap::Claims),
+    day!(2018, 4, day_4_sleep::Nights),
+    day!(2018, 5, day_5_polymer::Polymer),
+    day!(2018, 6, day_6_chronal_coordinates::Coordinates),
+    day!(2018, 7, day_7_dag::Instructions),
+    day!(2018, 8, day_8_license_file::Node),
+    day!(2018, 9, day_9_marble_game::Game),
+    day!(2018, 10, day_10_stars::Stars),
+    day!(2018, 11, day_11_chronal_charge::FuelGrid),
+    day!(2018, 12, day_12_subterranean_sustainability::Cave),
+    day!(2018, 13, day_13_mine_cart_madness::Simulation),
+    day!(2018, 14, day_14_chocolate_charts::PuzzleInput),
+    day!(2018, 15, day_15_beverage_bandits::Battle, animated),
+    day!(2018, 16, day_16_chronal_classification::Manual),
+    day!(2019, 1, day_1_fuel_requirements::Modules),
+    day!(2019, 2, day_2_program_alarm::GravityAssist),
+    day!(2022, 1, year_2022_day_01::Elves),
+    day!(2022, 2, year_2022_day_02::StrategyGuide),
+    day!(2022, 3, year_2022_day_03::Backpacks),
+    day!(2022, 4, year_2022_day_04::ElfPairs),
+    day!(2022, 5, year_2022_day_05::Procedure),
+    day!(2022, 6, year_2022_day_06::Datastream),
+    day!(2022, 7, year_2022_day_07::DirectorySizes),
+    day!(2022, 8, year_2022_day_08::Forest),
+    day!(2022, 9, year_2022_day_09::Motions),
+    day!(2022, 10, year_2022_day_10::Program),
+    day!(2022, 11, year_2022_day_11::Monkeys),
+    day!(2022, 12, year_2022_day_12::HeightMap),
+    day!(2022, 13, year_2022_day_13::DistressSignal),
+    day!(2022, 14, year_2022_day_14::Cave, animated),
+    day!(2022, 15, year_2022_day_15::Sensors),
+    day!(2022, 16, year_2022_day_16::World),
+    day!(2022, 17, year_2022_day_17::Jets, animated),
+    day!(2022, 18, year_2022_day_18::Scan),
+    day!(2022, 19, year_2022_day_19::Blueprints),
+    day!(2022, 20, year_2022_day_20::EncryptedFile),
+    day!(2022, 21, year_2022_day_21::Troop),
+    day!(2022, 22, year_2022_day_22::Notes),
+    day!(2022, 23, year_2022_day_23::Grove),
+    day!(2022, 24, year_2022_day_24::Valley),
+    day!(2022, 25, year_2022_day_25::FuelRequirements),
+    day!(2023, 9, year_2023_day_09::Report),
+    day!(2023, 10, year_2023_day_10::PipeMaze),
+    day!(2023, 11, year_2023_day_11::Image),
+    day!(2023, 24, year_2023_day_24::Hailstorm),
+    day!(2024, 1, year_2024_day_01::LocationLists),
+    day!(2024, 2, year_2024_day_02::UnusualData),
+    day!(2024, 3, year_2024_day_03::Memory),
+    day!(2024, 4, year_2024_day_04::WordSearch),
+    day!(2024, 5, year_2024_day_05::World),
+    day!(2024, 6, year_2024_day_06::World),
+    day!(2024, 7, year_2024_day_07::Equations),
+    day!(2024, 8, year_2024_day_08::Map),
+    day!(2024, 9, year_2024_day_09::Disk),
+    day!(2024, 10, year_2024_day_10::Map),
+    day!(2024, 11, year_2024_day_11::Pebbles),
+    day!(2024, 12, year_2024_day_12::GardenMap),
+    day!(2024, 13, year_2024_day_13::ClawMachines),
+    day!(2024, 14, year_2024_day_14::Robots, animated),
+    day!(2024, 15, year_2024_day_15::Warehouses, animated),
+    day!(2024, 16, year_2024_day_16::Maze),
+    day!(2024, 17, year_2024_day_17::Computer),
+    day!(2024, 18, year_2024_day_18::MazeInput),
+    day!(2024, 19, year_2024_day_19::Onsen),
+    day!(2024, 20, year_2024_day_20::Maze),
+    day!(2024, 21, year_2024_day_21::DoorCodes),
+    day!(2024, 22, year_2024_day_22::Buyers),
+    day!(2024, 23, year_2024_day_23::Network),
+    day!(2024, 24, year_2024_day_24::PuzzleInput),
+    day!(2024, 25, year_2024_day_25::Schematics),
+];
@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::Path;
+
+use aoc_input::Cache;
+
+use crate::registry;
+
+/// The workspace this binary was built from.
+pub const DEFAULT_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+const MANIFEST: &str = include_str!("../templates/Cargo.toml.txt");
+const LIBRARY: &str = include_str!("../templates/lib.rs.txt");
+const BUILD_SCRIPT: &str = include_str!("../templates/build.rs.txt");
+
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{padded}", &format!("{day:02}"))
+}
+
+/// Where in `lines` a new line with `key` goes, given how to read the key of the lines
+/// it's kept in order with. Goes after the last of them if none sort after it, or at
+/// `fallback` if there are none.
+fn insertion_point<K: Ord>(
+    lines: &[&str],
+    key: K,
+    mut key_of: impl FnMut(&str) -> Option<K>,
+    fallback: usize,
+) -> usize {
+    let mut last = None;
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(k) = key_of(line) {
+            if k > key {
+                return i;
+            }
+            last = Some(i + 1);
+        }
+    }
+    last.unwrap_or(fallback)
+}
+
+fn insert_line(text: &str, at: usize, line: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    lines.insert(at, line);
+    lines.join("\n") + "\n"
+}
+
+/// The number at the start of `text`, and what follows it.
+fn leading_number(text: &str) -> Option<(u16, &str)> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    Some((text[..end].parse().ok()?, &text[end..]))
+}
+
+/// Adds the year's `day_*` glob to the workspace members, unless it's there already.
+fn add_workspace_member(manifest: &str, year: u16) -> Result<String, String> {
+    let member = format!("    \"{year}/day_*\",");
+    let lines: Vec<&str> = manifest.lines().collect();
+    if lines.contains(&member.as_str()) {
+        return Ok(manifest.to_string());
+    }
+    let end = lines
+        .iter()
+        .position(|l| l.trim() == "]")
+        .ok_or("the workspace manifest has no members list")?;
+    let at = insertion_point(
+        &lines[..end],
+        year,
+        |l| leading_number(l.trim().strip_prefix('"')?).map(|(y, _)| y),
+        end,
+    );
+    Ok(insert_line(manifest, at, &member))
+}
+
+/// Adds the day's crate to the runner's dependencies, in order of year and day.
+fn add_dependency(manifest: &str, year: u16, day: u8) -> String {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let at = insertion_point(
+        &lines,
+        (year, u16::from(day)),
+        |l| {
+            let path = &l[l.find("path = \"../")? + "path = \"../".len()..];
+            let (y, rest) = leading_number(path)?;
+            let (d, _) = leading_number(rest.strip_prefix("/day_")?)?;
+            Some((y, d))
+        },
+        lines.len(),
+    );
+    let line = format!("year_{year}_day_{day:02} = {{ path = \"../{year}/day_{day:02}\" }}");
+    insert_line(manifest, at, &line)
+}
+
+/// Adds the day to the runner's registry, in order of year and day.
+fn add_registry_entry(source: &str, year: u16, day: u8) -> Result<String, String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let end = lines
+        .iter()
+        .rposition(|l| l.trim() == "];")
+        .ok_or("the registry has no list of days")?;
+    let at = insertion_point(
+        &lines[..end],
+        (year, u16::from(day)),
+        |l| {
+            let (y, rest) = leading_number(l.trim().strip_prefix("day!(")?)?;
+            let (d, _) = leading_number(rest.strip_prefix(", ")?)?;
+            Some((y, d))
+        },
+        end,
+    );
+    let line = format!("    day!({year}, {day}, year_{year}_day_{day:02}::PuzzleInput),");
+    Ok(insert_line(source, at, &line))
+}
+
+fn edit(path: &Path, change: impl FnOnce(&str) -> Result<String, String>) -> Result<(), String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    fs::write(path, change(&text)?).map_err(|e| format!("could not write {}: {e}", path.display()))
+}
+
+/// Creates a crate for a new day under `root` from the template, adds it to the workspace
+/// and the runner, and leaves an empty input in `cache` to paste into if none is cached.
+/// Refuses if the day already has a crate or is registered.
+pub fn run(root: &Path, cache: &Cache, year: u16, day: u8) -> Result<(), String> {
+    let dir = root.join(year.to_string()).join(format!("day_{day:02}"));
+    if registry::find(year, day).is_some() {
+        return Err(format!("{year} day {day} is already registered"));
+    }
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|e| format!("could not write {}: {e}", path.display()))
+    };
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("could not create {}: {e}", dir.display()))?;
+    write(&dir.join("Cargo.toml"), &fill(MANIFEST, year, day))?;
+    write(&dir.join("src/lib.rs"), &fill(LIBRARY, year, day))?;
+    write(&dir.join("build.rs"), &fill(BUILD_SCRIPT, year, day))?;
+    write(&dir.join("example_1.txt"), "")?;
+
+    edit(&root.join("Cargo.toml"), |m| add_workspace_member(m, year))?;
+    edit(&root.join("aoc/Cargo.toml"), |m| {
+        Ok(add_dependency(m, year, day))
+    })?;
+    edit(&root.join("aoc/src/registry.rs"), |s| {
+        add_registry_entry(s, year, day)
+    })?;
+
+    let input = cache
+        .store_placeholder(year, day)
+        .map_err(|e| e.to_string())?;
+
+    println!("created {}", dir.display());
+    println!("paste the example into example_1.txt");
+    if !cache.has_input(year, day) {
+        println!(
+            "paste the input into {}, or fetch it with `aoc input fetch {year} {day}`",
+            input.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_a_new_year_to_the_workspace() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2018/day_*\",\n    \"2019/intcode\",\n    \"2024/day_*\",\n]\n";
+        let added = add_workspace_member(manifest, 2020).unwrap();
+        assert_eq!(
+            added,
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2018/day_*\",\n    \"2019/intcode\",\n    \"2020/day_*\",\n    \"2024/day_*\",\n]\n"
+        );
+        assert_eq!(add_workspace_member(&added, 2024).unwrap(), added);
+    }
+
+    #[test]
+    fn keeps_dependencies_in_order() {
+        let manifest = "[dependencies]\nclap = \"4\"\nday_1_frequency = { path = \"../2018/day_1_frequency\" }\nyear_2024_day_02 = { path = \"../2024/day_02\" }\nyear_2024_day_10 = { path = \"../2024/day_10\" }\n";
+        assert_eq!(
+            add_dependency(manifest, 2024, 3).lines().nth(4),
+            Some("year_2024_day_03 = { path = \"../2024/day_03\" }")
+        );
+        assert_eq!(
+            add_dependency(manifest, 2025, 1).lines().last(),
+            Some("year_2025_day_01 = { path = \"../2025/day_01\" }")
+        );
+    }
+
+    #[test]
+    fn keeps_the_registry_in_order() {
+        let source = "pub static DAYS: &[Day] = &[\n    day!(2018, 1, day_1_frequency::FrequencyChanges),\n    day!(2024, 9, year_2024_day_09::Disk),\n    day!(2024, 15, year_2024_day_15::Warehouses, animated),\n];\n";
+        let added = add_registry_entry(source, 2024, 10).unwrap();
+        assert_eq!(
+            added.lines().nth(3),
+            Some("    day!(2024, 10, year_2024_day_10::PuzzleInput),")
+        );
+        let added = add_registry_entry(source, 2025, 1).unwrap();
+        assert_eq!(
+            added.lines().nth(4),
+            Some("    day!(2025, 1, year_2025_day_01::PuzzleInput),")
+        );
+    }
+
+    #[test]
+    fn creates_a_day_and_refuses_to_overwrite_it() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"aoc\",\n]\n").unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\n").unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), "&[\n];\n").unwrap();
+
+        let cache = Cache::new(root.join("cache"));
+        run(&root, &cache, 2030, 7).unwrap();
+        let lib = fs::read_to_string(root.join("2030/day_07/src/lib.rs")).unwrap();
+        assert!(lib.contains("aoc_input::challenge(2030, 7)"));
+        let manifest = fs::read_to_string(root.join("2030/day_07/Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"year_2030_day_07\""));
+        let build_script = fs::read_to_string(root.join("2030/day_07/build.rs")).unwrap();
+        assert!(build_script.contains("aoc_input::check_challenge_input(2030, 7)"));
+        assert!(root.join("2030/day_07/example_1.txt").is_file());
+        assert_eq!(fs::read_to_string(cache.input_path(2030, 7)).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "members = [\n    \"aoc\",\n    \"2030/day_*\",\n]\n"
+        );
+
+        fs::write(root.join("2030/day_07/src/lib.rs"), "// solved").unwrap();
+        assert!(run(&root, &cache, 2030, 7).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("2030/day_07/src/lib.rs")).unwrap(),
+            "// solved"
+        );
+        assert!(run(&root, &cache, 2024, 1)
+            .unwrap_err()
+            .contains("already registered"));
+
+        cache.store_input(2030, 8, "3   4\n").unwrap();
+        run(&root, &cache, 2030, 8).unwrap();
+        assert_eq!(cache.input(2030, 8).unwrap(), "3   4\n");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::btree_map::{BTreeMap, Entry};
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use aoc_core::Error;
+
+use crate::answers::{DayAnswers, YearAnswers};
+use crate::registry::{self, Day};
+
+enum Status {
+    Pass,
+    /// Got an answer other than the known one, or an error instead of one.
+    Fail(String),
+    /// Nothing to compare the answer with.
+    NoAnswer,
+    /// Nothing to solve, because the input isn't cached.
+    NoInput,
+    /// The solution doesn't answer this part.
+    Unsolved,
+}
+
+impl Status {
+    fn cell(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail(_) => "FAIL",
+            Self::NoAnswer => "missing (no answer)",
+            Self::NoInput => "missing (no input)",
+            Self::Unsolved => "missing (unsolved)",
+        }
+    }
+}
+
+fn check(day: &Day, input: &str, part: u8, expected: Option<&str>) -> Status {
+    match ((day.solve)(input, part), expected) {
+        (Err(Error::Unsolved), _) => Status::Unsolved,
+        (Err(e), _) => Status::Fail(format!("error: {e}")),
+        (Ok(_), None) => Status::NoAnswer,
+        (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+        (Ok(answer), Some(expected)) => Status::Fail(format!("expected {expected}, got {answer}")),
+    }
+}
+
+fn verify_day(day: &Day, answers: Option<&DayAnswers>) -> [Status; 2] {
+    let input = match aoc_input::load(day.year, day.day) {
+        Ok(input) => input,
+        Err(aoc_input::Error::Missing { .. }) => return [Status::NoInput, Status::NoInput],
+        Err(e) => return [Status::Fail(e.to_string()), Status::Fail(e.to_string())],
+    };
+    [1, 2].map(|part| check(day, &input, part, answers.and_then(|a| a.part(part))))
+}
+
+/// Solves every registered day that matches the filters and compares the answers with
+/// the ones in `answers_dir`. Fails if any part gave a different answer.
+pub fn run(answers_dir: &Path, year: Option<u16>, day: Option<u8>) -> Result<(), String> {
+    let days: Vec<&Day> = registry::DAYS
+        .iter()
+        .filter(|d| year.is_none_or(|y| d.year == y) && day.is_none_or(|n| d.day == n))
+        .collect();
+    if days.is_empty() {
+        return Err("no registered days match".to_string());
+    }
+
+    let mut answers = BTreeMap::new();
+    let mut failures = Vec::new();
+    let (mut passed, mut missing) = (0, 0);
+    println!(
+        "{:<4}  {:>3}  {:<19}  {:<19}  {:>9}",
+        "year", "day", "part 1", "part 2", "time"
+    );
+    for day in days {
+        let year_answers = match answers.entry(day.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(YearAnswers::load(answers_dir, day.year)?),
+        };
+        let start = Instant::now();
+        let statuses = verify_day(day, year_answers.day(day.day));
+        let elapsed = start.elapsed();
+        println!(
+            "{:<4}  {:>3}  {:<19}  {:<19}  {:>9}",
+            day.year,
+            day.day,
+            statuses[0].cell(),
+            statuses[1].cell(),
+            format!("{elapsed:.2?}")
+        );
+        // Slow days take a while, so show each row as soon as it's ready.
+        let _ = std::io::stdout().flush();
+        for (part, status) in (1..).zip(statuses) {
+            match status {
+                Status::Pass => passed += 1,
+                Status::NoAnswer | Status::NoInput | Status::Unsolved => missing += 1,
+                Status::Fail(reason) => failures.push(format!(
+                    "{} day {} part {part}: {reason}",
+                    day.year, day.day
+                )),
+            }
+        }
+    }
+
+    println!();
+    for failure in &failures {
+        println!("{failure}");
+    }
+    println!(
+        "{passed} passed, {} failed, {missing} missing",
+        failures.len()
+    );
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err("some answers did not match".to_string())
+    }
+}
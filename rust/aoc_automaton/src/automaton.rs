@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::board::{Board, Sparse};
+use crate::position::{neighbours, window, Position};
+
+/// The neighbourhood patterns that leave a cell live in the next generation. A pattern is
+/// the cell's [`window`], read with x changing fastest.
+#[derive(Debug, Clone, Default)]
+pub struct RuleTable {
+    radius: i64,
+    live: HashSet<u64>,
+}
+
+impl RuleTable {
+    pub fn new(radius: i64) -> Self {
+        Self {
+            radius,
+            live: HashSet::new(),
+        }
+    }
+
+    /// Makes cells whose neighbourhood looks like `pattern` live in the next generation.
+    pub fn insert(&mut self, pattern: &[bool]) {
+        self.live.insert(Self::key(pattern.iter().copied()));
+    }
+
+    pub fn radius(&self) -> i64 {
+        self.radius
+    }
+
+    fn key(pattern: impl Iterator<Item = bool>) -> u64 {
+        pattern.fold(0, |key, live| key << 1 | u64::from(live))
+    }
+
+    fn next<P: Position>(&self, board: &impl Board<P>, cell: P) -> bool {
+        let pattern = window(cell, self.radius).map(|p| board.is_live(p));
+        self.live.contains(&Self::key(pattern))
+    }
+}
+
+/// A board and how many generations it has been stepped.
+#[derive(Debug, Clone)]
+pub struct Automaton<P, B = Sparse<P>> {
+    board: B,
+    generation: u64,
+    limits: Option<(P, P)>,
+    position: PhantomData<P>,
+}
+
+impl<P: Position, B: Board<P>> Automaton<P, B> {
+    pub fn new(board: B) -> Self {
+        Self {
+            board,
+            generation: 0,
+            limits: None,
+            position: PhantomData,
+        }
+    }
+
+    /// Keeps every cell outside the box between two corners dead.
+    pub fn bounded(mut self, lo: P, hi: P) -> Self {
+        self.limits = Some((lo, hi));
+        self
+    }
+
+    pub fn board(&self) -> &B {
+        &self.board
+    }
+
+    pub fn into_board(self) -> B {
+        self.board
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn state_hash(&self) -> u64 {
+        self.board.state_hash()
+    }
+
+    fn within_limits(&self, cell: P) -> bool {
+        self.limits.is_none_or(|(lo, hi)| {
+            let (lo, hi, c) = (lo.coords(), hi.coords(), cell.coords());
+            (0..3).all(|axis| lo[axis] <= c[axis] && c[axis] <= hi[axis])
+        })
+    }
+
+    /// Moves on a generation, deciding each cell within `radius` of a live one with `rule`,
+    /// which sees the board as it was.
+    pub fn step(&mut self, radius: i64, mut rule: impl FnMut(P, &B) -> bool) {
+        let candidates: BTreeSet<P> = self
+            .board
+            .live_cells()
+            .into_iter()
+            .flat_map(|cell| window(cell, radius))
+            .filter(|&cell| self.within_limits(cell))
+            .collect();
+        let next = B::from_cells(
+            candidates
+                .into_iter()
+                .filter(|&cell| rule(cell, &self.board)),
+        );
+        self.board = next;
+        self.generation += 1;
+    }
+
+    /// Moves on a generation by looking each cell's neighbourhood up in `table`.
+    pub fn step_table(&mut self, table: &RuleTable) {
+        self.step(table.radius, |cell, board| table.next(board, cell));
+    }
+
+    /// Moves on a generation by a rule given whether a cell is live and how many of its
+    /// neighbours are. The Game of Life is `|live, n| n == 3 || live && n == 2`.
+    pub fn step_life(&mut self, rule: impl Fn(bool, usize) -> bool) {
+        self.step(1, |cell, board| {
+            let live_neighbours = neighbours(cell).filter(|&n| board.is_live(n)).count();
+            rule(board.is_live(cell), live_neighbours)
+        });
+    }
+
+    /// Moves on a generation in two phases. First every live cell proposes where to go,
+    /// which may be where it is, seeing the board as it was. Then each cell moves to its
+    /// proposal if nothing else proposed the same place, and stays put otherwise. Returns
+    /// whether anything moved.
+    ///
+    /// A cell that stays put is treated as proposing its own position, so cells should only
+    /// propose places that are empty.
+    pub fn step_moves(&mut self, mut propose: impl FnMut(P, &B) -> P) -> bool {
+        let proposals: Vec<(P, P)> = self
+            .board
+            .live_cells()
+            .into_iter()
+            .map(|cell| (cell, propose(cell, &self.board)))
+            .collect();
+        let mut proposers = BTreeMap::<P, usize>::new();
+        for &(_, proposal) in &proposals {
+            *proposers.entry(proposal).or_default() += 1;
+        }
+        let mut moved = false;
+        self.board = B::from_cells(proposals.into_iter().map(|(cell, proposal)| {
+            if proposal != cell && proposers[&proposal] == 1 && self.within_limits(proposal) {
+                moved = true;
+                proposal
+            } else {
+                cell
+            }
+        }));
+        self.generation += 1;
+        moved
+    }
+}
+
+/// The generation, then a picture of the board.
+impl<P: Position, B: Board<P>> fmt::Display for Automaton<P, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "generation {}:", self.generation)?;
+        f.write_str(&self.board.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_geometry::{Vec2, Vec3};
+
+    use crate::Dense;
+
+    use super::*;
+
+    fn life(live: bool, n: usize) -> bool {
+        n == 3 || live && n == 2
+    }
+
+    #[test]
+    fn glides_the_same_on_either_board() {
+        let glider = ".#.\n..#\n###\n";
+        let mut sparse = Automaton::new(Sparse::<Vec2<i32>>::from_text(glider));
+        let mut dense = Automaton::new(Dense::<Vec2<i32>>::from_text(glider));
+        for _ in 0..4 {
+            sparse.step_life(life);
+            dense.step_life(life);
+            assert_eq!(sparse.state_hash(), dense.state_hash());
+        }
+        // four generations on, a glider is the same shape one cell down and to the right
+        let moved = Sparse::from_cells(
+            Sparse::<Vec2<i32>>::from_text(glider)
+                .live_cells()
+                .into_iter()
+                .map(|cell| cell + Vec2::new(1, 1)),
+        );
+        assert_eq!(sparse.board(), &moved);
+        assert_eq!(sparse.to_string(), "generation 4:\n.#.\n..#\n###\n");
+    }
+
+    #[test]
+    fn grows_conway_cubes() {
+        // the example from 2020 day 17
+        let mut cubes = Automaton::new(Dense::<Vec3<i64>>::from_text(".#.\n..#\n###\n"));
+        for _ in 0..6 {
+            cubes.step_life(|live, n| n == 3 || live && n == 2);
+        }
+        assert_eq!(cubes.board().population(), 112);
+    }
+
+    #[test]
+    fn looks_up_rule_tables() {
+        // rule 90: a cell is live when exactly one of its neighbours was
+        let mut table = RuleTable::new(1);
+        for pattern in [[true, false, false], [true, true, false]] {
+            table.insert(&pattern);
+            table.insert(&[pattern[2], pattern[1], pattern[0]]);
+        }
+        let mut line = Automaton::new(Sparse::from_cells([0i64]));
+        for _ in 0..3 {
+            line.step_table(&table);
+        }
+        assert_eq!(line.board().render(), "#.#.#.#\n");
+    }
+
+    #[test]
+    fn keeps_to_its_bounds() {
+        let blinker = Sparse::<Vec2<i16>>::from_text("###\n");
+        let mut bounded = Automaton::new(blinker).bounded(Vec2::new(0, 0), Vec2::new(2, 0));
+        bounded.step_life(life);
+        assert_eq!(bounded.board().population(), 1);
+    }
+
+    #[test]
+    fn resolves_clashing_moves() {
+        // each cell moves towards x = 0; the two cells two apart clash and stay put
+        let mut line = Automaton::new(Sparse::from_cells([-1i64, 1, 5]));
+        let moved = line.step_moves(|cell, _| cell - cell.signum());
+        assert!(moved);
+        assert_eq!(line.board().live_cells(), [-1, 1, 4]);
+        assert_eq!(line.generation(), 1);
+    }
+}
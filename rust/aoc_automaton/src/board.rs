@@ -0,0 +1,287 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::position::{bounds, cuboid, Position};
+
+/// Which cells are live.
+pub trait Board<P: Position>: Clone + Default {
+    fn is_live(&self, position: P) -> bool;
+
+    fn set(&mut self, position: P, live: bool);
+
+    /// The live cells, in order.
+    fn live_cells(&self) -> Vec<P>;
+
+    fn population(&self) -> usize {
+        self.live_cells().len()
+    }
+
+    fn from_cells(cells: impl IntoIterator<Item = P>) -> Self {
+        let mut board = Self::default();
+        for cell in cells {
+            board.set(cell, true);
+        }
+        board
+    }
+
+    /// Reads a picture with `#` for live cells, one row per line. Rows count y, so a
+    /// picture of a line should be a single row.
+    fn from_text(input: &str) -> Self {
+        Self::from_cells(input.lines().enumerate().flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| P::from_coords([x as i64, y as i64, 0]))
+        }))
+    }
+
+    /// The corners of the smallest box holding every live cell.
+    fn bounds(&self) -> Option<(P, P)> {
+        bounds(self.live_cells()).map(|(lo, hi)| (P::from_coords(lo), P::from_coords(hi)))
+    }
+
+    /// A hash of which cells are live, the same for equal boards of either kind.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.live_cells().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// A hash of the live cells relative to the corner of their bounds, so a pattern hashes
+    /// the same wherever it has moved to.
+    fn shape_hash(&self) -> u64 {
+        let cells = self.live_cells();
+        let lo = bounds(cells.iter().copied()).map_or([0; 3], |(lo, _)| lo);
+        let mut hasher = DefaultHasher::new();
+        for cell in cells {
+            let c = cell.coords();
+            [c[0] - lo[0], c[1] - lo[1], c[2] - lo[2]].hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// A picture of the live cells within their bounds, `#` for live and `.` for dead. Space
+    /// is drawn a layer at a time, each headed with its z.
+    fn render(&self) -> String {
+        let Some((lo, hi)) = bounds(self.live_cells()) else {
+            return String::new();
+        };
+        let mut picture = String::new();
+        for z in lo[2]..=hi[2] {
+            if P::DIMENSIONS == 3 {
+                if z != lo[2] {
+                    picture.push('\n');
+                }
+                picture += &format!("z={z}\n");
+            }
+            for y in lo[1]..=hi[1] {
+                for cell in cuboid::<P>([lo[0], y, z], [hi[0], y, z]) {
+                    picture.push(if self.is_live(cell) { '#' } else { '.' });
+                }
+                picture.push('\n');
+            }
+        }
+        picture
+    }
+}
+
+/// A board that only keeps its live cells, so it can grow without limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sparse<P>(BTreeSet<P>);
+
+impl<P> Default for Sparse<P> {
+    fn default() -> Self {
+        Self(BTreeSet::new())
+    }
+}
+
+impl<P: Position> Board<P> for Sparse<P> {
+    fn is_live(&self, position: P) -> bool {
+        self.0.contains(&position)
+    }
+
+    fn set(&mut self, position: P, live: bool) {
+        if live {
+            self.0.insert(position);
+        } else {
+            self.0.remove(&position);
+        }
+    }
+
+    fn live_cells(&self) -> Vec<P> {
+        self.0.iter().copied().collect()
+    }
+
+    fn population(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A board that keeps a flag for every cell in a box, which grows whenever a cell outside
+/// it comes alive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dense<P> {
+    lo: [i64; 3],
+    size: [usize; 3],
+    cells: Vec<bool>,
+    position: PhantomData<P>,
+}
+
+impl<P> Default for Dense<P> {
+    fn default() -> Self {
+        Self {
+            lo: [0; 3],
+            size: [0; 3],
+            cells: Vec::new(),
+            position: PhantomData,
+        }
+    }
+}
+
+impl<P: Position> Dense<P> {
+    fn index(&self, position: P) -> Option<usize> {
+        let c = position.coords();
+        let mut index = 0;
+        for axis in (0..3).rev() {
+            let offset = usize::try_from(c[axis] - self.lo[axis]).ok()?;
+            if offset >= self.size[axis] {
+                return None;
+            }
+            index = index * self.size[axis] + offset;
+        }
+        Some(index)
+    }
+
+    /// Makes room for `position`, with some to spare on the side it grew towards.
+    fn grow_to(&mut self, position: P) {
+        let c = position.coords();
+        let mut lo = self.lo;
+        let mut size = self.size;
+        for axis in 0..P::DIMENSIONS {
+            let margin = (self.size[axis] / 2 + 1) as i64;
+            let hi = self.lo[axis] + self.size[axis] as i64 - 1;
+            if self.cells.is_empty() {
+                lo[axis] = c[axis];
+                size[axis] = 1;
+            } else if c[axis] < lo[axis] {
+                lo[axis] = c[axis] - margin;
+                size[axis] = (hi - lo[axis] + 1) as usize;
+            } else if c[axis] > hi {
+                size[axis] = (c[axis] + margin - lo[axis] + 1) as usize;
+            }
+        }
+        size[P::DIMENSIONS..].fill(1);
+        let mut grown = Self {
+            lo,
+            size,
+            cells: vec![false; size.iter().product()],
+            position: PhantomData,
+        };
+        for cell in self.live_cells() {
+            let index = grown
+                .index(cell)
+                .expect("the grown board covers the old one");
+            grown.cells[index] = true;
+        }
+        *self = grown;
+    }
+}
+
+impl<P: Position> Board<P> for Dense<P> {
+    fn is_live(&self, position: P) -> bool {
+        self.index(position).is_some_and(|i| self.cells[i])
+    }
+
+    fn set(&mut self, position: P, live: bool) {
+        match self.index(position) {
+            Some(i) => self.cells[i] = live,
+            None if live => {
+                self.grow_to(position);
+                self.set(position, live);
+            }
+            None => {}
+        }
+    }
+
+    fn live_cells(&self) -> Vec<P> {
+        let hi = [0, 1, 2].map(|axis| self.lo[axis] + self.size[axis] as i64 - 1);
+        let mut cells: Vec<P> = cuboid(self.lo, hi)
+            .zip(&self.cells)
+            .filter(|&(_, &live)| live)
+            .map(|(cell, _)| cell)
+            .collect();
+        cells.sort_unstable();
+        cells
+    }
+}
+
+impl<P: Position> fmt::Display for Sparse<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+impl<P: Position> fmt::Display for Dense<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_geometry::{Vec2, Vec3};
+
+    use super::*;
+
+    const GLIDER: &str = ".#.\n..#\n###\n";
+
+    #[test]
+    fn reads_and_draws_a_picture() {
+        let sparse = Sparse::<Vec2<i64>>::from_text(GLIDER);
+        let dense = Dense::<Vec2<i64>>::from_text(GLIDER);
+        assert_eq!(sparse.population(), 5);
+        assert_eq!(sparse.render(), GLIDER);
+        assert_eq!(dense.render(), GLIDER);
+        assert_eq!(sparse.state_hash(), dense.state_hash());
+    }
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut dense = Dense::<Vec3<i32>>::default();
+        let cells = [
+            Vec3::new(0, 0, 0),
+            Vec3::new(-5, 2, 1),
+            Vec3::new(3, -4, -2),
+            Vec3::new(9, 9, 9),
+        ];
+        for cell in cells {
+            dense.set(cell, true);
+        }
+        let mut sorted = cells.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(dense.live_cells(), sorted);
+        assert_eq!(
+            dense.bounds(),
+            Some((Vec3::new(-5, -4, -2), Vec3::new(9, 9, 9)))
+        );
+        dense.set(Vec3::new(9, 9, 9), false);
+        assert_eq!(dense.population(), 3);
+    }
+
+    #[test]
+    fn hashes_shapes_wherever_they_are() {
+        let here = Sparse::<i64>::from_cells([1, 2, 4]);
+        let there = Sparse::<i64>::from_cells([11, 12, 14]);
+        assert_ne!(here.state_hash(), there.state_hash());
+        assert_eq!(here.shape_hash(), there.shape_hash());
+    }
+
+    #[test]
+    fn draws_space_in_layers() {
+        let board = Sparse::from_cells([Vec3::new(0i64, 0, 0), Vec3::new(1, 0, 1)]);
+        assert_eq!(board.render(), "z=0\n#.\n\nz=1\n.#\n");
+    }
+}
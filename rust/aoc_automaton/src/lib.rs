@@ -0,0 +1,19 @@
+//! Boards of live and dead cells that step from one generation to the next by neighbourhood
+//! rules, like the Game of Life.
+//!
+//! Cells sit on a line, a plane or in space: any [`Position`], which covers plain integers,
+//! `Vec2` and `Vec3`. A [`Sparse`] board keeps just the live cells and suits patterns that
+//! spread out, and a [`Dense`] board keeps a flag for every cell in a box that grows to fit.
+//! An [`Automaton`] steps either kind, by a closure, a [`RuleTable`] of neighbourhood
+//! patterns, a count of live neighbours, or by having every cell propose a move and only
+//! making the moves nobody else wanted.
+//!
+//! Only cells near a live one are considered for the next generation, so rules must leave a
+//! cell with nothing live around it dead.
+mod automaton;
+mod board;
+mod position;
+
+pub use automaton::{Automaton, RuleTable};
+pub use board::{Board, Dense, Sparse};
+pub use position::{neighbours, window, Position};
@@ -0,0 +1,120 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use aoc_geometry::{Vec2, Vec3};
+
+/// Where a cell is on a line, a plane or in space.
+pub trait Position: Copy + Ord + Hash + Debug {
+    const DIMENSIONS: usize;
+
+    /// Its coordinates along x, y and z, with zeros for the axes it doesn't have.
+    fn coords(self) -> [i64; 3];
+
+    fn from_coords(coords: [i64; 3]) -> Self;
+}
+
+macro_rules! positions {
+    ($($t:ty),*) => {$(
+        impl Position for $t {
+            const DIMENSIONS: usize = 1;
+
+            fn coords(self) -> [i64; 3] {
+                [self.into(), 0, 0]
+            }
+
+            fn from_coords([x, _, _]: [i64; 3]) -> Self {
+                x as $t
+            }
+        }
+
+        impl Position for Vec2<$t> {
+            const DIMENSIONS: usize = 2;
+
+            fn coords(self) -> [i64; 3] {
+                [self.x.into(), self.y.into(), 0]
+            }
+
+            fn from_coords([x, y, _]: [i64; 3]) -> Self {
+                Vec2::new(x as $t, y as $t)
+            }
+        }
+
+        impl Position for Vec3<$t> {
+            const DIMENSIONS: usize = 3;
+
+            fn coords(self) -> [i64; 3] {
+                [self.x.into(), self.y.into(), self.z.into()]
+            }
+
+            fn from_coords([x, y, z]: [i64; 3]) -> Self {
+                Vec3::new(x as $t, y as $t, z as $t)
+            }
+        }
+    )*};
+}
+
+positions!(i16, i32, i64);
+
+/// Every position in the box between two corners, with x changing fastest and z slowest.
+pub(crate) fn cuboid<P: Position>(lo: [i64; 3], hi: [i64; 3]) -> impl Iterator<Item = P> {
+    (lo[2]..=hi[2]).flat_map(move |z| {
+        (lo[1]..=hi[1]).flat_map(move |y| (lo[0]..=hi[0]).map(move |x| P::from_coords([x, y, z])))
+    })
+}
+
+/// The corners of the smallest box holding every position, or `None` if there are none.
+pub(crate) fn bounds<P: Position>(
+    positions: impl IntoIterator<Item = P>,
+) -> Option<([i64; 3], [i64; 3])> {
+    positions.into_iter().fold(None, |bounds, p| {
+        let c = p.coords();
+        let (mut lo, mut hi) = bounds.unwrap_or((c, c));
+        for axis in 0..3 {
+            lo[axis] = lo[axis].min(c[axis]);
+            hi[axis] = hi[axis].max(c[axis]);
+        }
+        Some((lo, hi))
+    })
+}
+
+/// The position and everything within `radius` of it along each of its axes, with x changing
+/// fastest and z slowest. On a line with a radius of 2 that's the five pots from two to the
+/// left to two to the right.
+pub fn window<P: Position>(centre: P, radius: i64) -> impl Iterator<Item = P> {
+    let c = centre.coords();
+    let reach = |axis: usize| if axis < P::DIMENSIONS { radius } else { 0 };
+    let lo = [c[0] - reach(0), c[1] - reach(1), c[2] - reach(2)];
+    let hi = [c[0] + reach(0), c[1] + reach(1), c[2] + reach(2)];
+    cuboid(lo, hi)
+}
+
+/// The 2, 8 or 26 positions touching this one, diagonals included.
+pub fn neighbours<P: Position>(centre: P) -> impl Iterator<Item = P> {
+    window(centre, 1).filter(move |&p| p != centre)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_neighbours_in_each_dimension() {
+        assert_eq!(neighbours(5i64).collect::<Vec<_>>(), [4, 6]);
+        assert_eq!(neighbours(Vec2::new(0i16, 0)).count(), 8);
+        assert_eq!(neighbours(Vec3::new(0i32, 0, 0)).count(), 26);
+    }
+
+    #[test]
+    fn windows_read_left_to_right() {
+        assert_eq!(window(10i64, 2).collect::<Vec<_>>(), [8, 9, 10, 11, 12]);
+        assert_eq!(
+            window(Vec2::new(1i64, 1), 1).take(4).collect::<Vec<_>>(),
+            [
+                Vec2::new(0, 0),
+                Vec2::new(1, 0),
+                Vec2::new(2, 0),
+                Vec2::new(0, 1)
+            ]
+        );
+    }
+}
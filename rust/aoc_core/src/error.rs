@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::ParseError;
+
+/// Why a day couldn't produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle input wasn't in the expected format.
+    Parse(ParseError),
+    /// The input was understood, but has no answer.
+    NoAnswer(String),
+    /// This part hasn't been solved in code.
+    Unsolved,
+}
+
+impl Error {
+    /// See [`ParseError::new`].
+    pub fn parse(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        Self::Parse(ParseError::new(input, at, expected))
+    }
+
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        Self::NoAnswer(message.into())
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "could not parse input: {error}"),
+            Self::NoAnswer(message) => write!(f, "no answer: {message}"),
+            Self::Unsolved => write!(f, "not solved"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
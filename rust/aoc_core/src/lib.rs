@@ -0,0 +1,11 @@
+//! Pieces shared by every day's solution.
+
+mod error;
+mod parse_error;
+mod solution;
+
+pub use error::Error;
+#[cfg(feature = "nom")]
+pub use parse_error::finish;
+pub use parse_error::ParseError;
+pub use solution::{part1, part2, solve, time, Solution, Timings};
@@ -0,0 +1,133 @@
+use std::fmt;
+
+/// Where in the puzzle input parsing stopped, and what was expected there.
+///
+/// Lines and columns count from 1, columns in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Points at the start of `at`, which must be a slice of `input`: usually
+    /// the input a parser had left, or the line it was looking at.
+    pub fn new(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// Points just past the last character of `input`, for when it ran out
+    /// before something required turned up.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input, &input[input.len()..], expected)
+    }
+
+    /// Adapts the error from a nom parser that was given `input`, or a slice
+    /// of it.
+    #[cfg(feature = "nom")]
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::new(input, e.input, describe(e.code))
+            }
+            nom::Err::Incomplete(_) => Self::at_end(input, "more input"),
+        }
+    }
+}
+
+#[cfg(feature = "nom")]
+fn describe(kind: nom::error::ErrorKind) -> String {
+    use nom::error::ErrorKind;
+
+    match kind {
+        ErrorKind::Digit => "a number",
+        ErrorKind::Char => "a particular character",
+        ErrorKind::Tag => "particular text",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::Many1 | ErrorKind::SeparatedList | ErrorKind::Many1Count => "at least one item",
+        ErrorKind::TakeUntil => "a separator further on",
+        _ => return kind.description().to_lowercase(),
+    }
+    .to_string()
+}
+
+/// Takes the result of running a nom parser over all of `input`, failing
+/// unless everything but trailing whitespace was consumed.
+#[cfg(feature = "nom")]
+pub fn finish<'a, T>(
+    input: &'a str,
+    result: nom::IResult<&'a str, T, nom::error::Error<&'a str>>,
+) -> Result<T, ParseError> {
+    match result {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => Err(ParseError::new(input, rest.trim_start(), "end of input")),
+        Err(e) => Err(ParseError::from_nom(input, e)),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_a_slice_of_the_input() {
+        let input = "1,2\n3;4\n";
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(
+            ParseError::new(input, &line[1..], "a comma").to_string(),
+            "line 2, column 2: expected a comma"
+        );
+        assert_eq!(
+            ParseError::at_end(input, "another line"),
+            ParseError {
+                line: 3,
+                column: 1,
+                expected: "another line".to_string()
+            }
+        );
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn adapts_nom_errors() {
+        use nom::{bytes::complete::tag, character::complete::digit1, sequence::preceded};
+
+        let input = "12\nab";
+        let result: nom::IResult<&str, &str> = preceded(tag("12\n"), digit1)(input);
+        assert_eq!(
+            crate::finish(input, result).map_err(|e| e.to_string()),
+            Err("line 2, column 1: expected a number".to_string())
+        );
+        assert_eq!(
+            crate::finish(input, tag("12")(input)).map_err(|e| e.to_string()),
+            Err("line 2, column 1: expected end of input".to_string())
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::Error;
+
+/// A day's puzzle, parsed once and then asked for either part's answer.
+///
+/// `Params` holds anything the puzzle text states that isn't in the input
+/// (a row number, a grid size, ...). Its `Default` is what the real puzzle
+/// uses; the examples usually need something smaller.
+///
+/// Days with only one puzzle, or parts that were solved by hand, set the
+/// answer type to [`std::convert::Infallible`] and return [`Error::Unsolved`].
+pub trait Solution: Sized {
+    type Params: Default;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self, Error>;
+
+    fn part1(&self, params: &Self::Params) -> Result<Self::Part1, Error>;
+
+    fn part2(&self, params: &Self::Params) -> Result<Self::Part2, Error>;
+}
+
+/// Parses `input` and solves part 1 with the default parameters.
+pub fn part1<S: Solution>(input: &str) -> Result<S::Part1, Error> {
+    S::parse(input)?.part1(&S::Params::default())
+}
+
+/// Parses `input` and solves part 2 with the default parameters.
+pub fn part2<S: Solution>(input: &str) -> Result<S::Part2, Error> {
+    S::parse(input)?.part2(&S::Params::default())
+}
+
+/// Parses `input` and solves one part with the default parameters, formatting the answer.
+pub fn solve<S: Solution>(input: &str, part: u8) -> Result<String, Error> {
+    match part {
+        1 => part1::<S>(input).map(|answer| answer.to_string()),
+        2 => part2::<S>(input).map(|answer| answer.to_string()),
+        _ => Err(Error::Unsolved),
+    }
+}
+
+/// How long each step of solving a day took. Parts the solution doesn't answer have no time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Parses `input` and solves both parts with the default parameters, timing each step.
+pub fn time<S: Solution>(input: &str) -> Result<Timings, Error> {
+    let start = Instant::now();
+    let solution = black_box(S::parse(input)?);
+    let parse = start.elapsed();
+    let params = S::Params::default();
+    Ok(Timings {
+        parse,
+        part1: time_part(|| solution.part1(&params))?,
+        part2: time_part(|| solution.part2(&params))?,
+    })
+}
+
+fn time_part<T>(part: impl FnOnce() -> Result<T, Error>) -> Result<Option<Duration>, Error> {
+    let start = Instant::now();
+    match black_box(part()) {
+        Ok(_) => Ok(Some(start.elapsed())),
+        Err(Error::Unsolved) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    struct Numbers(Vec<u32>);
+
+    impl Solution for Numbers {
+        type Params = ();
+        type Part1 = u32;
+        type Part2 = Infallible;
+
+        fn parse(input: &str) -> Result<Self, Error> {
+            input
+                .lines()
+                .map(|line| {
+                    line.parse()
+                        .map_err(|_| Error::parse(input, line, "a number"))
+                })
+                .collect::<Result<_, _>>()
+                .map(Numbers)
+        }
+
+        fn part1(&self, _: &()) -> Result<u32, Error> {
+            Ok(self.0.iter().sum())
+        }
+
+        fn part2(&self, _: &()) -> Result<Infallible, Error> {
+            Err(Error::Unsolved)
+        }
+    }
+
+    #[test]
+    fn solves_each_part() {
+        assert_eq!(solve::<Numbers>("1\n2\n3\n", 1), Ok("6".to_string()));
+        assert_eq!(solve::<Numbers>("1\n2\n3\n", 2), Err(Error::Unsolved));
+    }
+
+    #[test]
+    fn times_the_answered_parts() {
+        let timings = time::<Numbers>("1\n2\n3\n").unwrap();
+        assert!(timings.part1.is_some());
+        assert_eq!(timings.part2, None);
+        assert!(time::<Numbers>("one\n").is_err());
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            solve::<Numbers>("1\ntwo\n", 1).map_err(|e| e.to_string()),
+            Err("could not parse input: line 2, column 1: expected a number".to_string())
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Skipping ahead through simulations that run for far too many steps to do one at a time.
+//!
+//! [`simulate_until`] steps a state until the state's key repeats, then jumps over as many
+//! whole cycles as fit before the target and runs the rest from what it's already seen. The
+//! answer is the sum of what each step added. States that keep drifting, like a pattern
+//! sliding along a line, cycle too if the key leaves out where they are.
+use std::collections::{hash_map::Entry, HashMap};
+use std::hash::Hash;
+use std::ops::{Add, Mul};
+
+/// What a step adds to the answer, where `Default` is nothing. It has to count whole cycles
+/// of steps, so it converts from a `u64`.
+pub trait Metric: Copy + Default + Add<Output = Self> + Mul<Output = Self> + TryFrom<u64> {}
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T> + TryFrom<u64>> Metric for T {}
+
+/// The total of `step` over `target_steps` steps from `state`.
+///
+/// `step` moves the state on one step and says how much that added. `key` picks out
+/// everything about a state that decides what later steps add, so two states with the same
+/// key must go on to add the same amounts.
+///
+/// Panics if the number of cycles skipped doesn't fit in a `M`.
+pub fn simulate_until<S, K, M>(
+    mut state: S,
+    mut step: impl FnMut(&mut S) -> M,
+    mut key: impl FnMut(&S) -> K,
+    target_steps: u64,
+) -> M
+where
+    K: Eq + Hash,
+    M: Metric,
+{
+    let sum = |added: &[M]| added.iter().fold(M::default(), |total, &a| total + a);
+    let mut seen = HashMap::from([(key(&state), 0)]);
+    let mut added = Vec::new();
+    for steps in 1..=target_steps {
+        added.push(step(&mut state));
+        match seen.entry(key(&state)) {
+            Entry::Occupied(start) => {
+                let start = *start.get();
+                let length = steps - start;
+                let repeats = (target_steps - steps) / length;
+                let rest = (target_steps - steps) % length;
+                let cycle = &added[start as usize..];
+                let repeats = M::try_from(repeats)
+                    .unwrap_or_else(|_| panic!("{repeats} cycles is too many to count"));
+                return sum(&added) + sum(cycle) * repeats + sum(&cycle[..rest as usize]);
+            }
+            Entry::Vacant(unseen) => {
+                unseen.insert(steps);
+            }
+        }
+    }
+    sum(&added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_running_every_step() {
+        // squaring and adding one, mod 10, goes 3, 0, 1, 2, 5, 6, 7, 0, 1, ...
+        let step = |x: &mut u64| {
+            *x = (*x * *x + 1) % 10;
+            *x
+        };
+        for target in 0..40 {
+            let mut x = 3;
+            let expected: u64 = (0..target).map(|_| step(&mut x)).sum();
+            assert_eq!(simulate_until(3, step, |&x| x, target), expected);
+        }
+    }
+
+    #[test]
+    fn skips_a_trillion_steps() {
+        let total = simulate_until(
+            0u64,
+            |x| {
+                *x = (*x + 1) % 3;
+                *x
+            },
+            |&x| x,
+            1_000_000_000_000,
+        );
+        // each cycle of 3 steps adds 1 + 2 + 0, and the step left over adds 1
+        assert_eq!(total, 1_000_000_000_000);
+    }
+
+    #[test]
+    fn follows_a_pattern_drifting_along() {
+        // the positions 2, 3 and 7 all move one to the right each step, adding 3 to their sum
+        let total = simulate_until(
+            vec![2i64, 3, 7],
+            |positions| {
+                positions.iter_mut().for_each(|p| *p += 1);
+                positions.len() as i64
+            },
+            |positions| {
+                positions
+                    .iter()
+                    .map(|p| p - positions[0])
+                    .collect::<Vec<_>>()
+            },
+            50_000_000_000,
+        );
+        assert_eq!(total, 3 * 50_000_000_000);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Signed, Vec2};
+
+/// One of the four compass points, a step along a row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// One step this way, with `y` growing south.
+    pub fn offset<T: Signed>(self) -> Vec2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Direction::North => Vec2::new(zero, -one),
+            Direction::East => Vec2::new(one, zero),
+            Direction::South => Vec2::new(zero, one),
+            Direction::West => Vec2::new(-one, zero),
+        }
+    }
+}
+
+/// One of the four points between the compass points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Diagonal {
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+impl Diagonal {
+    /// Clockwise from north east.
+    pub const ALL: [Diagonal; 4] = [
+        Diagonal::NorthEast,
+        Diagonal::SouthEast,
+        Diagonal::SouthWest,
+        Diagonal::NorthWest,
+    ];
+
+    /// One step this way, with `y` growing south.
+    pub fn offset<T: Signed>(self) -> Vec2<T> {
+        let (vertical, horizontal) = match self {
+            Diagonal::NorthEast => (Direction::North, Direction::East),
+            Diagonal::SouthEast => (Direction::South, Direction::East),
+            Diagonal::SouthWest => (Direction::South, Direction::West),
+            Diagonal::NorthWest => (Direction::North, Direction::West),
+        };
+        vertical.offset() + horizontal.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(
+                direction.turn_right().offset::<i32>(),
+                direction.offset::<i32>().rotate_right()
+            );
+            assert_eq!(direction.opposite().offset::<i32>(), -direction.offset());
+        }
+        assert_eq!(Diagonal::SouthWest.offset::<i8>(), Vec2::new(-1, 1));
+    }
+}
@@ -0,0 +1,14 @@
+//! Points and offsets on a plane or in space, and the compass directions between them.
+//!
+//! `y` grows down the page, as puzzle input reads, so [`Direction::North`] is `(0, -1)` and
+//! turning right goes clockwise on screen. Days that want `y` to grow upwards can still
+//! use the arithmetic and pick their own offsets.
+mod direction;
+mod number;
+mod vec2;
+mod vec3;
+
+pub use direction::{Diagonal, Direction};
+pub use number::{Number, Signed};
+pub use vec2::Vec2;
+pub use vec3::Vec3;
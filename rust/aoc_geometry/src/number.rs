@@ -0,0 +1,65 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// The primitive numbers a vector can be made of.
+pub trait Number:
+    Copy
+    + Debug
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// How far apart two numbers are, which never underflows an unsigned type.
+    fn distance(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// Numbers that can point backwards, and so describe a direction.
+pub trait Signed: Number + Neg<Output = Self> {
+    /// `-1`, `0` or `1`, matching the sign.
+    fn signum(self) -> Self {
+        if self > Self::ZERO {
+            Self::ONE
+        } else if self < Self::ZERO {
+            -Self::ONE
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+macro_rules! number {
+    ($zero:literal, $one:literal: $($t:ty),*) => {
+        $(impl Number for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+        })*
+    };
+}
+
+number!(0, 1: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+number!(0.0, 1.0: f32, f64);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+impl Signed for f32 {}
+impl Signed for f64 {}
@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Diagonal, Direction, Number, Signed};
+
+/// A point on a plane, or the offset between two.
+///
+/// Points sort in reading order: by row, then along it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Number> Vec2<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// The distance walking along the grid lines.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// The distance when diagonal steps count as one, as a king moves.
+    pub fn chebyshev(self, other: Self) -> T {
+        let (dx, dy) = (self.x.distance(other.x), self.y.distance(other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Signed> Vec2<T> {
+    /// A quarter turn clockwise on screen, so north becomes east.
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise on screen, so north becomes west.
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Each coordinate's sign, which is the single step (diagonals included) towards
+    /// wherever this points.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The points north, east, south and west of this one.
+    pub fn neighbours_4(self) -> [Self; 4] {
+        Direction::ALL.map(|direction| self + direction.offset())
+    }
+
+    /// The eight points around this one, clockwise from north.
+    pub fn neighbours_8(self) -> [Self; 8] {
+        let [north, east, south, west] = Direction::ALL.map(|d| self + d.offset());
+        let [north_east, south_east, south_west, north_west] =
+            Diagonal::ALL.map(|d| self + d.offset());
+        [
+            north, north_east, east, south_east, south, south_west, west, north_west,
+        ]
+    }
+}
+
+impl<T: Ord> Ord for Vec2<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.y, &self.x).cmp(&(&other.y, &other.x))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Vec2<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (&self.y, &self.x).partial_cmp(&(&other.y, &other.x))
+    }
+}
+
+impl<T: Number> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Number> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Number> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Signed> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Number> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self {
+        Self::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Number> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, scale: T) {
+        *self = *self * scale;
+    }
+}
+
+impl<T: Number> Div<T> for Vec2<T> {
+    type Output = Self;
+
+    fn div(self, scale: T) -> Self {
+        Self::new(self.x / scale, self.y / scale)
+    }
+}
+
+impl<T: Number> Sum for Vec2<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<[T; 2]> for Vec2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// `x,y`, the way puzzles write a point.
+impl<T: fmt::Display> fmt::Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(-1, 5);
+        assert_eq!(a + b, Vec2::new(2, 3));
+        assert_eq!(a - b, Vec2::new(4, -7));
+        assert_eq!(-a, Vec2::new(-3, 2));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+        assert_eq!([a, b, a].into_iter().sum::<Vec2<i32>>(), Vec2::new(5, 1));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Vec2::<u8>::new(1, 9).manhattan(Vec2::new(4, 2)), 10);
+        assert_eq!((b - a).signum(), Vec2::new(-1, 1));
+        assert_eq!(a.to_string(), "3,-2");
+    }
+
+    #[test]
+    fn rotations_go_round_the_compass() {
+        let north = Direction::North.offset::<i32>();
+        assert_eq!(north.rotate_right(), Direction::East.offset());
+        assert_eq!(north.rotate_left(), Direction::West.offset());
+        assert_eq!(north.rotate_right().rotate_right(), -north);
+    }
+
+    #[test]
+    fn sorts_in_reading_order() {
+        let mut points = vec![
+            Vec2::new(2, 1),
+            Vec2::new(0, 2),
+            Vec2::new(1, 1),
+            Vec2::new(5, 0),
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            [
+                Vec2::new(5, 0),
+                Vec2::new(1, 1),
+                Vec2::new(2, 1),
+                Vec2::new(0, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let centre = Vec2::new(0, 0);
+        assert_eq!(
+            centre.neighbours_4(),
+            [
+                Vec2::new(0, -1),
+                Vec2::new(1, 0),
+                Vec2::new(0, 1),
+                Vec2::new(-1, 0)
+            ]
+        );
+        assert_eq!(centre.neighbours_8()[1], Vec2::new(1, -1));
+        assert!(centre
+            .neighbours_8()
+            .iter()
+            .all(|n| n.chebyshev(centre) == 1));
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Number, Signed};
+
+/// A point in space, or the offset between two.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Number> Vec3<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO, T::ZERO);
+
+    /// The distance walking along the grid lines.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    /// The distance when diagonal steps count as one.
+    pub fn chebyshev(self, other: Self) -> T {
+        [self.y.distance(other.y), self.z.distance(other.z)]
+            .into_iter()
+            .fold(self.x.distance(other.x), |a, b| if b > a { b } else { a })
+    }
+}
+
+impl<T: Signed> Vec3<T> {
+    /// The six points sharing a face with this one.
+    pub fn neighbours_6(self) -> [Self; 6] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Self::new(-one, zero, zero),
+            Self::new(one, zero, zero),
+            Self::new(zero, -one, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, zero, -one),
+            Self::new(zero, zero, one),
+        ]
+        .map(|offset| self + offset)
+    }
+}
+
+impl<T: Number> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Number> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Number> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Signed> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Number> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self {
+        Self::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl<T: Number> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, scale: T) {
+        *self = *self * scale;
+    }
+}
+
+impl<T: Number> Div<T> for Vec3<T> {
+    type Output = Self;
+
+    fn div(self, scale: T) -> Self {
+        Self::new(self.x / scale, self.y / scale, self.z / scale)
+    }
+}
+
+impl<T: Number> Sum for Vec3<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// `x,y,z`, the way puzzles write a point.
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(4, 0, -1);
+        assert_eq!(a + b, Vec3::new(5, -2, 2));
+        assert_eq!(b - a, Vec3::new(3, 2, -4));
+        assert_eq!(-a * 2, Vec3::new(-2, 4, -6));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Vec3::new(0.5, 1.0, 2.0) * 2.0, Vec3::new(1.0, 2.0, 4.0));
+        assert!(a.neighbours_6().iter().all(|n| n.manhattan(a) == 1));
+    }
+}
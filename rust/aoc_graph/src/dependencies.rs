@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::error;
+use std::fmt;
+
+/// Tasks and which of them have to be finished before others can start.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dependencies<V> {
+    unblocks: BTreeMap<V, BTreeSet<V>>,
+}
+
+/// The tasks left over when some depend on each other in a loop, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<V>(pub Vec<V>);
+
+/// When and by which worker a task was done.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scheduled<V> {
+    pub task: V,
+    pub worker: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Every task in the order they were started, and when the last one finished.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule<V> {
+    pub tasks: Vec<Scheduled<V>>,
+    pub finish: u64,
+}
+
+impl<V> Default for Dependencies<V> {
+    fn default() -> Self {
+        Self {
+            unblocks: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V: Ord + Clone> Dependencies<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_task(&mut self, task: V) {
+        self.unblocks.entry(task).or_default();
+    }
+
+    /// Makes `before` have to be finished before `after` can start.
+    pub fn add(&mut self, before: V, after: V) {
+        self.add_task(after.clone());
+        self.unblocks.entry(before).or_default().insert(after);
+    }
+
+    pub fn tasks(&self) -> impl Iterator<Item = &V> {
+        self.unblocks.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.unblocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unblocks.is_empty()
+    }
+
+    /// How many unfinished tasks each task is waiting on.
+    fn waiting(&self) -> BTreeMap<&V, usize> {
+        let mut waiting: BTreeMap<&V, usize> = self.unblocks.keys().map(|t| (t, 0)).collect();
+        for after in self.unblocks.values().flatten() {
+            *waiting.get_mut(after).unwrap() += 1;
+        }
+        waiting
+    }
+
+    /// Marks `task` finished, adding what it unblocks to `ready` once nothing else holds it up.
+    fn finish<'a>(
+        &'a self,
+        task: &V,
+        waiting: &mut BTreeMap<&'a V, usize>,
+        ready: &mut BTreeSet<&'a V>,
+    ) {
+        for after in &self.unblocks[task] {
+            let count = waiting.get_mut(after).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.insert(after);
+            }
+        }
+    }
+
+    fn cycle(waiting: BTreeMap<&V, usize>) -> Cycle<V> {
+        Cycle(
+            waiting
+                .into_iter()
+                .filter(|&(_, count)| count > 0)
+                .map(|(task, _)| task.clone())
+                .collect(),
+        )
+    }
+
+    /// Every task after the ones it depends on, by Kahn's algorithm. Whenever more than one
+    /// task could go next, the smallest does.
+    pub fn order(&self) -> Result<Vec<V>, Cycle<V>> {
+        let mut waiting = self.waiting();
+        let mut ready: BTreeSet<&V> = waiting
+            .iter()
+            .filter(|&(_, &count)| count == 0)
+            .map(|(&task, _)| task)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(task) = ready.pop_first() {
+            self.finish(task, &mut waiting, &mut ready);
+            order.push(task.clone());
+        }
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(Self::cycle(waiting))
+        }
+    }
+
+    /// Shares the tasks between `workers`, each task taking its `duration`. Whenever a worker
+    /// is free, it starts the smallest task that's ready, and tasks finishing together all
+    /// finish before any more start.
+    ///
+    /// Panics if there are tasks but no workers.
+    pub fn schedule(
+        &self,
+        workers: usize,
+        mut duration: impl FnMut(&V) -> u64,
+    ) -> Result<Schedule<V>, Cycle<V>> {
+        assert!(
+            workers > 0 || self.is_empty(),
+            "there's no one to do the tasks"
+        );
+        let mut waiting = self.waiting();
+        let mut ready: BTreeSet<&V> = waiting
+            .iter()
+            .filter(|&(_, &count)| count == 0)
+            .map(|(&task, _)| task)
+            .collect();
+        let mut free: BTreeSet<usize> = (0..workers).collect();
+        let mut running = BinaryHeap::new();
+        let mut tasks = Vec::with_capacity(self.len());
+        let mut time = 0;
+        loop {
+            while !free.is_empty() && !ready.is_empty() {
+                let worker = free.pop_first().unwrap();
+                let task = ready.pop_first().unwrap();
+                let end = time + duration(task);
+                running.push(Reverse((end, worker, task)));
+                tasks.push(Scheduled {
+                    task: task.clone(),
+                    worker,
+                    start: time,
+                    end,
+                });
+            }
+            let Some(Reverse((end, worker, task))) = running.pop() else {
+                break;
+            };
+            time = end;
+            free.insert(worker);
+            self.finish(task, &mut waiting, &mut ready);
+            while let Some(Reverse((end, worker, task))) = running.peek() {
+                if *end > time {
+                    break;
+                }
+                free.insert(*worker);
+                self.finish(task, &mut waiting, &mut ready);
+                running.pop();
+            }
+        }
+        if tasks.len() == self.len() {
+            Ok(Schedule {
+                tasks,
+                finish: time,
+            })
+        } else {
+            Err(Self::cycle(waiting))
+        }
+    }
+}
+
+impl<V: fmt::Debug> fmt::Display for Cycle<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the tasks {:?} can't start as they wait on each other",
+            self.0
+        )
+    }
+}
+
+impl<V: fmt::Debug> error::Error for Cycle<V> {}
+
+impl<V: Ord + Clone> FromIterator<(V, V)> for Dependencies<V> {
+    fn from_iter<I: IntoIterator<Item = (V, V)>>(pairs: I) -> Self {
+        let mut dependencies = Self::new();
+        dependencies.extend(pairs);
+        dependencies
+    }
+}
+
+impl<V: Ord + Clone> Extend<(V, V)> for Dependencies<V> {
+    fn extend<I: IntoIterator<Item = (V, V)>>(&mut self, pairs: I) {
+        for (before, after) in pairs {
+            self.add(before, after);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from 2018 day 7.
+    fn sleigh() -> Dependencies<char> {
+        [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn orders_the_smallest_ready_task_first() {
+        let order: String = sleigh().order().unwrap().into_iter().collect();
+        assert_eq!(order, "CABDFE");
+    }
+
+    #[test]
+    fn shares_tasks_between_workers() {
+        let schedule = sleigh()
+            .schedule(2, |&task| u64::from(task as u8 - b'A' + 1))
+            .unwrap();
+        assert_eq!(schedule.finish, 15);
+        let started: String = schedule.tasks.iter().map(|s| s.task).collect();
+        assert_eq!(started, "CAFBDE");
+        assert_eq!(
+            schedule.tasks[2],
+            Scheduled {
+                task: 'F',
+                worker: 1,
+                start: 3,
+                end: 9
+            }
+        );
+    }
+
+    #[test]
+    fn one_worker_follows_the_order() {
+        let schedule = sleigh().schedule(1, |_| 0).unwrap();
+        let started: String = schedule.tasks.iter().map(|s| s.task).collect();
+        assert_eq!(started, "CABDFE");
+        assert_eq!(schedule.finish, 0);
+    }
+
+    #[test]
+    fn reports_a_cycle() {
+        let mut dependencies = sleigh();
+        dependencies.add('E', 'A');
+        let cycle = Cycle(vec!['A', 'B', 'D', 'E']);
+        assert_eq!(dependencies.order(), Err(cycle.clone()));
+        assert_eq!(dependencies.schedule(3, |_| 1), Err(cycle));
+    }
+}
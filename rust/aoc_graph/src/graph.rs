@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound::{Excluded, Unbounded};
+
+/// An undirected graph without loops, its vertices in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph<V> {
+    adjacency: BTreeMap<V, BTreeSet<V>>,
+}
+
+impl<V> Default for Graph<V> {
+    fn default() -> Self {
+        Self {
+            adjacency: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V: Ord + Clone> Graph<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_vertex(&mut self, v: V) {
+        self.adjacency.entry(v).or_default();
+    }
+
+    /// Joins `a` and `b`. Joining a vertex to itself only adds the vertex.
+    pub fn add_edge(&mut self, a: V, b: V) {
+        if a == b {
+            self.add_vertex(a);
+            return;
+        }
+        self.adjacency
+            .entry(a.clone())
+            .or_default()
+            .insert(b.clone());
+        self.adjacency.entry(b).or_default().insert(a);
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = &V> {
+        self.adjacency.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn neighbours<'a>(&'a self, v: &V) -> impl Iterator<Item = &'a V> {
+        self.adjacency.get(v).into_iter().flatten()
+    }
+
+    pub fn contains_edge(&self, a: &V, b: &V) -> bool {
+        self.adjacency.get(a).is_some_and(|n| n.contains(b))
+    }
+
+    /// Every three vertices all joined to each other, once each, in order.
+    pub fn triangles(&self) -> Vec<[&V; 3]> {
+        let mut triangles = Vec::new();
+        for (a, a_neighbours) in &self.adjacency {
+            for b in a_neighbours.range((Excluded(a), Unbounded)) {
+                let b_neighbours = &self.adjacency[b];
+                for c in a_neighbours.range((Excluded(b), Unbounded)) {
+                    if b_neighbours.contains(c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// Every clique that can't be grown by another vertex.
+    pub fn maximal_cliques(&self) -> Vec<BTreeSet<V>> {
+        let mut cliques = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.adjacency.keys().collect(),
+            BTreeSet::new(),
+            &mut |clique| cliques.push(clique.iter().map(|&v| v.clone()).collect()),
+        );
+        cliques
+    }
+
+    /// The largest set of vertices all joined to each other, the first in order if there's
+    /// more than one. Empty if the graph is.
+    pub fn maximum_clique(&self) -> BTreeSet<V> {
+        let mut best: Vec<&V> = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.adjacency.keys().collect(),
+            BTreeSet::new(),
+            &mut |clique| {
+                let mut clique = clique.to_vec();
+                clique.sort();
+                if clique.len() > best.len() || (clique.len() == best.len() && clique < best) {
+                    best = clique;
+                }
+            },
+        );
+        best.into_iter().cloned().collect()
+    }
+
+    /// Reports each maximal clique containing all of `clique`, some of `candidates` and none
+    /// of `excluded`. Only the candidates not next to a pivot are branched on, as any clique
+    /// through a pivot's neighbour alone could take the pivot as well.
+    fn bron_kerbosch<'a>(
+        &'a self,
+        clique: &mut Vec<&'a V>,
+        mut candidates: BTreeSet<&'a V>,
+        mut excluded: BTreeSet<&'a V>,
+        report: &mut impl FnMut(&[&'a V]),
+    ) {
+        let Some(pivot) = candidates.union(&excluded).max_by_key(|&&u| {
+            let neighbours = &self.adjacency[u];
+            candidates
+                .iter()
+                .filter(|&&v| neighbours.contains(v))
+                .count()
+        }) else {
+            report(clique);
+            return;
+        };
+        let pivot_neighbours = &self.adjacency[*pivot];
+        let branches: Vec<&V> = candidates
+            .iter()
+            .copied()
+            .filter(|v| !pivot_neighbours.contains(v))
+            .collect();
+        for v in branches {
+            let neighbours = &self.adjacency[v];
+            clique.push(v);
+            self.bron_kerbosch(
+                clique,
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|u| neighbours.contains(u))
+                    .collect(),
+                excluded
+                    .iter()
+                    .copied()
+                    .filter(|u| neighbours.contains(u))
+                    .collect(),
+                report,
+            );
+            clique.pop();
+            candidates.remove(v);
+            excluded.insert(v);
+        }
+    }
+}
+
+impl<V: Ord + Clone> FromIterator<(V, V)> for Graph<V> {
+    fn from_iter<I: IntoIterator<Item = (V, V)>>(edges: I) -> Self {
+        let mut graph = Self::new();
+        graph.extend(edges);
+        graph
+    }
+}
+
+impl<V: Ord + Clone> Extend<(V, V)> for Graph<V> {
+    fn extend<I: IntoIterator<Item = (V, V)>>(&mut self, edges: I) {
+        for (a, b) in edges {
+            self.add_edge(a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two squares sharing the edge 2-3, one with both diagonals and one with none.
+    fn squares() -> Graph<u8> {
+        [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (2, 4),
+            (3, 5),
+            (4, 5),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn finds_each_triangle_once() {
+        let graph = squares();
+        assert_eq!(
+            graph.triangles(),
+            [[&0, &1, &2], [&0, &1, &3], [&0, &2, &3], [&1, &2, &3]]
+        );
+    }
+
+    #[test]
+    fn finds_the_maximal_cliques() {
+        let mut cliques = squares().maximal_cliques();
+        cliques.sort();
+        assert_eq!(
+            cliques,
+            [
+                BTreeSet::from([0, 1, 2, 3]),
+                BTreeSet::from([2, 4]),
+                BTreeSet::from([3, 5]),
+                BTreeSet::from([4, 5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_the_maximum_clique() {
+        assert_eq!(squares().maximum_clique(), BTreeSet::from([0, 1, 2, 3]));
+        let mut graph = Graph::new();
+        graph.add_edge("b", "c");
+        graph.add_edge("a", "d");
+        graph.add_vertex("e");
+        assert_eq!(graph.maximum_clique(), BTreeSet::from(["a", "d"]));
+        assert_eq!(Graph::<u8>::new().maximum_clique(), BTreeSet::new());
+    }
+
+    #[test]
+    fn ignores_loops() {
+        let mut graph = Graph::new();
+        graph.add_edge(1, 1);
+        assert_eq!(graph.len(), 1);
+        assert!(!graph.contains_edge(&1, &1));
+        assert_eq!(graph.maximum_clique(), BTreeSet::from([1]));
+    }
+}
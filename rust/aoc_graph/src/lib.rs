@@ -0,0 +1,13 @@
+//! Graph algorithms that keep coming back: cliques, dependency order and connected pieces.
+//!
+//! A [`Graph`] is undirected and finds its triangles and its maximum clique, the latter by
+//! Bron–Kerbosch with pivoting. [`Dependencies`] are directed and come out in topological
+//! order, ties going to the smallest task, or get shared between workers who each take the
+//! smallest task ready. [`UnionFind`] joins numbered items into components.
+mod dependencies;
+mod graph;
+mod union_find;
+
+pub use dependencies::{Cycle, Dependencies, Schedule, Scheduled};
+pub use graph::Graph;
+pub use union_find::UnionFind;
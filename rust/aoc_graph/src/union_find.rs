@@ -0,0 +1,92 @@
+/// Items numbered from zero, joined into components with [`UnionFind::union`].
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// `len` items, each in a component of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The item standing for the component `item` is in.
+    pub fn find(&mut self, mut item: usize) -> usize {
+        while self.parent[item] != item {
+            self.parent[item] = self.parent[self.parent[item]];
+            item = self.parent[item];
+        }
+        item
+    }
+
+    /// Puts `a` and `b` in the same component, and says whether they weren't already.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] < self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// How many items are in the same component as `item`.
+    pub fn size_of(&mut self, item: usize) -> usize {
+        let root = self.find(item);
+        self.size[root]
+    }
+
+    /// Each component's items in order, the components ordered by their first item.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![None; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for item in 0..self.len() {
+            let root = self.find(item);
+            let i = *index[root].get_or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[i].push(item);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_components() {
+        let mut items = UnionFind::new(6);
+        assert!(items.union(4, 1));
+        assert!(items.union(1, 3));
+        assert!(!items.union(3, 4));
+        assert!(items.union(5, 2));
+        assert!(items.connected(4, 3));
+        assert!(!items.connected(0, 1));
+        assert_eq!(items.size_of(3), 3);
+        assert_eq!(items.components(), [vec![0], vec![1, 3, 4], vec![2, 5]]);
+    }
+}
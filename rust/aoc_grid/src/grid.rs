@@ -0,0 +1,318 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use aoc_core::ParseError;
+
+use crate::{Coord, Ray};
+
+/// Cells stored row by row in one `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics unless there are `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width} by {height} grid needs {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+            .map(&mut cell)
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    /// Reads one row per line, turning each character into a cell. `cell` says what it
+    /// expected when it doesn't like a character, and every row must be as wide as the
+    /// first. Blank lines at the end are ignored.
+    pub fn parse<E: Into<String>>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                if width == Some(row_width) {
+                    return Err(ParseError::new(input, &line[i..], "the end of the row"));
+                }
+                cells.push(
+                    cell(c).map_err(|expected| ParseError::new(input, &line[i..], expected))?,
+                );
+                row_width += 1;
+            }
+            match width {
+                Some(width) if row_width < width => {
+                    return Err(ParseError::new(
+                        input,
+                        &line[line.len()..],
+                        format!("a row {width} cells wide"),
+                    ))
+                }
+                Some(_) => {}
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, position: Coord) -> Option<usize> {
+        let x = usize::try_from(position.x)
+            .ok()
+            .filter(|&x| x < self.width)?;
+        let y = usize::try_from(position.y)
+            .ok()
+            .filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn coord_of(&self, index: usize) -> Coord {
+        Coord::new((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    pub fn contains(&self, position: Coord) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    /// The cell at `position`, or `None` off the edge of the grid.
+    pub fn get(&self, position: Coord) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Coord) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width as isize;
+        (0..self.height as isize).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.coord_of(i), cell))
+    }
+
+    /// The position of the first cell, row by row, that matches.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.coord_of(i))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The positions next to `position` north, east, south and west that are on the grid.
+    pub fn neighbours_4(&self, position: Coord) -> impl Iterator<Item = Coord> + '_ {
+        position
+            .neighbours_4()
+            .into_iter()
+            .filter(|&p| self.contains(p))
+    }
+
+    /// As [`Grid::neighbours_4`], but including the diagonals.
+    pub fn neighbours_8(&self, position: Coord) -> impl Iterator<Item = Coord> + '_ {
+        position
+            .neighbours_8()
+            .into_iter()
+            .filter(|&p| self.contains(p))
+    }
+
+    /// The cells from `from` (exclusive) to the edge, `step` at a time. A
+    /// [`Direction`](crate::Direction)'s offset walks along a row or column and a
+    /// [`Diagonal`](crate::Diagonal)'s along a diagonal.
+    pub fn ray(&self, from: Coord, step: Coord) -> Ray<'_, T> {
+        Ray::new(self, from, step)
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(cell).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, cell: T) -> Self {
+        Self::new(width, height, vec![cell; width * height])
+    }
+
+    /// Swaps rows for columns, mirroring the grid along its leading diagonal.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Coord::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// A quarter turn clockwise, so the first column becomes the first row, reversed.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height as isize;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Coord::new(p.y, height - 1 - p.x)].clone()
+        })
+    }
+
+    /// A quarter turn anticlockwise, so the last column becomes the first row.
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let width = self.width as isize;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Coord::new(width - 1 - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Keeps each character of the input as it is.
+    pub fn from_chars(input: &str) -> Result<Self, ParseError> {
+        Self::parse(input, Ok::<_, String>)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Coord) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position} is outside a {} by {} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, position: Coord) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position} is outside a {width} by {height} grid"))
+    }
+}
+
+/// Draws the grid as the puzzle would, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Diagonal, Direction};
+
+    const EXAMPLE: &str = "abc\ndef\n";
+
+    #[test]
+    fn parses_rows_of_cells() {
+        let grid = Grid::from_chars(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Coord::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(-1, 0)), None);
+        assert_eq!(grid.find(|&c| c == 'e'), Some(Coord::new(1, 1)));
+        assert_eq!(grid.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn reports_bad_cells_and_ragged_rows() {
+        let digits = |c: char| c.to_digit(10).ok_or("a digit");
+        assert_eq!(
+            Grid::parse("12\n3x\n", digits).unwrap_err().to_string(),
+            "line 2, column 2: expected a digit"
+        );
+        assert_eq!(
+            Grid::parse("12\n3\n", digits).unwrap_err().to_string(),
+            "line 2, column 2: expected a row 2 cells wide"
+        );
+        assert_eq!(
+            Grid::parse("12\n345\n", digits).unwrap_err().to_string(),
+            "line 2, column 3: expected the end of the row"
+        );
+    }
+
+    #[test]
+    fn finds_neighbours_on_the_grid() {
+        let grid = Grid::from_chars(EXAMPLE).unwrap();
+        assert_eq!(
+            grid.neighbours_4(Coord::ZERO).collect::<Vec<_>>(),
+            [Coord::new(1, 0), Coord::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours_8(Coord::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn casts_rays_to_the_edge() {
+        let grid = Grid::from_chars("abcd\nefgh\nijkl\n").unwrap();
+        let cells = |x, y, step| {
+            grid.ray(Coord::new(x, y), step)
+                .map(|(_, &c)| c)
+                .collect::<String>()
+        };
+        assert_eq!(cells(0, 0, Direction::East.offset()), "bcd");
+        assert_eq!(cells(3, 1, Direction::West.offset()), "gfe");
+        assert_eq!(cells(1, 0, Direction::South.offset()), "fj");
+        assert_eq!(cells(0, 0, Diagonal::SouthEast.offset()), "fk");
+        assert_eq!(cells(0, 0, Coord::ZERO), "");
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::from_chars("abc\ndef\n").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+    }
+}
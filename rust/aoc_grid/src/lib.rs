@@ -0,0 +1,14 @@
+//! A rectangular grid of cells, for the many puzzles whose input is a map.
+//!
+//! Positions are [`Vec2`]s with `y` growing down the page, as the input reads. They're
+//! signed so that stepping off an edge gives a position [`Grid::get`] can say `None` to,
+//! rather than an overflow.
+mod grid;
+mod ray;
+
+pub use aoc_geometry::{Diagonal, Direction, Vec2};
+pub use grid::Grid;
+pub use ray::Ray;
+
+/// A position on a grid, or a step across one.
+pub type Coord = Vec2<isize>;
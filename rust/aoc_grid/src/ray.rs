@@ -0,0 +1,33 @@
+use crate::{Coord, Grid};
+
+/// The cells met walking in a straight line from a position, not counting the position
+/// itself, until falling off the grid. See [`Grid::ray`].
+#[derive(Debug, Clone)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Coord,
+    step: Coord,
+}
+
+impl<'a, T> Ray<'a, T> {
+    pub(crate) fn new(grid: &'a Grid<T>, from: Coord, step: Coord) -> Self {
+        Self {
+            grid,
+            position: from,
+            step,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Coord, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == Coord::ZERO {
+            return None;
+        }
+        self.position += self.step;
+        let cell = self.grid.get(self.position)?;
+        Some((self.position, cell))
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::Error;
+
+/// A directory holding each day's input and puzzle page, laid out as
+/// `<root>/<year>/day_<dd>/input.txt` and `.../puzzle.html`.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    /// Names the cache directory, overriding the default.
+    pub const DIR_VAR: &'static str = "AOC_CACHE_DIR";
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses `$AOC_CACHE_DIR`, falling back to `advent-of-code` under the user's cache
+    /// directory (`$XDG_CACHE_HOME` or `~/.cache`).
+    pub fn from_env() -> Self {
+        let root = env::var_os(Self::DIR_VAR)
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("XDG_CACHE_HOME").map(|d| PathBuf::from(d).join("advent-of-code"))
+            })
+            .or_else(|| env::var_os("HOME").map(|d| PathBuf::from(d).join(".cache/advent-of-code")))
+            .unwrap_or_else(|| PathBuf::from(".advent-of-code"));
+        Self::new(root)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn day_dir(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day_{day:02}"))
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("input.txt")
+    }
+
+    pub fn page_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("puzzle.html")
+    }
+
+    /// The cached input. An empty file is a placeholder waiting for one, so it counts as
+    /// missing too.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.input_path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) if input.is_empty() => Err(Error::Missing { year, day, path }),
+            Ok(input) => Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(Error::Missing { year, day, path })
+            }
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+
+    /// Whether a real input, not just a placeholder, is cached for the day.
+    pub fn has_input(&self, year: u16, day: u8) -> bool {
+        is_input(&self.input_path(year, day))
+    }
+
+    /// The cached puzzle page, if one was saved alongside the input.
+    pub fn page(&self, year: u16, day: u8) -> Result<Option<String>, Error> {
+        let path = self.page_path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(page) => Ok(Some(page)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+
+    pub fn store_input(&self, year: u16, day: u8, input: &str) -> Result<PathBuf, Error> {
+        write(self.input_path(year, day), input)
+    }
+
+    /// Creates an empty input file to paste the input into by hand, unless the day already
+    /// has one. Gives the path either way.
+    pub fn store_placeholder(&self, year: u16, day: u8) -> Result<PathBuf, Error> {
+        let path = self.input_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(_) => Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(path),
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+
+    pub fn store_page(&self, year: u16, day: u8, page: &str) -> Result<PathBuf, Error> {
+        write(self.page_path(year, day), page)
+    }
+
+    /// Every year and day with a cached input, in order.
+    pub fn days(&self) -> Result<Vec<(u16, u8)>, Error> {
+        let mut days = Vec::new();
+        for year_dir in read_dir(&self.root)? {
+            let Some(year) = parse_name(&year_dir, "") else {
+                continue;
+            };
+            for day_dir in read_dir(&year_dir)? {
+                if let Some(day) = parse_name(&day_dir, "day_") {
+                    if is_input(&day_dir.join("input.txt")) {
+                        days.push((year, day));
+                    }
+                }
+            }
+        }
+        days.sort_unstable();
+        Ok(days)
+    }
+}
+
+fn is_input(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() > 0)
+}
+
+fn write(path: PathBuf, contents: &str) -> Result<PathBuf, Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&path, contents).map_err(|e| Error::io(&path, e))?;
+    Ok(path)
+}
+
+/// The entries of `dir`, or nothing if it doesn't exist yet.
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|e| e.path()).map_err(|e| Error::io(dir, e)))
+            .collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(Error::io(dir, e)),
+    }
+}
+
+fn parse_name<T: std::str::FromStr>(path: &Path, prefix: &str) -> Option<T> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(prefix)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_and_lists_days() {
+        let root = env::temp_dir().join(format!("aoc_input_cache_{}", std::process::id()));
+        let cache = Cache::new(&root);
+        assert!(matches!(
+            cache.input(2024, 1),
+            Err(Error::Missing {
+                year: 2024,
+                day: 1,
+                ..
+            })
+        ));
+        assert_eq!(cache.days().unwrap(), vec![]);
+
+        cache.store_input(2024, 25, "#####\n").unwrap();
+        cache.store_input(2022, 3, "vJrwpWtwJgWr\n").unwrap();
+        cache
+            .store_page(2022, 3, "<h2>--- Day 3: Rucksack Reorganization ---</h2>")
+            .unwrap();
+        assert_eq!(cache.input(2024, 25).unwrap(), "#####\n");
+        assert_eq!(
+            cache.input_path(2022, 3),
+            root.join("2022/day_03/input.txt")
+        );
+        assert!(cache.page(2022, 3).unwrap().is_some());
+        assert_eq!(cache.page(2024, 25).unwrap(), None);
+        assert_eq!(cache.days().unwrap(), vec![(2022, 3), (2024, 25)]);
+
+        cache.store_placeholder(2024, 25).unwrap();
+        assert_eq!(cache.input(2024, 25).unwrap(), "#####\n");
+        let path = cache.store_placeholder(2024, 2).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "");
+        assert!(!cache.has_input(2024, 2));
+        assert!(matches!(cache.input(2024, 2), Err(Error::Missing { .. })));
+        assert_eq!(cache.days().unwrap(), vec![(2022, 3), (2024, 25)]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Why an input couldn't be loaded, stored or fetched.
+#[derive(Debug)]
+pub enum Error {
+    /// Nothing has been cached for this day yet.
+    Missing {
+        year: u16,
+        day: u8,
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The server answered, but not with the page asked for.
+    Http {
+        url: String,
+        status: u16,
+    },
+    /// The request never got an answer.
+    Transport {
+        url: String,
+        message: String,
+    },
+}
+
+impl Error {
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { year, day, path } => write!(
+                f,
+                "no input cached for {year} day {day} at {}; fetch it with `aoc input fetch {year} {day}`",
+                path.display()
+            ),
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Http { url, status } => write!(f, "{url} answered with status {status}"),
+            Self::Transport { url, message } => write!(f, "could not reach {url}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::env;
+
+use crate::{puzzle_path, Error};
+
+/// Downloads puzzle pages and inputs from the site, or from a stand-in such as a
+/// [`FixtureServer`](crate::FixtureServer).
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+    /// Overrides the site the fetcher talks to.
+    pub const BASE_URL_VAR: &'static str = "AOC_BASE_URL";
+    /// The `session` cookie of a logged in browser. Inputs differ per user, so the real
+    /// site won't hand them out without it.
+    pub const SESSION_VAR: &'static str = "AOC_SESSION";
+
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: None,
+            agent: ureq::AgentBuilder::new()
+                .user_agent("github.com/ccouzens/advent-of-code")
+                .build(),
+        }
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    /// Configured from `$AOC_BASE_URL` and `$AOC_SESSION`.
+    pub fn from_env() -> Self {
+        let fetcher = Self::new(
+            env::var(Self::BASE_URL_VAR).unwrap_or_else(|_| Self::DEFAULT_BASE_URL.to_string()),
+        );
+        match env::var(Self::SESSION_VAR) {
+            Ok(session) => fetcher.with_session(session.trim()),
+            Err(_) => fetcher,
+        }
+    }
+
+    pub fn page(&self, year: u16, day: u8) -> Result<String, Error> {
+        self.get(&puzzle_path(year, day))
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        self.get(&format!("{}/input", puzzle_path(year, day)))
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let url = format!("{}{path}", self.base_url);
+        let mut request = self.agent.get(&url);
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={session}"));
+        }
+        let transport_error = |message: String| Error::Transport {
+            url: url.clone(),
+            message,
+        };
+        match request.call() {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| transport_error(e.to_string())),
+            Err(ureq::Error::Status(status, _)) => Err(Error::Http {
+                url: url.clone(),
+                status,
+            }),
+            Err(ureq::Error::Transport(e)) => Err(transport_error(e.to_string())),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::FixtureServer;
+
+    const PAGE: &str = "<main>\n<article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2>\n<p>The Chief Historian is always present</p></article>\n</main>";
+
+    #[test]
+    fn fetches_from_a_stub_server() {
+        let server = FixtureServer::new()
+            .with_page(2024, 1, PAGE)
+            .with_input(2024, 1, "3   4\n4   3\n")
+            .start("127.0.0.1:0")
+            .unwrap();
+        let fetcher = Fetcher::new(server.base_url()).with_session("abc");
+
+        assert_eq!(fetcher.input(2024, 1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(
+            crate::puzzle_title(&fetcher.page(2024, 1).unwrap()),
+            Some("Historian Hysteria")
+        );
+        assert!(matches!(
+            fetcher.input(2024, 2),
+            Err(Error::Http { status: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn serves_a_cache_back_out() {
+        let root = std::env::temp_dir().join(format!("aoc_input_serve_{}", std::process::id()));
+        let cache = crate::Cache::new(&root);
+        cache
+            .store_input(2022, 6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n")
+            .unwrap();
+        let server = FixtureServer::from_cache(&cache)
+            .unwrap()
+            .start("127.0.0.1:0")
+            .unwrap();
+        let fetcher = Fetcher::new(server.base_url());
+
+        assert_eq!(
+            fetcher.input(2022, 6).unwrap(),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"
+        );
+        assert!(matches!(
+            fetcher.page(2022, 6),
+            Err(Error::Http { status: 404, .. })
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reports_unreachable_servers() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(matches!(
+            Fetcher::new(base_url).input(2024, 1),
+            Err(Error::Transport { .. })
+        ));
+    }
+}
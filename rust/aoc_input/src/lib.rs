@@ -0,0 +1,110 @@
+//! Puzzle inputs, kept in one cache directory outside the repository instead of next to
+//! each day's code.
+mod cache;
+mod error;
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "server")]
+mod server;
+
+pub use cache::Cache;
+pub use error::Error;
+#[cfg(feature = "fetch")]
+pub use fetch::Fetcher;
+#[cfg(feature = "server")]
+pub use server::{FixtureServer, RunningServer};
+
+/// Reads a day's input from the cache named by the environment. See [`Cache::from_env`].
+pub fn load(year: u16, day: u8) -> Result<String, Error> {
+    Cache::from_env().input(year, day)
+}
+
+/// A day's input for its challenge tests. Those are ignored unless the day's build script found
+/// the input cached (see [`check_challenge_input`]), so this only panics, saying where it looked,
+/// when they're run anyway with `--include-ignored`.
+pub fn challenge(year: u16, day: u8) -> String {
+    load(year, day).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// For a day's build script. Sets the `challenge_input` cfg when the day's input is cached, so
+/// its challenge tests can be marked
+/// `#[cfg_attr(not(challenge_input), ignore = "input not cached")]`, and has Cargo run the
+/// script again once that might have changed.
+pub fn check_challenge_input(year: u16, day: u8) {
+    for instruction in challenge_input_instructions(&Cache::from_env(), year, day) {
+        println!("{instruction}");
+    }
+}
+
+fn challenge_input_instructions(cache: &Cache, year: u16, day: u8) -> Vec<String> {
+    let input = cache.input_path(year, day);
+    // Cargo reruns a build script on every build while a path it watches is missing, so make
+    // sure the cache is there and watch the closest part of it that exists.
+    let _ = std::fs::create_dir_all(cache.root());
+    let watched = input
+        .ancestors()
+        .take_while(|path| path.starts_with(cache.root()))
+        .find(|path| path.exists())
+        .unwrap_or(cache.root());
+    let mut instructions = vec![
+        "cargo::rustc-check-cfg=cfg(challenge_input)".to_string(),
+        format!("cargo::rerun-if-changed={}", watched.display()),
+    ];
+    for var in [Cache::DIR_VAR, "XDG_CACHE_HOME", "HOME"] {
+        instructions.push(format!("cargo::rerun-if-env-changed={var}"));
+    }
+    if cache.has_input(year, day) {
+        instructions.push("cargo::rustc-cfg=challenge_input".to_string());
+    }
+    instructions
+}
+
+/// Where a day's puzzle page lives on the site. Its input is at `/input` under it.
+pub fn puzzle_path(year: u16, day: u8) -> String {
+    format!("/{year}/day/{day}")
+}
+
+/// Pulls the title out of a puzzle page's `<h2>--- Day 1: Title ---</h2>` heading.
+pub fn puzzle_title(page: &str) -> Option<&str> {
+    let start = page.find("<h2>")? + "<h2>".len();
+    let end = start + page[start..].find("</h2>")?;
+    let heading = page[start..end].trim_matches(|c: char| c == '-' || c.is_whitespace());
+    Some(heading.split_once(": ").map_or(heading, |(_, title)| title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn reads_titles_from_puzzle_pages() {
+        let page =
+            r#"<main><article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>"#;
+        assert_eq!(puzzle_title(page), Some("Historian Hysteria"));
+        assert_eq!(puzzle_title("<p>Not found</p>"), None);
+    }
+
+    #[test]
+    fn tells_cargo_whether_challenge_inputs_are_cached() {
+        let root = std::env::temp_dir().join(format!("aoc_input_challenge_{}", std::process::id()));
+        let cache = Cache::new(&root);
+        let instructions = |day| challenge_input_instructions(&cache, 2024, day);
+        let watched = |day| {
+            instructions(day).into_iter().find_map(|i| {
+                i.strip_prefix("cargo::rerun-if-changed=")
+                    .map(PathBuf::from)
+            })
+        };
+        assert_eq!(watched(1), Some(root.clone()));
+
+        cache.store_placeholder(2024, 1).unwrap();
+        assert_eq!(watched(1), Some(cache.input_path(2024, 1)));
+        assert_eq!(watched(2), Some(root.join("2024")));
+        assert!(!instructions(1).contains(&"cargo::rustc-cfg=challenge_input".to_string()));
+
+        cache.store_input(2024, 1, "3   4\n").unwrap();
+        assert!(instructions(1).contains(&"cargo::rustc-cfg=challenge_input".to_string()));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use tiny_http::{Response, Server};
+
+use crate::{puzzle_path, Cache, Error};
+
+/// A stand-in for the site that serves canned puzzle pages and inputs at the same paths,
+/// so a [`Fetcher`](crate::Fetcher) can be pointed at it instead.
+#[derive(Debug, Default, Clone)]
+pub struct FixtureServer {
+    routes: HashMap<String, String>,
+}
+
+impl FixtureServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves every input in `cache`, and the puzzle pages saved with them.
+    pub fn from_cache(cache: &Cache) -> Result<Self, Error> {
+        let mut server = Self::new();
+        for (year, day) in cache.days()? {
+            if let Some(page) = cache.page(year, day)? {
+                server = server.with_page(year, day, page);
+            }
+            server = server.with_input(year, day, cache.input(year, day)?);
+        }
+        Ok(server)
+    }
+
+    pub fn with_page(mut self, year: u16, day: u8, page: impl Into<String>) -> Self {
+        self.routes.insert(puzzle_path(year, day), page.into());
+        self
+    }
+
+    pub fn with_input(mut self, year: u16, day: u8, input: impl Into<String>) -> Self {
+        self.routes
+            .insert(format!("{}/input", puzzle_path(year, day)), input.into());
+        self
+    }
+
+    /// Starts answering requests on a background thread. Bind to port 0 to have one picked.
+    pub fn start(self, addr: &str) -> Result<RunningServer, Error> {
+        let transport_error = |message: String| Error::Transport {
+            url: format!("http://{addr}"),
+            message,
+        };
+        let server = Arc::new(Server::http(addr).map_err(|e| transport_error(e.to_string()))?);
+        let local_addr = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| transport_error("not listening on an IP address".to_string()))?;
+        let thread = thread::spawn({
+            let server = Arc::clone(&server);
+            move || {
+                while let Ok(request) = server.recv() {
+                    let path = request.url().split('?').next().unwrap_or_default();
+                    let response = match self.routes.get(path) {
+                        Some(body) => Response::from_string(body.as_str()),
+                        None => Response::from_string("404 Not Found").with_status_code(404),
+                    };
+                    // The client hanging up early is its own problem.
+                    let _ = request.respond(response);
+                }
+            }
+        });
+        Ok(RunningServer {
+            base_url: format!("http://{local_addr}"),
+            local_addr,
+            server,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// Stops the server when dropped.
+pub struct RunningServer {
+    base_url: String,
+    local_addr: SocketAddr,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RunningServer {
+    /// What to give [`Fetcher::new`](crate::Fetcher::new) to talk to this server.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Serves until the process is killed.
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RunningServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Sets of integers kept as the ranges they cover, for puzzles about spans on a line.
+//!
+//! An [`IntervalSet`] holds its ranges sorted, apart and merged wherever they touch, so
+//! looking up a point and inserting or removing a range take logarithmic time, plus time
+//! for any ranges merged away. Ranges go in as `a..b` or `a..=b` and come out half open.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+/// The integers an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + fmt::Debug {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! integers {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*};
+}
+
+integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A range that can go into an [`IntervalSet`].
+pub trait Interval<T> {
+    /// The range as a start and an end just past it.
+    fn half_open(self) -> (T, T);
+}
+
+impl<T: Integer> Interval<T> for Range<T> {
+    fn half_open(self) -> (T, T) {
+        (self.start, self.end)
+    }
+}
+
+/// An inclusive range ending at `T`'s maximum can't be held, as its end is one past it.
+impl<T: Integer> Interval<T> for RangeInclusive<T> {
+    fn half_open(self) -> (T, T) {
+        let (start, end) = self.into_inner();
+        if end < start {
+            (start, start)
+        } else {
+            (start, end + T::ONE)
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Each range's end by its start.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Adds every integer in `range`.
+    pub fn insert(&mut self, range: impl Interval<T>) {
+        let (mut start, mut end) = range.half_open();
+        if start >= end {
+            return;
+        }
+        if let Some((&before, &before_end)) = self.ranges.range(..=start).next_back() {
+            if before_end >= start {
+                start = before;
+                end = end.max(before_end);
+            }
+        }
+        while let Some((&next, &next_end)) = self.ranges.range(start..).next() {
+            if next > end {
+                break;
+            }
+            end = end.max(next_end);
+            self.ranges.remove(&next);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes out every integer in `range`.
+    pub fn remove(&mut self, range: impl Interval<T>) {
+        let (start, end) = range.half_open();
+        if start >= end {
+            return;
+        }
+        if let Some((&before, &before_end)) = self.ranges.range(..start).next_back() {
+            if before_end > start {
+                self.ranges.insert(before, start);
+                if before_end > end {
+                    self.ranges.insert(end, before_end);
+                    return;
+                }
+            }
+        }
+        while let Some((&next, &next_end)) = self.ranges.range(start..end).next() {
+            self.ranges.remove(&next);
+            if next_end > end {
+                self.ranges.insert(end, next_end);
+            }
+        }
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.range_containing(point).is_some()
+    }
+
+    /// The whole range `point` is part of.
+    pub fn range_containing(&self, point: T) -> Option<Range<T>> {
+        self.ranges
+            .range(..=point)
+            .next_back()
+            .filter(|(_, &end)| point < end)
+            .map(|(&start, &end)| start..end)
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |total, (&start, &end)| total + (end - start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges, in order, none touching another.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The ranges of integers within `bounds` that aren't in the set, in order.
+    pub fn gaps(&self, bounds: impl Interval<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let (start, end) = bounds.half_open();
+        let mut from = start;
+        let first = self.range_containing(start).map_or(start, |r| r.start);
+        self.ranges
+            .range(first..)
+            .map(|(&start, &end)| (start, end))
+            .chain(std::iter::once((end, end)))
+            .map_while(move |(next, next_end)| {
+                if from >= end {
+                    return None;
+                }
+                let gap = from..next.min(end);
+                from = from.max(next_end);
+                Some(gap)
+            })
+            .filter(|gap| gap.start < gap.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.ranges() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        for range in self.ranges() {
+            for overlap in other.overlapping(range.clone()) {
+                intersection.insert(range.start.max(overlap.start)..range.end.min(overlap.end));
+            }
+        }
+        intersection
+    }
+
+    /// The ranges that share at least one integer with `range`.
+    fn overlapping(&self, range: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let first = self
+            .range_containing(range.start)
+            .map_or(range.start, |r| r.start);
+        self.ranges
+            .range(first..range.end)
+            .map(|(&start, &end)| start..end)
+    }
+}
+
+impl<T: Integer, R: Interval<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Integer, R: Interval<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+/// The ranges, like `{1..3, 5..6}`.
+impl<T: Integer> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.ranges()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn merges_ranges_that_touch() {
+        let mut s = set(&[1..3, 7..9]);
+        s.insert(3..4);
+        assert_eq!(s.ranges().collect::<Vec<_>>(), [1..4, 7..9]);
+        s.insert(0..=20);
+        assert_eq!(s.ranges().count(), 1);
+        assert_eq!(s.range_containing(0), Some(0..21));
+        let (start, end) = (5, 2);
+        s.insert(start..end);
+        assert_eq!(s.len(), 21);
+    }
+
+    #[test]
+    fn removes_from_the_middle() {
+        let mut s = set(&[0..10, 12..15, 20..30]);
+        s.remove(3..=4);
+        s.remove(8..22);
+        assert_eq!(s.ranges().collect::<Vec<_>>(), [0..3, 5..8, 22..30]);
+        assert_eq!(s.len(), 14);
+        s.remove(-5..100);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn answers_point_queries() {
+        let s = set(&[-4..0, 3..5]);
+        assert!(s.contains(-4));
+        assert!(!s.contains(0));
+        assert_eq!(s.range_containing(4), Some(3..5));
+        assert_eq!(s.range_containing(5), None);
+    }
+
+    #[test]
+    fn finds_gaps_within_bounds() {
+        let s = set(&[0..3, 5..6, 8..12]);
+        assert_eq!(s.gaps(1..10).collect::<Vec<_>>(), [3..5, 6..8]);
+        assert_eq!(
+            s.gaps(-2..=13).collect::<Vec<_>>(),
+            [-2..0, 3..5, 6..8, 12..14]
+        );
+        assert_eq!(s.gaps(9..11).count(), 0);
+        let empty = IntervalSet::<u8>::new();
+        let mut gaps = empty.gaps(2..4);
+        assert_eq!(gaps.next(), Some(2..4));
+        assert_eq!(gaps.next(), None);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..5, 10..15]);
+        let b = set(&[3..12, 20..21]);
+        assert_eq!(a.union(&b), set(&[0..15, 20..21]));
+        assert_eq!(a.intersection(&b), set(&[3..5, 10..12]));
+        assert_eq!(a.difference(&b), set(&[0..3, 12..15]));
+        assert_eq!(format!("{:?}", a.difference(&b)), "{0..3, 12..15}");
+    }
+}
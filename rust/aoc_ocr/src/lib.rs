@@ -0,0 +1,111 @@
+//! Reading the block letters some puzzles draw as their answer.
+//!
+//! [`read`] takes the picture as rows of lit and unlit pixels. Letters are split apart at the
+//! blank columns between them and matched against the font for the picture's height: six rows
+//! for most years, ten for 2018 day 10.
+mod font;
+
+use aoc_core::Error;
+
+/// The letters in a picture, left to right. Rows can be ragged, with anything missing unlit,
+/// and blank rows above or below the letters are ignored.
+pub fn read<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, Error> {
+    let rows: Vec<&[bool]> = rows.iter().map(AsRef::as_ref).collect();
+    let lit_rows = |row: &&[bool]| row.contains(&true);
+    let top = rows.iter().position(lit_rows).unwrap_or(0);
+    let bottom = rows.iter().rposition(lit_rows).map_or(0, |b| b + 1);
+    let rows = &rows[top..bottom.max(top)];
+    let font = match rows.len() {
+        0 => return Err(Error::no_answer("there are no letters to read")),
+        6 => font::SMALL,
+        10 => font::LARGE,
+        height => {
+            return Err(Error::no_answer(format!(
+                "there's no font with letters {height} rows tall"
+            )))
+        }
+    };
+    let lit = |x: usize, y: usize| rows[y].get(x) == Some(&true);
+    let blank_column = |x: usize| (0..rows.len()).all(|y| !lit(x, y));
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !blank_column(x) {
+            x += 1;
+        }
+        let glyph = (0..rows.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        match font.iter().find(|(_, shape)| *shape == glyph) {
+            Some(&(letter, _)) => letters.push(letter),
+            None => {
+                return Err(Error::no_answer(format!(
+                    "couldn't read the letter at column {start}:\n{glyph}"
+                )))
+            }
+        }
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(art: &str) -> Vec<Vec<bool>> {
+        art.lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let art = "\
+            ###..#..#..##..####..##....##.###..###..\n\
+            #..#.#.#..#..#....#.#..#....#.#..#.#..#.\n\
+            #..#.##...#..#...#..#..#....#.###..#..#.\n\
+            ###..#.#..####..#...####....#.#..#.###..\n\
+            #.#..#.#..#..#.#....#..#.#..#.#..#.#.#..\n\
+            #..#.#..#.#..#.####.#..#..##..###..#..#.\n";
+        assert_eq!(read(&pixels(art)), Ok("RKAZAJBR".to_string()));
+    }
+
+    #[test]
+    fn reads_large_letters_with_a_margin() {
+        let mut art = String::from("..........\n");
+        for (l, z) in font::LARGE[9].1.lines().zip(font::LARGE[14].1.lines()) {
+            art += &format!("..{l}..{z}\n");
+        }
+        assert_eq!(read(&pixels(&art)), Ok("LZ".to_string()));
+    }
+
+    #[test]
+    fn reports_what_it_cannot_read() {
+        assert_eq!(
+            read(&pixels("#.#\n.#.\n#.#\n.#.\n#.#\n.#.")),
+            Err(Error::no_answer(
+                "couldn't read the letter at column 0:\n#.#\n.#.\n#.#\n.#.\n#.#\n.#."
+            ))
+        );
+        assert_eq!(
+            read(&pixels("#\n#\n#")),
+            Err(Error::no_answer("there's no font with letters 3 rows tall"))
+        );
+        assert_eq!(
+            read(&pixels("...\n...")),
+            Err(Error::no_answer("there are no letters to read"))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::{rgb_pixels, Backend, Raster};
+
+/// Writes the frames as one looping GIF. Every frame has to be the size of the first.
+pub struct AnimatedGif<W: Write> {
+    out: Option<W>,
+    encoder: Option<Encoder<W>>,
+    size: (u16, u16),
+    scale: usize,
+    delay: Duration,
+}
+
+impl<W: Write> AnimatedGif<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: Some(out),
+            encoder: None,
+            size: (0, 0),
+            scale: 1,
+            delay: Duration::from_millis(50),
+        }
+    }
+
+    /// How many pixels wide and high each cell is. Defaults to 1.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// How long to show each frame, to the nearest hundredth of a second. Defaults to 50ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl<W: Write> Backend for AnimatedGif<W> {
+    fn frame(&mut self, frame: &Raster) -> io::Result<()> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "frame too big for a GIF");
+        let width = u16::try_from(frame.width() * self.scale).map_err(|_| too_big())?;
+        let height = u16::try_from(frame.height() * self.scale).map_err(|_| too_big())?;
+        if let Some(out) = self.out.take() {
+            let mut encoder = Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+            encoder
+                .set_repeat(Repeat::Infinite)
+                .map_err(io::Error::other)?;
+            self.encoder = Some(encoder);
+            self.size = (width, height);
+        }
+        let encoder = self.encoder.as_mut().expect("made on the first frame");
+        if (width, height) != self.size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a {width} by {height} frame doesn't fit a {} by {} GIF",
+                    self.size.0, self.size.1
+                ),
+            ));
+        }
+        let mut gif_frame =
+            Frame::from_rgb_speed(width, height, &rgb_pixels(frame, self.scale), 10);
+        gif_frame.delay = (self.delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.encoder.take() {
+            Some(encoder) => encoder.into_inner()?.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Colour};
+    use aoc_grid::Grid;
+
+    #[test]
+    fn writes_one_gif_of_same_sized_frames() {
+        let mut out = Vec::new();
+        let mut gif = AnimatedGif::new(&mut out).scale(2);
+        let frame = |colour| Grid::filled(3, 2, Cell::new('#', colour));
+        gif.frame(&frame(Colour::WHITE)).unwrap();
+        gif.frame(&frame(Colour::BLACK)).unwrap();
+        assert_eq!(
+            gif.frame(&Grid::filled(1, 1, Cell::default()))
+                .unwrap_err()
+                .to_string(),
+            "a 2 by 2 frame doesn't fit a 6 by 4 GIF"
+        );
+        gif.finish().unwrap();
+        drop(gif);
+        assert_eq!(&out[..6], b"GIF89a");
+        assert_eq!(out.last(), Some(&0x3b));
+    }
+}
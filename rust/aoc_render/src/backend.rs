@@ -0,0 +1,28 @@
+use std::io;
+
+use crate::Raster;
+
+/// Somewhere to send frames.
+pub trait Backend {
+    fn frame(&mut self, frame: &Raster) -> io::Result<()>;
+
+    /// Called once after the last frame, to write out anything still held back.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps every frame.
+impl Backend for Vec<Raster> {
+    fn frame(&mut self, frame: &Raster) -> io::Result<()> {
+        self.push(frame.clone());
+        Ok(())
+    }
+}
+
+/// A puzzle whose solution can be watched.
+pub trait Animate {
+    /// Runs the solution, passing `backend` a frame at each step. Finishing the backend is
+    /// left to the caller.
+    fn animate(&self, backend: &mut dyn Backend) -> io::Result<()>;
+}
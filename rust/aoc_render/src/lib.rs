@@ -0,0 +1,17 @@
+//! Watching a simulation run, one frame at a time.
+//!
+//! A state that can be drawn implements [`Render`], giving a [`Raster`] of coloured
+//! characters. A puzzle that can be watched implements [`Animate`], passing a frame at each
+//! step to a [`Backend`]: a [`Terminal`] animation, a [`PngSequence`] or an [`AnimatedGif`].
+//! A `Vec<Raster>` is a backend too, which keeps every frame for tests.
+mod animated_gif;
+mod backend;
+mod png_sequence;
+mod raster;
+mod terminal;
+
+pub use animated_gif::AnimatedGif;
+pub use backend::{Animate, Backend};
+pub use png_sequence::PngSequence;
+pub use raster::{rgb_pixels, Cell, Colour, Raster, Render};
+pub use terminal::Terminal;
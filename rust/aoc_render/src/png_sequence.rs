@@ -0,0 +1,50 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+
+use crate::{rgb_pixels, Backend, Raster};
+
+/// Writes each frame to its own numbered PNG in a directory, `00000.png` onwards.
+pub struct PngSequence {
+    directory: PathBuf,
+    scale: usize,
+    written: usize,
+}
+
+impl PngSequence {
+    /// The directory is made when the first frame arrives, if it isn't there already.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            scale: 1,
+            written: 0,
+        }
+    }
+
+    /// How many pixels wide and high each cell is. Defaults to 1.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+impl Backend for PngSequence {
+    fn frame(&mut self, frame: &Raster) -> io::Result<()> {
+        if self.written == 0 {
+            fs::create_dir_all(&self.directory)?;
+        }
+        let path = self.directory.join(format!("{:05}.png", self.written));
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            (frame.width() * self.scale) as u32,
+            (frame.height() * self.scale) as u32,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&rgb_pixels(frame, self.scale))?;
+        writer.finish()?;
+        self.written += 1;
+        Ok(())
+    }
+}
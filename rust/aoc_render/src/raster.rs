@@ -0,0 +1,96 @@
+use std::fmt;
+
+use aoc_grid::Grid;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Whether dark text shows up better on this than light text.
+    pub(crate) fn is_light(self) -> bool {
+        299 * u32::from(self.r) + 587 * u32::from(self.g) + 114 * u32::from(self.b) > 128_000
+    }
+}
+
+/// One square of a frame: the character the puzzle would print there, and a colour for the
+/// backends that draw pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub symbol: char,
+    pub colour: Colour,
+}
+
+impl Cell {
+    pub const fn new(symbol: char, colour: Colour) -> Self {
+        Self { symbol, colour }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new(' ', Colour::BLACK)
+    }
+}
+
+/// Just the symbol, so a [`Raster`] prints as the puzzle draws it.
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+/// A frame, row by row.
+pub type Raster = Grid<Cell>;
+
+/// A state that can be drawn as a frame.
+pub trait Render {
+    fn render(&self) -> Raster;
+}
+
+/// The frame as RGB bytes, row by row, with each cell a `scale` by `scale` square.
+pub fn rgb_pixels(frame: &Raster, scale: usize) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(frame.width() * frame.height() * scale * scale * 3);
+    for row in frame.rows() {
+        for _ in 0..scale {
+            for cell in row {
+                let Colour { r, g, b } = cell.colour;
+                for _ in 0..scale {
+                    pixels.extend([r, g, b]);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prints_symbols_and_scales_pixels() {
+        let red = Colour::new(255, 0, 0);
+        let frame = Grid::new(2, 1, vec![Cell::new('#', red), Cell::default()]);
+        assert_eq!(frame.to_string(), "# \n");
+        assert_eq!(rgb_pixels(&frame, 1), [255, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            rgb_pixels(&frame, 2),
+            [
+                [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0],
+                [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0]
+            ]
+            .concat()
+        );
+    }
+}
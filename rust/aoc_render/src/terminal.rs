@@ -0,0 +1,93 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Backend, Colour, Raster};
+
+/// Redraws each frame in place with ANSI escape codes, each cell's colour behind its symbol.
+pub struct Terminal<W> {
+    out: W,
+    delay: Duration,
+    started: bool,
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            delay: Duration::from_millis(50),
+            started: false,
+        }
+    }
+
+    /// How long to leave each frame up. Defaults to 50ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl<W: Write> Backend for Terminal<W> {
+    fn frame(&mut self, frame: &Raster) -> io::Result<()> {
+        if !self.started {
+            write!(self.out, "\x1b[2J")?;
+            self.started = true;
+        }
+        write!(self.out, "\x1b[H")?;
+        for row in frame.rows() {
+            let mut current = None;
+            for cell in row {
+                if current != Some(cell.colour) {
+                    let Colour { r, g, b } = cell.colour;
+                    let text = if cell.colour.is_light() { 0 } else { 255 };
+                    write!(
+                        self.out,
+                        "\x1b[48;2;{r};{g};{b}m\x1b[38;2;{text};{text};{text}m"
+                    )?;
+                    current = Some(cell.colour);
+                }
+                write!(self.out, "{}", cell.symbol)?;
+            }
+            writeln!(self.out, "\x1b[0m")?;
+        }
+        self.out.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        write!(self.out, "\x1b[0m")?;
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+    use aoc_grid::Grid;
+
+    #[test]
+    fn colours_runs_of_cells_once() {
+        let mut out = Vec::new();
+        let frame = Grid::new(
+            3,
+            1,
+            vec![
+                Cell::new('#', Colour::WHITE),
+                Cell::new('#', Colour::WHITE),
+                Cell::new('.', Colour::BLACK),
+            ],
+        );
+        let mut terminal = Terminal::new(&mut out).delay(Duration::ZERO);
+        terminal.frame(&frame).unwrap();
+        terminal.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[H\
+             \x1b[48;2;255;255;255m\x1b[38;2;0;0;0m##\
+             \x1b[48;2;0;0;0m\x1b[38;2;255;255;255m.\x1b[0m\n\
+             \x1b[0m"
+        );
+    }
+}
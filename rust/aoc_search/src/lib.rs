@@ -0,0 +1,12 @@
+//! Shortest paths over any state space described by a neighbours closure.
+//!
+//! [`bfs`] is for moves that all cost the same and [`dijkstra`] for weighted ones. Giving
+//! either a [`Search::heuristic`] turns it into A*. A search starts from one or more states,
+//! then either stops at the nearest goal with [`Search::shortest`] or reaches everything with
+//! [`Search::explore`]. Both keep every predecessor on a cheapest path, so all the optimal
+//! routes can be walked back, not just one.
+mod paths;
+mod search;
+
+pub use paths::Paths;
+pub use search::{bfs, dijkstra, Cost, Search, Shortest, State, TieBreak};
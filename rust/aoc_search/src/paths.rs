@@ -0,0 +1,64 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Cost, State};
+
+/// The cost to each state a search reached, and every predecessor that reaches it that
+/// cheaply.
+///
+/// After [`Search::shortest`](crate::Search::shortest) this is only settled for states no
+/// costlier than the goal.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    pub(crate) costs: HashMap<S, C>,
+    pub(crate) predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: State, C: Cost> Paths<S, C> {
+    pub(crate) fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn costs(&self) -> &HashMap<S, C> {
+        &self.costs
+    }
+
+    /// The states one step before `state` on a cheapest path to it, in the order the search
+    /// expanded them. Starts have none.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One cheapest path from a start to `end`, inclusive, following the first predecessor at
+    /// each step.
+    pub fn path_to(&self, end: &S) -> Option<Vec<S>> {
+        self.costs.get(end)?;
+        let mut path = vec![end.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state on any cheapest path to any of `ends`, including the starts and the ends.
+    pub fn on_paths_to(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut on_paths = HashSet::new();
+        let mut to_visit: Vec<S> = ends
+            .into_iter()
+            .filter(|end| self.costs.contains_key(end))
+            .collect();
+        while let Some(state) = to_visit.pop() {
+            if on_paths.insert(state.clone()) {
+                to_visit.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        on_paths
+    }
+}
@@ -0,0 +1,390 @@
+use std::cmp::Ordering;
+use std::collections::{hash_map::Entry, BinaryHeap};
+use std::hash::Hash;
+use std::iter::Map;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+use crate::Paths;
+
+/// Anything a search can visit. It's kept in hash maps, and ordered for
+/// [`TieBreak::Smallest`].
+pub trait State: Clone + Eq + Hash + Ord {}
+
+impl<T: Clone + Eq + Hash + Ord> State for T {}
+
+/// The cost of a path, where `Default` is nothing at all. Moves shouldn't cost less than
+/// nothing.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// Which state to take next when several are equally cheap. This decides which goal
+/// [`Search::shortest`] reports first and the order of [`Paths::predecessors`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The one found first.
+    #[default]
+    FirstFound,
+    /// The smallest by `Ord`, which for an `aoc_geometry::Vec2` is reading order.
+    Smallest,
+}
+
+/// A search that hasn't run yet. Build one with [`bfs`] or [`dijkstra`].
+pub struct Search<S, C, N, H = fn(&S) -> C> {
+    neighbours: N,
+    heuristic: H,
+    starts: Vec<S>,
+    tie_break: TieBreak,
+    cost: PhantomData<C>,
+}
+
+/// The cheapest way to a goal.
+#[derive(Debug, Clone)]
+pub struct Shortest<S, C> {
+    pub cost: C,
+    /// Every goal state that cost reaches, in the order they were taken.
+    pub ends: Vec<S>,
+    pub paths: Paths<S, C>,
+}
+
+/// A search where every move costs 1. `neighbours` gives the states one move on from a state.
+#[allow(clippy::type_complexity)]
+pub fn bfs<S, N, I>(
+    mut neighbours: N,
+) -> Search<S, usize, impl FnMut(&S) -> Map<I::IntoIter, fn(S) -> (S, usize)>>
+where
+    S: State,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    fn one_move<S>(state: S) -> (S, usize) {
+        (state, 1)
+    }
+    dijkstra(move |state: &S| {
+        neighbours(state)
+            .into_iter()
+            .map(one_move as fn(S) -> (S, usize))
+    })
+}
+
+/// A search where `neighbours` gives the states one move on from a state with what each move
+/// costs.
+pub fn dijkstra<S, C, N, I>(neighbours: N) -> Search<S, C, N>
+where
+    S: State,
+    C: Cost,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    Search {
+        neighbours,
+        heuristic: |_| C::default(),
+        starts: Vec::new(),
+        tie_break: TieBreak::default(),
+        cost: PhantomData,
+    }
+}
+
+impl<S, C, N, H, I> Search<S, C, N, H>
+where
+    S: State,
+    C: Cost,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+{
+    pub fn start(mut self, state: S) -> Self {
+        self.starts.push(state);
+        self
+    }
+
+    /// Searches from all of `states` at once, so each state's cost is from its nearest start.
+    pub fn starts(mut self, states: impl IntoIterator<Item = S>) -> Self {
+        self.starts.extend(states);
+        self
+    }
+
+    /// Turns the search into A*. `heuristic` must never overestimate the cost to a goal, and
+    /// for [`Search::shortest`] to find every cheapest path it mustn't drop by more than a
+    /// move costs.
+    pub fn heuristic<H2: FnMut(&S) -> C>(self, heuristic: H2) -> Search<S, C, N, H2> {
+        Search {
+            neighbours: self.neighbours,
+            heuristic,
+            starts: self.starts,
+            tie_break: self.tie_break,
+            cost: PhantomData,
+        }
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Searches until the cheapest state matching `goal`, carrying on only to find any other
+    /// goals that cost the same. `None` when no goal can be reached.
+    pub fn shortest(mut self, mut goal: impl FnMut(&S) -> bool) -> Option<Shortest<S, C>> {
+        let mut ends = Vec::new();
+        let (paths, cost) = self.run(|state| {
+            let reached = goal(state);
+            if reached {
+                ends.push(state.clone());
+            }
+            reached
+        });
+        Some(Shortest {
+            cost: cost?,
+            ends,
+            paths,
+        })
+    }
+
+    /// Searches every state reachable from the starts.
+    pub fn explore(mut self) -> Paths<S, C> {
+        self.run(|_| false).0
+    }
+
+    /// Takes states cheapest first, calling `reached` on each. Once that says a goal has been
+    /// reached, only states with the same cost are taken, and goals aren't searched beyond.
+    fn run(&mut self, mut reached: impl FnMut(&S) -> bool) -> (Paths<S, C>, Option<C>) {
+        let mut paths = Paths::new();
+        let mut frontier = Frontier {
+            heap: BinaryHeap::new(),
+            found: 0,
+            tie_break: self.tie_break,
+        };
+        for start in std::mem::take(&mut self.starts) {
+            if paths.costs.insert(start.clone(), C::default()).is_none() {
+                let priority = (self.heuristic)(&start);
+                frontier.push(priority, C::default(), start);
+            }
+        }
+
+        let mut goal_cost = None;
+        while let Some(Step {
+            priority,
+            cost,
+            state,
+            ..
+        }) = frontier.heap.pop()
+        {
+            if goal_cost.is_some_and(|goal_cost| priority > goal_cost) {
+                break;
+            }
+            if paths.costs.get(&state).is_some_and(|&best| cost > best) {
+                continue;
+            }
+            if goal_cost.is_none_or(|goal_cost| cost == goal_cost) && reached(&state) {
+                goal_cost = Some(cost);
+                continue;
+            }
+            for (next, step_cost) in (self.neighbours)(&state) {
+                let next_cost = cost + step_cost;
+                match paths.costs.entry(next.clone()) {
+                    Entry::Occupied(mut best) => match next_cost.cmp(best.get()) {
+                        Ordering::Less => {
+                            best.insert(next_cost);
+                        }
+                        Ordering::Equal => {
+                            paths
+                                .predecessors
+                                .entry(next)
+                                .or_default()
+                                .push(state.clone());
+                            continue;
+                        }
+                        Ordering::Greater => continue,
+                    },
+                    Entry::Vacant(best) => {
+                        best.insert(next_cost);
+                    }
+                }
+                paths.predecessors.insert(next.clone(), vec![state.clone()]);
+                let priority = next_cost + (self.heuristic)(&next);
+                frontier.push(priority, next_cost, next);
+            }
+        }
+        (paths, goal_cost)
+    }
+}
+
+struct Frontier<S, C> {
+    heap: BinaryHeap<Step<S, C>>,
+    found: usize,
+    tie_break: TieBreak,
+}
+
+impl<S: State, C: Cost> Frontier<S, C> {
+    fn push(&mut self, priority: C, cost: C, state: S) {
+        self.heap.push(Step {
+            priority,
+            found: self.found,
+            tie_break: self.tie_break,
+            cost,
+            state,
+        });
+        self.found += 1;
+    }
+}
+
+/// A state waiting to be taken. The heap is a max-heap, so the next one to take is the
+/// greatest.
+struct Step<S, C> {
+    priority: C,
+    found: usize,
+    tie_break: TieBreak,
+    cost: C,
+    state: S,
+}
+
+impl<S: State, C: Cost> Ord for Step<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| match self.tie_break {
+                TieBreak::FirstFound => Ordering::Equal,
+                TieBreak::Smallest => other.state.cmp(&self.state),
+            })
+            .then_with(|| other.found.cmp(&self.found))
+    }
+}
+
+impl<S: State, C: Cost> PartialOrd for Step<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: State, C: Cost> PartialEq for Step<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: State, C: Cost> Eq for Step<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use aoc_geometry::Vec2;
+
+    use super::*;
+
+    type Point = Vec2<i32>;
+
+    /// Open squares are `.`, walls `#`.
+    fn open_squares(map: &str) -> HashSet<Point> {
+        map.lines()
+            .zip(0..)
+            .flat_map(|(line, y)| {
+                line.chars()
+                    .zip(0..)
+                    .filter(|&(c, _)| c != '#')
+                    .map(move |(_, x)| Point::new(x, y))
+            })
+            .collect()
+    }
+
+    const MAZE: &str = "\
+.....
+.###.
+.#...
+.#.#.
+...#.
+";
+
+    #[test]
+    fn bfs_counts_moves() {
+        let open = open_squares(MAZE);
+        let search = || bfs(|p: &Point| p.neighbours_4().into_iter().filter(|n| open.contains(n)));
+        let shortest = search()
+            .start(Point::new(0, 0))
+            .shortest(|&p| p == Point::new(4, 4))
+            .unwrap();
+        assert_eq!(shortest.cost, 8);
+        assert_eq!(shortest.paths.path_to(&Point::new(4, 4)).unwrap().len(), 9);
+
+        let everywhere = search().start(Point::new(0, 0)).explore();
+        assert_eq!(everywhere.costs().len(), open.len());
+        assert_eq!(everywhere.cost(&Point::new(2, 3)), Some(7));
+        assert!(search()
+            .start(Point::new(0, 0))
+            .shortest(|&p| p == Point::new(1, 1))
+            .is_none());
+    }
+
+    #[test]
+    fn dijkstra_and_a_star_agree() {
+        // Moving right is cheap and moving down is dear.
+        let neighbours = |p: &Point| {
+            [(Point::new(p.x + 1, p.y), 1), (Point::new(p.x, p.y + 1), 5)]
+                .into_iter()
+                .filter(|(n, _)| n.x <= 6 && n.y <= 6)
+        };
+        let goal = Point::new(6, 6);
+        let plain = dijkstra(neighbours)
+            .start(Point::ZERO)
+            .shortest(|&p| p == goal)
+            .unwrap();
+        let guided = dijkstra(neighbours)
+            .heuristic(|p: &Point| p.manhattan(goal))
+            .start(Point::ZERO)
+            .shortest(|&p| p == goal)
+            .unwrap();
+        assert_eq!(plain.cost, 36);
+        assert_eq!(guided.cost, 36);
+        assert!(guided.paths.costs().len() <= plain.paths.costs().len());
+    }
+
+    #[test]
+    fn starts_from_many_places() {
+        let open = open_squares(MAZE);
+        let paths = bfs(|p: &Point| p.neighbours_4().into_iter().filter(|n| open.contains(n)))
+            .starts([Point::new(0, 0), Point::new(4, 4)])
+            .explore();
+        assert_eq!(paths.cost(&Point::new(4, 0)), Some(4));
+        assert_eq!(paths.cost(&Point::new(0, 4)), Some(4));
+        assert_eq!(paths.cost(&Point::new(2, 3)), Some(5));
+    }
+
+    #[test]
+    fn finds_every_cheapest_path() {
+        // On an open 3 by 3 square every monotone path corner to corner is cheapest.
+        let paths = bfs(|p: &Point| {
+            p.neighbours_4()
+                .into_iter()
+                .filter(|n| (0..3).contains(&n.x) && (0..3).contains(&n.y))
+        })
+        .start(Point::ZERO)
+        .explore();
+        let corner = Point::new(2, 2);
+        assert_eq!(paths.on_paths_to([corner]).len(), 9);
+        assert_eq!(paths.predecessors(&corner).len(), 2);
+        assert_eq!(paths.on_paths_to([Point::new(2, 0)]).len(), 3);
+    }
+
+    #[test]
+    fn breaks_ties_in_reading_order() {
+        let targets = [Point::new(3, 1), Point::new(1, 3), Point::new(2, 2)];
+        let search = |tie_break| {
+            bfs(|p: &Point| p.neighbours_4())
+                .tie_break(tie_break)
+                .start(Point::ZERO)
+                .shortest(|p| targets.contains(p))
+                .unwrap()
+        };
+        let smallest = search(TieBreak::Smallest);
+        assert_eq!(smallest.cost, 4);
+        assert_eq!(
+            smallest.ends,
+            [Point::new(3, 1), Point::new(2, 2), Point::new(1, 3)]
+        );
+        let mut first_found = search(TieBreak::FirstFound).ends;
+        first_found.sort();
+        assert_eq!(first_found, smallest.ends);
+    }
+}